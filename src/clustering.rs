@@ -0,0 +1,46 @@
+//! Clustering algorithms and the types required to use them.
+//!
+//! # Example
+//! ```
+//! use auto_palette::clustering::{DistanceMetric, Fit, Initializer, Kmeans, KmeansParams, Point2};
+//! use rand::rngs::StdRng;
+//! use rand::SeedableRng;
+//!
+//! let dataset = vec![
+//!     Point2(0.0, 0.0),
+//!     Point2(0.5, 0.0),
+//!     Point2(0.0, 0.5),
+//!     Point2(8.0, 8.0),
+//!     Point2(8.5, 8.0),
+//!     Point2(8.0, 8.5),
+//! ];
+//! let initializer = Initializer::KmeansPlusPlus(StdRng::seed_from_u64(0));
+//! let params = KmeansParams::new(2, DistanceMetric::SquaredEuclidean, initializer);
+//! let kmeans: Kmeans<f64, Point2<f64>> = Kmeans::fit(&dataset, &params);
+//!
+//! let labels = kmeans.labels();
+//! assert_eq!(labels[0], labels[1]);
+//! assert_eq!(labels[0], labels[2]);
+//! assert_eq!(labels[3], labels[4]);
+//! assert_eq!(labels[3], labels[5]);
+//! assert_ne!(labels[0], labels[3]);
+//! ```
+pub use crate::math::clustering::dbscan::algorithm::DBSCAN;
+pub use crate::math::clustering::dbscan::params::Params as DBSCANParams;
+pub use crate::math::clustering::dbscan::stats::Stats as DBSCANStats;
+pub use crate::math::clustering::hierarchical::algorithm::HierarchicalClustering;
+pub use crate::math::clustering::kmeans::algorithm::Kmeans;
+pub use crate::math::clustering::kmeans::init::Initializer;
+pub use crate::math::clustering::kmeans::params::KmeansParams;
+pub use crate::math::clustering::metrics::{adjusted_rand_index, silhouette_score};
+pub use crate::math::clustering::spectral::algorithm::SpectralClustering;
+pub use crate::math::clustering::spectral::params::SpectralParams;
+pub use crate::math::clustering::traits::Fit;
+pub use crate::math::distance::cylindrical::CylindricalDistance;
+pub use crate::math::distance::mahalanobis::MahalanobisDistance;
+pub use crate::math::distance::measure::DistanceMeasure;
+pub use crate::math::distance::metric::{DistanceMetric, ParseDistanceMetricError};
+pub use crate::math::distance::projected::ProjectedDistance;
+pub use crate::math::number::{Float, Number};
+pub use crate::math::point::{Point, Point2, Point3, Point5};
+pub use crate::math::preprocessing::{MinMaxScaler, StandardScaler};
@@ -0,0 +1,191 @@
+use crate::color::lab::Lab;
+use crate::color::rgba::Rgba;
+use crate::color::white_point::WhitePoint;
+use crate::color::xyz::XYZ;
+use crate::math::number::Float;
+use core::fmt::Debug;
+
+/// Trait for colors that can be compared within a tolerance.
+pub trait ApproxEq {
+    /// The type of the tolerance.
+    type Epsilon: Copy + Debug;
+
+    /// Return the default tolerance, which absorbs the rounding errors of the conversions of this
+    /// crate for both `f32` and `f64`.
+    #[must_use]
+    fn default_epsilon() -> Self::Epsilon;
+
+    /// Return whether every channel of this color is within `epsilon` of the other color.
+    #[must_use]
+    fn approx_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool;
+}
+
+/// Assert that two colors are equal within a tolerance using [`ApproxEq`].
+///
+/// The tolerance defaults to [`ApproxEq::default_epsilon`] and can be given as the third argument.
+///
+/// # Panics
+/// Panics with both colors and the tolerance if the colors are not approximately equal.
+///
+/// # Examples
+/// ```
+/// use auto_palette::color::{assert_color_eq, Lab, Rgba, XYZ};
+///
+/// let rgba = Rgba::new(255, 128, 0, 255);
+/// let lab: Lab<f32> = Lab::from(&XYZ::from(&rgba));
+/// assert_color_eq!(Rgba::from(&XYZ::from(&lab)), rgba);
+/// assert_color_eq!(lab, Lab::new(67.05, 42.82, 74.02), 0.01);
+/// ```
+#[macro_export]
+macro_rules! assert_color_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => $crate::assert_color_eq!(
+                *left,
+                *right,
+                $crate::color::default_epsilon_of(left)
+            ),
+        }
+    };
+    ($left:expr, $right:expr, $epsilon:expr $(,)?) => {
+        match (&$left, &$right, $epsilon) {
+            (left, right, epsilon) => {
+                if !$crate::color::ApproxEq::approx_eq(left, right, epsilon) {
+                    panic!(
+                        "assertion `left ≈ right` failed\n   left: {:?}\n  right: {:?}\nepsilon: {:?}",
+                        left, right, epsilon
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Return the default tolerance of the type of the given color.
+#[doc(hidden)]
+#[inline]
+#[must_use]
+pub fn default_epsilon_of<T: ApproxEq>(_: &T) -> T::Epsilon {
+    T::default_epsilon()
+}
+
+#[inline]
+fn channel_eq<F: Float>(a: F, b: F, epsilon: F) -> bool {
+    (a - b).abs() <= epsilon
+}
+
+impl ApproxEq for Rgba {
+    type Epsilon = u8;
+
+    fn default_epsilon() -> u8 {
+        1
+    }
+
+    fn approx_eq(&self, other: &Self, epsilon: u8) -> bool {
+        self.r.abs_diff(other.r) <= epsilon
+            && self.g.abs_diff(other.g) <= epsilon
+            && self.b.abs_diff(other.b) <= epsilon
+            && self.a.abs_diff(other.a) <= epsilon
+    }
+}
+
+impl<F, W> ApproxEq for Lab<F, W>
+where
+    F: Float,
+    W: WhitePoint<F>,
+{
+    type Epsilon = F;
+
+    fn default_epsilon() -> F {
+        F::from_f64(1e-3)
+    }
+
+    fn approx_eq(&self, other: &Self, epsilon: F) -> bool {
+        channel_eq(self.l, other.l, epsilon)
+            && channel_eq(self.a, other.a, epsilon)
+            && channel_eq(self.b, other.b, epsilon)
+    }
+}
+
+impl<F, W> ApproxEq for XYZ<F, W>
+where
+    F: Float,
+    W: WhitePoint<F>,
+{
+    type Epsilon = F;
+
+    fn default_epsilon() -> F {
+        F::from_f64(1e-5)
+    }
+
+    fn approx_eq(&self, other: &Self, epsilon: F) -> bool {
+        channel_eq(self.x, other.x, epsilon)
+            && channel_eq(self.y, other.y, epsilon)
+            && channel_eq(self.z, other.z, epsilon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::approx_eq;
+    use crate::color::white_point::D65;
+
+    #[test]
+    fn approx_eq_should_compare_rgba_within_tolerance() {
+        let color = Rgba::new(255, 128, 0, 255);
+        assert!(approx_eq(&color, &Rgba::new(254, 129, 1, 255), 1));
+        assert!(!approx_eq(&color, &Rgba::new(250, 128, 0, 255), 1));
+        assert!(!approx_eq(&color, &Rgba::new(255, 128, 0, 0), 1));
+    }
+
+    #[test]
+    fn approx_eq_should_compare_lab_within_tolerance() {
+        let lab: Lab<f64> = Lab::new(53.23, 80.11, 67.22);
+        assert!(approx_eq(
+            &lab,
+            &Lab::new(53.2300001, 80.1099999, 67.22),
+            1e-6
+        ));
+        assert!(!approx_eq(&lab, &Lab::new(53.23, 80.11, 60.0), 1e-6));
+    }
+
+    #[test]
+    fn approx_eq_should_compare_xyz_within_tolerance() {
+        let xyz: XYZ<f64, D65> = XYZ::new(0.412391, 0.212639, 0.019331);
+        assert!(approx_eq(
+            &xyz,
+            &XYZ::new(0.41239100000000004, 0.212639, 0.01933100000000002),
+            1e-9
+        ));
+        assert!(!approx_eq(
+            &xyz,
+            &XYZ::new(0.357584, 0.715169, 0.119195),
+            1e-9
+        ));
+    }
+
+    #[test]
+    fn assert_color_eq_should_pass_for_near_equal_colors() {
+        let rgba = Rgba::new(255, 128, 0, 255);
+        let xyz: XYZ<f32, D65> = XYZ::from(&rgba);
+        let lab: Lab<f32> = Lab::from(&xyz);
+        assert_color_eq!(Rgba::from(&XYZ::from(&lab)), rgba);
+        assert_color_eq!(XYZ::<f32, D65>::from(&lab), xyz);
+        assert_color_eq!(rgba, Rgba::new(250, 130, 0, 255), 5);
+        assert_color_eq!(lab, Lab::new(67.05, 42.82, 74.02), 0.01);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion `left ≈ right` failed")]
+    fn assert_color_eq_should_fail_for_different_rgba() {
+        assert_color_eq!(Rgba::new(255, 0, 0, 255), Rgba::new(0, 0, 255, 255));
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion `left ≈ right` failed")]
+    fn assert_color_eq_should_fail_for_different_lab() {
+        let lab: Lab<f64> = Lab::new(53.23, 80.11, 67.22);
+        assert_color_eq!(lab, Lab::new(53.23, 80.11, 60.0), 1.0);
+    }
+}
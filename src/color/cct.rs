@@ -0,0 +1,54 @@
+use crate::color::white_point::WhitePoint;
+use crate::color::xyz::XYZ;
+use crate::math::number::Float;
+
+/// Estimate the correlated color temperature in Kelvin of the given color.
+///
+/// The temperature is approximated from the chromaticity coordinates using McCamy's formula, which is
+/// accurate for colors close to the Planckian locus. Returns NaN for black since its chromaticity is
+/// undefined.
+///
+/// [Color temperature - Wikipedia](https://en.wikipedia.org/wiki/Color_temperature#Approximation)
+#[must_use]
+pub fn correlated_color_temperature<F, W>(xyz: &XYZ<F, W>) -> F
+where
+    F: Float,
+    W: WhitePoint<F>,
+{
+    let sum = xyz.x + xyz.y + xyz.z;
+    if sum.is_zero() {
+        return F::from_f64(f64::NAN);
+    }
+
+    let x = xyz.x / sum;
+    let y = xyz.y / sum;
+    let n = (x - F::from_f64(0.3320)) / (F::from_f64(0.1858) - y);
+    F::from_f64(449.0) * n.powi(3)
+        + F::from_f64(3525.0) * n.powi(2)
+        + F::from_f64(6823.3) * n
+        + F::from_f64(5520.33)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::rgba::Rgba;
+    use crate::color::white_point::D65;
+
+    #[test]
+    fn correlated_color_temperature_should_estimate_temperature() {
+        let white: XYZ<f64, D65> = XYZ::from(&Rgba::white());
+        let cct = correlated_color_temperature(&white);
+        assert!((cct - 6504.0).abs() < 10.0);
+
+        let warm: XYZ<f64, D65> = XYZ::from(&Rgba::new(255, 180, 107, 255));
+        let cct = correlated_color_temperature(&warm);
+        assert!((2800.0..3200.0).contains(&cct));
+    }
+
+    #[test]
+    fn correlated_color_temperature_should_return_nan_for_black() {
+        let black: XYZ<f64, D65> = XYZ::from(&Rgba::black());
+        assert!(correlated_color_temperature(&black).is_nan());
+    }
+}
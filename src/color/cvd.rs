@@ -0,0 +1,121 @@
+use crate::color::rgba::Rgba;
+use crate::color::transfer::{linear_to_srgb, srgb_to_linear};
+
+/// Matrix converting linear sRGB to LMS cone responses.
+const RGB_TO_LMS: [[f64; 3]; 3] = [
+    [17.8824, 43.5161, 4.11935],
+    [3.45565, 27.1554, 3.86714],
+    [0.0299566, 0.184309, 1.46709],
+];
+
+/// Matrix converting LMS cone responses to linear sRGB, the inverse of [`RGB_TO_LMS`].
+const LMS_TO_RGB: [[f64; 3]; 3] = [
+    [0.0809444479, -0.130504409, 0.116721066],
+    [-0.0102485335, 0.0540193266, -0.113614708],
+    [-0.000365296938, -0.00412161469, 0.693511405],
+];
+
+/// Matrix replacing the missing L cone response with one reconstructed from the M and S cones.
+const PROTANOPIA: [[f64; 3]; 3] = [[0.0, 2.02344, -2.52581], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+/// Matrix replacing the missing M cone response with one reconstructed from the L and S cones.
+const DEUTERANOPIA: [[f64; 3]; 3] = [[1.0, 0.0, 0.0], [0.494207, 0.0, 1.24827], [0.0, 0.0, 1.0]];
+
+/// Matrix replacing the missing S cone response with one reconstructed from the L and M cones.
+const TRITANOPIA: [[f64; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [-0.395913, 0.801109, 0.0]];
+
+/// Simulate how the given color appears to a viewer with protanopia, lacking the L cones.
+///
+/// The simulation follows Viénot et al. (1999), projecting the color in LMS space onto the plane
+/// of colors the viewer can distinguish. The alpha channel is kept as is.
+#[must_use]
+pub fn simulate_protanopia(color: &Rgba) -> Rgba {
+    simulate(color, &PROTANOPIA)
+}
+
+/// Simulate how the given color appears to a viewer with deuteranopia, lacking the M cones.
+///
+/// See [`simulate_protanopia`] for the simulation method.
+#[must_use]
+pub fn simulate_deuteranopia(color: &Rgba) -> Rgba {
+    simulate(color, &DEUTERANOPIA)
+}
+
+/// Simulate how the given color appears to a viewer with tritanopia, lacking the S cones.
+///
+/// See [`simulate_protanopia`] for the simulation method.
+#[must_use]
+pub fn simulate_tritanopia(color: &Rgba) -> Rgba {
+    simulate(color, &TRITANOPIA)
+}
+
+#[must_use]
+fn simulate(color: &Rgba, deficiency: &[[f64; 3]; 3]) -> Rgba {
+    let max = Rgba::max_value::<f64>();
+    let rgb = [color.r, color.g, color.b].map(|value| srgb_to_linear(f64::from(value) / max));
+    let lms = multiply(deficiency, &multiply(&RGB_TO_LMS, &rgb));
+    // Round half up, since the encoded value is non-negative.
+    let [r, g, b] = multiply(&LMS_TO_RGB, &lms)
+        .map(|value| (linear_to_srgb(value.clamp(0.0, 1.0)) * max + 0.5) as u8);
+    Rgba::new(r, g, b, color.a)
+}
+
+#[inline]
+#[must_use]
+fn multiply(matrix: &[[f64; 3]; 3], vector: &[f64; 3]) -> [f64; 3] {
+    matrix.map(|row| row[0] * vector[0] + row[1] * vector[1] + row[2] * vector[2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::difference::ciede2000;
+    use crate::color::lab::Lab;
+    use crate::color::xyz::XYZ;
+
+    fn delta_e(color1: &Rgba, color2: &Rgba) -> f64 {
+        let lab1: Lab<f64> = Lab::from(&XYZ::from(color1));
+        let lab2: Lab<f64> = Lab::from(&XYZ::from(color2));
+        ciede2000(&lab1, &lab2)
+    }
+
+    #[test]
+    fn simulate_should_keep_achromatic_colors() {
+        for color in [
+            Rgba::new(0, 0, 0, 255),
+            Rgba::new(128, 128, 128, 128),
+            Rgba::new(255, 255, 255, 255),
+        ] {
+            for simulated in [
+                simulate_protanopia(&color),
+                simulate_deuteranopia(&color),
+                simulate_tritanopia(&color),
+            ] {
+                assert!(simulated.distance_squared(&color) <= 3);
+                assert_eq!(simulated.a, color.a);
+            }
+        }
+    }
+
+    #[test]
+    fn simulate_deuteranopia_should_converge_red_and_green() {
+        let red = Rgba::new(255, 0, 0, 255);
+        let green = Rgba::new(0, 255, 0, 255);
+        let simulated_red = simulate_deuteranopia(&red);
+        let simulated_green = simulate_deuteranopia(&green);
+
+        // Both colors collapse onto the yellow-blue axis.
+        assert!(simulated_red.r.abs_diff(simulated_red.g) <= 2);
+        assert!(simulated_green.r.abs_diff(simulated_green.g) <= 2);
+        assert!(delta_e(&simulated_red, &simulated_green) < delta_e(&red, &green) / 2.0);
+    }
+
+    #[test]
+    fn simulate_tritanopia_should_converge_blue_and_teal() {
+        let blue = Rgba::new(0, 0, 255, 255);
+        let teal = Rgba::new(0, 128, 128, 255);
+        let distance = delta_e(&blue, &teal);
+        assert!(delta_e(&simulate_tritanopia(&blue), &simulate_tritanopia(&teal)) < distance);
+        assert!(delta_e(&simulate_protanopia(&blue), &simulate_protanopia(&teal)) > 0.0);
+    }
+}
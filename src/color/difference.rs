@@ -0,0 +1,232 @@
+use crate::color::lab::Lab;
+use crate::color::white_point::WhitePoint;
+use crate::math::number::Float;
+
+/// Formula to compute the color difference between two colors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeltaEFormula {
+    /// The Euclidean distance in CIE L*a*b* color space.
+    Cie76,
+    /// The CIE94 formula with the weights for graphic arts.
+    Cie94,
+    /// The CIEDE2000 formula, which is the most perceptually uniform.
+    #[default]
+    Ciede2000,
+}
+
+impl DeltaEFormula {
+    /// Compute the color difference between two colors using this formula.
+    #[must_use]
+    pub fn compute<F, W>(&self, lab1: &Lab<F, W>, lab2: &Lab<F, W>) -> F
+    where
+        F: Float,
+        W: WhitePoint<F>,
+    {
+        match *self {
+            DeltaEFormula::Cie76 => cie76(lab1, lab2),
+            DeltaEFormula::Cie94 => cie94(lab1, lab2),
+            DeltaEFormula::Ciede2000 => ciede2000(lab1, lab2),
+        }
+    }
+}
+
+/// Compute the color difference between two colors using the CIE76 formula.
+///
+/// [Color difference - Wikipedia](https://en.wikipedia.org/wiki/Color_difference#CIE76)
+#[must_use]
+pub fn cie76<F, W>(lab1: &Lab<F, W>, lab2: &Lab<F, W>) -> F
+where
+    F: Float,
+    W: WhitePoint<F>,
+{
+    ((lab1.l - lab2.l).powi(2) + (lab1.a - lab2.a).powi(2) + (lab1.b - lab2.b).powi(2)).sqrt()
+}
+
+/// Compute the color difference between two colors using the CIE94 formula for graphic arts.
+///
+/// The formula is not symmetric, since the chroma of the first color is the reference for the
+/// weighting functions.
+///
+/// [Color difference - Wikipedia](https://en.wikipedia.org/wiki/Color_difference#CIE94)
+#[must_use]
+pub fn cie94<F, W>(lab1: &Lab<F, W>, lab2: &Lab<F, W>) -> F
+where
+    F: Float,
+    W: WhitePoint<F>,
+{
+    let c1 = (lab1.a.powi(2) + lab1.b.powi(2)).sqrt();
+    let c2 = (lab2.a.powi(2) + lab2.b.powi(2)).sqrt();
+    let delta_l = lab1.l - lab2.l;
+    let delta_c = c1 - c2;
+    // The hue difference is derived from the others and may be slightly negative by rounding.
+    let delta_h_squared =
+        ((lab1.a - lab2.a).powi(2) + (lab1.b - lab2.b).powi(2) - delta_c.powi(2)).max(F::zero());
+
+    let s_c = F::one() + F::from_f64(0.045) * c1;
+    let s_h = F::one() + F::from_f64(0.015) * c1;
+    (delta_l.powi(2) + (delta_c / s_c).powi(2) + delta_h_squared / s_h.powi(2)).sqrt()
+}
+
+/// Compute the color difference between two colors using the CIEDE2000 formula.
+///
+/// [Color difference - Wikipedia](https://en.wikipedia.org/wiki/Color_difference#CIEDE2000)
+#[must_use]
+pub fn ciede2000<F, W>(lab1: &Lab<F, W>, lab2: &Lab<F, W>) -> F
+where
+    F: Float,
+    W: WhitePoint<F>,
+{
+    let pow25_7 = F::from_f64(25.0).powi(7);
+    let two = F::from_f64(2.0);
+    let half_turn = F::from_f64(180.0);
+    let full_turn = F::from_f64(360.0);
+
+    let c1 = (lab1.a.powi(2) + lab1.b.powi(2)).sqrt();
+    let c2 = (lab2.a.powi(2) + lab2.b.powi(2)).sqrt();
+    let c_bar = (c1 + c2) / two;
+    let g = F::from_f64(0.5) * (F::one() - (c_bar.powi(7) / (c_bar.powi(7) + pow25_7)).sqrt());
+
+    let a1 = (F::one() + g) * lab1.a;
+    let a2 = (F::one() + g) * lab2.a;
+    let c1 = (a1.powi(2) + lab1.b.powi(2)).sqrt();
+    let c2 = (a2.powi(2) + lab2.b.powi(2)).sqrt();
+    let hue = |a: F, b: F| -> F {
+        if a.is_zero() && b.is_zero() {
+            return F::zero();
+        }
+        let h = b.atan2(a).to_degrees();
+        if h < F::zero() {
+            h + full_turn
+        } else {
+            h
+        }
+    };
+    let h1 = hue(a1, lab1.b);
+    let h2 = hue(a2, lab2.b);
+
+    let delta_l = lab2.l - lab1.l;
+    let delta_c = c2 - c1;
+    let is_achromatic = (c1 * c2).is_zero();
+    let delta_h = if is_achromatic {
+        F::zero()
+    } else if (h2 - h1).abs() <= half_turn {
+        h2 - h1
+    } else if h2 - h1 > half_turn {
+        h2 - h1 - full_turn
+    } else {
+        h2 - h1 + full_turn
+    };
+    let delta_h = two * (c1 * c2).sqrt() * (delta_h / two).to_radians().sin();
+
+    let l_bar = (lab1.l + lab2.l) / two;
+    let c_bar = (c1 + c2) / two;
+    let h_bar = if is_achromatic {
+        h1 + h2
+    } else if (h1 - h2).abs() <= half_turn {
+        (h1 + h2) / two
+    } else if h1 + h2 < full_turn {
+        (h1 + h2 + full_turn) / two
+    } else {
+        (h1 + h2 - full_turn) / two
+    };
+
+    let cos = |degrees: F| degrees.to_radians().cos();
+    let t = F::one() - F::from_f64(0.17) * cos(h_bar - F::from_f64(30.0))
+        + F::from_f64(0.24) * cos(two * h_bar)
+        + F::from_f64(0.32) * cos(F::from_f64(3.0) * h_bar + F::from_f64(6.0))
+        - F::from_f64(0.20) * cos(F::from_f64(4.0) * h_bar - F::from_f64(63.0));
+    let delta_theta =
+        F::from_f64(30.0) * (-((h_bar - F::from_f64(275.0)) / F::from_f64(25.0)).powi(2)).exp();
+    let r_c = two * (c_bar.powi(7) / (c_bar.powi(7) + pow25_7)).sqrt();
+    let s_l = F::one()
+        + F::from_f64(0.015) * (l_bar - F::from_f64(50.0)).powi(2)
+            / (F::from_f64(20.0) + (l_bar - F::from_f64(50.0)).powi(2)).sqrt();
+    let s_c = F::one() + F::from_f64(0.045) * c_bar;
+    let s_h = F::one() + F::from_f64(0.015) * c_bar * t;
+    let r_t = -(two * delta_theta).to_radians().sin() * r_c;
+
+    let l = delta_l / s_l;
+    let c = delta_c / s_c;
+    let h = delta_h / s_h;
+    (l.powi(2) + c.powi(2) + h.powi(2) + r_t * c * h).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ciede2000_should_compute_color_difference() {
+        // Test data from "The CIEDE2000 Color-Difference Formula" by Sharma et al.
+        let cases = [
+            ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 2.0425),
+            ((50.0, -1.3802, -84.2814), (50.0, 0.0, -82.7485), 1.0000),
+            ((50.0, 2.5, 0.0), (50.0, 0.0, -2.5), 4.3065),
+            ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 27.1492),
+            (
+                (60.2574, -34.0099, 36.2677),
+                (60.4626, -34.1751, 39.4387),
+                1.2644,
+            ),
+            (
+                (22.7233, 20.0904, -46.6940),
+                (23.0331, 14.9730, -42.5619),
+                2.0373,
+            ),
+        ];
+        for ((l1, a1, b1), (l2, a2, b2), expected) in cases {
+            let lab1: Lab<f64> = Lab::new(l1, a1, b1);
+            let lab2: Lab<f64> = Lab::new(l2, a2, b2);
+            assert!((ciede2000(&lab1, &lab2) - expected).abs() < 1e-4);
+            assert!((ciede2000(&lab2, &lab1) - expected).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn cie76_should_compute_euclidean_distance() {
+        let lab1: Lab<f64> = Lab::new(50.0, 2.6772, -79.7751);
+        let lab2: Lab<f64> = Lab::new(50.0, 0.0, -82.7485);
+        assert!((cie76(&lab1, &lab2) - 4.0011).abs() < 1e-4);
+        assert_eq!(cie76(&lab1, &lab2), cie76(&lab2, &lab1));
+        assert_eq!(cie76(&lab1, &lab1), 0.0);
+    }
+
+    #[test]
+    fn cie94_should_compute_color_difference() {
+        let cases = [
+            ((50.0, 2.6772, -79.7751), (50.0, 0.0, -82.7485), 1.3950),
+            ((50.0, 2.5, 0.0), (73.0, 25.0, -18.0), 34.6892),
+        ];
+        for ((l1, a1, b1), (l2, a2, b2), expected) in cases {
+            let lab1: Lab<f64> = Lab::new(l1, a1, b1);
+            let lab2: Lab<f64> = Lab::new(l2, a2, b2);
+            assert!((cie94(&lab1, &lab2) - expected).abs() < 1e-4);
+        }
+        let lab: Lab<f64> = Lab::new(53.23, 80.11, 67.22);
+        assert_eq!(cie94(&lab, &lab), 0.0);
+    }
+
+    #[test]
+    fn compute_should_dispatch_to_formula() {
+        let lab1: Lab<f64> = Lab::new(50.0, 2.5, 0.0);
+        let lab2: Lab<f64> = Lab::new(73.0, 25.0, -18.0);
+        assert_eq!(
+            DeltaEFormula::Cie76.compute(&lab1, &lab2),
+            cie76(&lab1, &lab2)
+        );
+        assert_eq!(
+            DeltaEFormula::Cie94.compute(&lab1, &lab2),
+            cie94(&lab1, &lab2)
+        );
+        assert_eq!(
+            DeltaEFormula::default().compute(&lab1, &lab2),
+            ciede2000(&lab1, &lab2)
+        );
+    }
+
+    #[test]
+    fn ciede2000_should_return_zero_for_same_colors() {
+        let lab: Lab<f64> = Lab::new(53.23, 80.11, 67.22);
+        assert_eq!(ciede2000(&lab, &lab), 0.0);
+    }
+}
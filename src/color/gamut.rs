@@ -0,0 +1,124 @@
+use crate::color::lab::Lab;
+use crate::color::lch::Lch;
+use crate::color::rgba::Rgba;
+use crate::color::transfer::linear_to_srgb;
+#[cfg(feature = "std")]
+use crate::color::white_point::D65;
+use crate::color::xyz::XYZ;
+use crate::math::number::Float;
+
+/// The maximum number of bisection steps to find the in-gamut chroma.
+const MAX_ITERATIONS: usize = 32;
+
+/// The tolerance of linear RGB values to be considered in gamut.
+const TOLERANCE: f64 = 1e-4;
+
+/// Map the given color into the sRGB gamut.
+///
+/// Out-of-gamut colors are mapped by reducing the chroma in CIE LCh color space while keeping the
+/// lightness and hue, which avoids the hue shift caused by clamping each channel independently.
+#[must_use]
+pub fn map_to_srgb<F: Float>(lab: &Lab<F>) -> Rgba {
+    if is_in_gamut(lab) {
+        return clamp_to_rgba(lab);
+    }
+
+    let lch = Lch::from(lab);
+    let mut min = F::zero();
+    let mut max = lch.c;
+    for _ in 0..MAX_ITERATIONS {
+        let chroma = (min + max) / F::from_f64(2.0);
+        let candidate = Lab::from(&Lch::<F>::new(lch.l, chroma, lch.h));
+        if is_in_gamut(&candidate) {
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+    clamp_to_rgba(&Lab::from(&Lch::<F>::new(lch.l, min, lch.h)))
+}
+
+/// Return whether the given color is within the sRGB gamut.
+#[must_use]
+pub fn is_in_gamut<F: Float>(lab: &Lab<F>) -> bool {
+    let (r, g, b) = Rgba::linear_from_xyz(&XYZ::from(lab));
+    let min = -F::from_f64(TOLERANCE);
+    let max = F::one() + F::from_f64(TOLERANCE);
+    [r, g, b].iter().all(|value| (min..=max).contains(value))
+}
+
+/// Convert the given color into RGBA, mapping it into the sRGB gamut only if a channel would be
+/// out of range.
+///
+/// The mean color of a cluster can be out of the sRGB gamut when distant colors are clustered,
+/// and such a color cannot be converted channel by channel.
+#[cfg(feature = "std")]
+#[must_use]
+pub(crate) fn to_rgba<F: Float>(lab: &Lab<F>) -> Rgba {
+    let xyz: XYZ<F, D65> = XYZ::from(lab);
+    let (r, g, b) = Rgba::linear_from_xyz(&xyz);
+    let max_value = Rgba::max_value::<F>();
+    let is_representable = [r, g, b].into_iter().all(|value| {
+        (linear_to_srgb(value) * max_value)
+            .round()
+            .to_u8()
+            .is_some()
+    });
+    if is_representable {
+        Rgba::from(&xyz)
+    } else {
+        map_to_srgb(lab)
+    }
+}
+
+fn clamp_to_rgba<F: Float>(lab: &Lab<F>) -> Rgba {
+    let (r, g, b) = Rgba::linear_from_xyz(&XYZ::from(lab));
+    let max_value = Rgba::max_value::<F>();
+    let f = |value: F| -> u8 {
+        let value = linear_to_srgb(value.clamp(F::zero(), F::one()));
+        (value * max_value)
+            .round()
+            .to_u8()
+            .expect("The value could not be cast to u8")
+    };
+    Rgba::new(f(r), f(g), f(b), Rgba::max_value())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_to_srgb_should_keep_in_gamut_color() {
+        let xyz: XYZ<f64, D65> = XYZ::from(&Rgba::new(255, 0, 0, 255));
+        let lab = Lab::from(&xyz);
+        assert!(is_in_gamut(&lab));
+        assert_eq!(map_to_srgb(&lab), Rgba::new(255, 0, 0, 255));
+    }
+
+    #[test]
+    fn map_to_srgb_should_preserve_hue_and_lightness() {
+        let lab: Lab<f64> = Lab::from(&Lch::new(50.0, 120.0, 200.0));
+        assert!(!is_in_gamut(&lab));
+
+        let rgba = map_to_srgb(&lab);
+        let xyz: XYZ<f64, D65> = XYZ::from(&rgba);
+        let actual = Lch::from(&Lab::from(&xyz));
+        assert!((actual.h - 200.0).abs() < 2.0);
+        assert!((actual.l - 50.0).abs() < 1.0);
+        assert!(actual.c < 120.0);
+    }
+
+    #[test]
+    fn to_rgba_should_map_only_unrepresentable_colors() {
+        for rgba in [Rgba::new(255, 0, 0, 255), Rgba::new(12, 200, 99, 255)] {
+            let xyz: XYZ<f64, D65> = XYZ::from(&rgba);
+            let lab = Lab::from(&xyz);
+            assert_eq!(to_rgba(&lab), Rgba::from(&xyz));
+        }
+
+        // A mean of saturated colors beyond the sRGB gamut.
+        let lab = Lab::<f64>::new(50.0, 120.0, -120.0);
+        assert_eq!(to_rgba(&lab), map_to_srgb(&lab));
+    }
+}
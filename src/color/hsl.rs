@@ -0,0 +1,172 @@
+use crate::color::rgba::Rgba;
+use crate::color::transfer::{linear_to_srgb, srgb_to_linear};
+use crate::color::white_point::D65;
+use crate::color::xyz::XYZ;
+use crate::math::number::Float;
+use core::fmt::{Display, Formatter};
+
+/// Color in HSL color space, the cylindrical representation of sRGB.
+///
+/// [HSL and HSV - Wikipedia](https://en.wikipedia.org/wiki/HSL_and_HSV)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hsl<F: Float> {
+    pub h: F,
+    pub s: F,
+    pub l: F,
+}
+
+impl<F> Hsl<F>
+where
+    F: Float,
+{
+    /// Create a color in HSL color space.
+    ///
+    /// The hue is given in degrees and normalized to the range [0, 360), and the saturation and
+    /// lightness are clamped to the range [0, 1].
+    #[inline]
+    #[must_use]
+    pub fn new(h: F, s: F, l: F) -> Self {
+        let max = F::from_f64(360.0);
+        let h = h % max;
+        Self {
+            h: if h < F::zero() { h + max } else { h },
+            s: s.clamp(F::zero(), F::one()),
+            l: l.clamp(F::zero(), F::one()),
+        }
+    }
+
+    /// Create a color from the given gamma-encoded sRGB values in the range [0, 1].
+    #[must_use]
+    pub(crate) fn from_srgb(r: F, g: F, b: F) -> Self {
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / F::from_f64(2.0);
+        let delta = max - min;
+        if delta.is_zero() {
+            return Hsl::new(F::zero(), F::zero(), l);
+        }
+
+        let s = delta / (F::one() - (F::from_f64(2.0) * l - F::one()).abs());
+        let h = if max == r {
+            ((g - b) / delta) % F::from_f64(6.0)
+        } else if max == g {
+            (b - r) / delta + F::from_f64(2.0)
+        } else {
+            (r - g) / delta + F::from_f64(4.0)
+        };
+        Hsl::new(h * F::from_f64(60.0), s, l)
+    }
+
+    /// Convert this color to gamma-encoded sRGB values in the range [0, 1].
+    #[must_use]
+    pub(crate) fn to_srgb(&self) -> (F, F, F) {
+        let chroma = (F::one() - (F::from_f64(2.0) * self.l - F::one()).abs()) * self.s;
+        let h = self.h / F::from_f64(60.0);
+        let x = chroma * (F::one() - (h % F::from_f64(2.0) - F::one()).abs());
+        let (r, g, b) = match h.to_u8().unwrap_or(0) {
+            0 => (chroma, x, F::zero()),
+            1 => (x, chroma, F::zero()),
+            2 => (F::zero(), chroma, x),
+            3 => (F::zero(), x, chroma),
+            4 => (x, F::zero(), chroma),
+            _ => (chroma, F::zero(), x),
+        };
+        let m = self.l - chroma / F::from_f64(2.0);
+        (r + m, g + m, b + m)
+    }
+}
+
+impl<F> Display for Hsl<F>
+where
+    F: Float + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Hsl({h}, {s}, {l})", h = self.h, s = self.s, l = self.l)
+    }
+}
+
+impl<F> From<&Rgba> for Hsl<F>
+where
+    F: Float,
+{
+    #[inline]
+    fn from(rgba: &Rgba) -> Self {
+        let max_value: F = Rgba::max_value();
+        Hsl::from_srgb(
+            rgba.r::<F>() / max_value,
+            rgba.g::<F>() / max_value,
+            rgba.b::<F>() / max_value,
+        )
+    }
+}
+
+impl<F> From<&XYZ<F, D65>> for Hsl<F>
+where
+    F: Float,
+{
+    /// Convert the color, clamping each channel outside the sRGB gamut.
+    #[inline]
+    fn from(xyz: &XYZ<F, D65>) -> Self {
+        let (r, g, b) = Rgba::linear_from_xyz(xyz);
+        let encode = |value: F| linear_to_srgb(value.clamp(F::zero(), F::one()));
+        Hsl::from_srgb(encode(r), encode(g), encode(b))
+    }
+}
+
+impl<F> From<&Hsl<F>> for XYZ<F, D65>
+where
+    F: Float,
+{
+    #[inline]
+    fn from(hsl: &Hsl<F>) -> Self {
+        let (r, g, b) = hsl.to_srgb();
+        XYZ::from_linear_rgb(srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_should_normalize_values() {
+        let hsl: Hsl<f64> = Hsl::new(-120.0, 1.5, -0.5);
+        assert_eq!(hsl, Hsl::new(240.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn from_rgba_should_convert_to_hsl() {
+        let hsl: Hsl<f64> = Hsl::from(&Rgba::new(255, 0, 0, 255));
+        assert_eq!(hsl, Hsl::new(0.0, 1.0, 0.5));
+
+        let hsl: Hsl<f64> = Hsl::from(&Rgba::new(0, 0, 255, 255));
+        assert_eq!(hsl, Hsl::new(240.0, 1.0, 0.5));
+
+        let hsl: Hsl<f64> = Hsl::from(&Rgba::new(255, 0, 255, 255));
+        assert_eq!(hsl, Hsl::new(300.0, 1.0, 0.5));
+
+        let hsl: Hsl<f64> = Hsl::from(&Rgba::new(51, 51, 51, 255));
+        assert_eq!(hsl, Hsl::new(0.0, 0.0, 0.2));
+    }
+
+    #[test]
+    fn to_srgb_should_invert_from_srgb() {
+        for (r, g, b) in [
+            (0.2_f64, 0.4, 0.6),
+            (0.9, 0.1, 0.3),
+            (0.5, 0.5, 0.1),
+            (0.0, 0.0, 0.0),
+        ] {
+            let (actual_r, actual_g, actual_b) = Hsl::from_srgb(r, g, b).to_srgb();
+            assert!((actual_r - r).abs() < 1e-12);
+            assert!((actual_g - g).abs() < 1e-12);
+            assert!((actual_b - b).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn fmt_should_format_hsl() {
+        let hsl: Hsl<f64> = Hsl::new(120.0, 0.5, 0.25);
+        assert_eq!(hsl.to_string(), "Hsl(120, 0.5, 0.25)");
+    }
+}
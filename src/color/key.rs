@@ -0,0 +1,101 @@
+use crate::color::lab::Lab;
+use crate::color::white_point::WhitePoint;
+use crate::color::xyz::XYZ;
+use crate::math::number::Float;
+use core::hash::{Hash, Hasher};
+
+/// Trait for colors whose floating-point components can be compared by their bit patterns.
+pub trait ColorBits {
+    /// Return the bit patterns of the components of this color.
+    #[must_use]
+    fn to_bits(&self) -> [u64; 3];
+}
+
+/// Return the bit pattern of the given value widened to `f64`, which is lossless for `f32`.
+#[inline]
+fn bits<F: Float>(value: F) -> u64 {
+    value.to_f64().unwrap_or(f64::NAN).to_bits()
+}
+
+impl<F, W> ColorBits for Lab<F, W>
+where
+    F: Float,
+    W: WhitePoint<F>,
+{
+    #[inline]
+    fn to_bits(&self) -> [u64; 3] {
+        [bits(self.l), bits(self.a), bits(self.b)]
+    }
+}
+
+impl<F, W> ColorBits for XYZ<F, W>
+where
+    F: Float,
+    W: WhitePoint<F>,
+{
+    #[inline]
+    fn to_bits(&self) -> [u64; 3] {
+        [bits(self.x), bits(self.y), bits(self.z)]
+    }
+}
+
+/// Wrapper to use a color with floating-point components as a key of a hash map.
+///
+/// The components are compared by their bit patterns, so NaN equals itself and `0.0` differs from
+/// `-0.0`, which keeps `Eq` and `Hash` consistent.
+#[derive(Debug, Clone)]
+pub struct ColorKey<C>(pub C);
+
+impl<C> PartialEq for ColorKey<C>
+where
+    C: ColorBits,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl<C> Eq for ColorKey<C> where C: ColorBits {}
+
+impl<C> Hash for ColorKey<C>
+where
+    C: ColorBits,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::white_point::D65;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashMap;
+
+    fn hash<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn hash_should_be_equal_for_equal_lab() {
+        let key1 = ColorKey(Lab::<f64, D65>::new(53.24, 80.09, 67.20));
+        let key2 = ColorKey(Lab::<f64, D65>::new(53.24, 80.09, 67.20));
+        assert_eq!(key1, key2);
+        assert_eq!(hash(&key1), hash(&key2));
+
+        let key3 = ColorKey(Lab::<f64, D65>::new(53.24, 80.09, 67.21));
+        assert_ne!(key1, key3);
+    }
+
+    #[test]
+    fn key_should_cache_converted_colors() {
+        let mut cache: HashMap<ColorKey<XYZ<f32, D65>>, Lab<f32, D65>> = HashMap::new();
+        let xyz = XYZ::<f32, D65>::new(0.4124, 0.2126, 0.0193);
+        cache.insert(ColorKey(xyz.clone()), Lab::from(&xyz));
+        assert_eq!(cache.get(&ColorKey(xyz.clone())), Some(&Lab::from(&xyz)));
+        assert_eq!(cache.get(&ColorKey(XYZ::new(0.0, 0.0, 0.0))), None);
+    }
+}
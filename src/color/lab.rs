@@ -1,8 +1,9 @@
+use crate::color::difference::DeltaEFormula;
 use crate::color::white_point::{WhitePoint, D65};
 use crate::color::xyz::XYZ;
 use crate::math::number::Float;
-use std::fmt::{Display, Formatter};
-use std::marker::PhantomData;
+use core::fmt::{Display, Formatter};
+use core::marker::PhantomData;
 
 /// Color in CIE L*a*b* color space.
 #[derive(Debug, Clone, PartialEq)]
@@ -26,10 +27,28 @@ where
             l: Self::normalize_l(l),
             a: Self::normalize_a(a),
             b: Self::normalize_b(b),
-            _w: PhantomData::default(),
+            _w: PhantomData,
         }
     }
 
+    /// Create a color in CIE L*a*b* color space and report whether any component was clamped.
+    ///
+    /// This is useful to detect out-of-range values when converting from a wide-gamut color.
+    #[inline]
+    #[must_use]
+    pub fn new_checked(l: F, a: F, b: F) -> (Self, bool) {
+        let lab = Self::new(l, a, b);
+        let clamped = lab.l != l || lab.a != a || lab.b != b;
+        (lab, clamped)
+    }
+
+    /// Compute the color difference between this color and the other color using the formula.
+    #[inline]
+    #[must_use]
+    pub fn delta_e(&self, other: &Self, formula: DeltaEFormula) -> F {
+        formula.compute(self, other)
+    }
+
     /// Return min value of l.
     #[inline]
     #[must_use]
@@ -96,7 +115,7 @@ where
     F: Float + Display,
     W: WhitePoint<F>,
 {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "Lab({l}, {a}, {b})", l = self.l, a = self.a, b = self.b)
     }
 }
@@ -135,6 +154,20 @@ mod tests {
     use super::*;
     use crate::color::rgba::Rgba;
 
+    #[test]
+    fn new_checked_should_report_clamping() {
+        let (lab, clamped): (Lab<f64, D65>, bool) = Lab::new_checked(53.23, 80.11, 67.22);
+        assert_eq!(lab, Lab::new(53.23, 80.11, 67.22));
+        assert!(!clamped);
+
+        let (lab, clamped): (Lab<f64, D65>, bool) = Lab::new_checked(50.0, 20.0, -160.0);
+        assert_eq!(lab, Lab::new(50.0, 20.0, -128.0));
+        assert!(clamped);
+
+        let (_, clamped): (Lab<f64, D65>, bool) = Lab::new_checked(100.5, 0.0, 0.0);
+        assert!(clamped);
+    }
+
     #[test]
     fn new_should_create_lab_color() {
         let lab: Lab<f64, D65> = Lab::new(53.23, 80.11, 67.22);
@@ -153,6 +186,20 @@ mod tests {
         assert_eq!(lab.b, 127.0);
     }
 
+    #[test]
+    fn delta_e_should_compute_difference_with_formula() {
+        let lab1: Lab<f64> = Lab::new(50.0, 2.6772, -79.7751);
+        let lab2: Lab<f64> = Lab::new(50.0, 0.0, -82.7485);
+        let cases = [
+            (DeltaEFormula::Cie76, 4.0011),
+            (DeltaEFormula::Cie94, 1.3950),
+            (DeltaEFormula::Ciede2000, 2.0425),
+        ];
+        for (formula, expected) in cases {
+            assert!((lab1.delta_e(&lab2, formula) - expected).abs() < 1e-4);
+        }
+    }
+
     #[test]
     fn to_string_should_return_string_representation() {
         let lab: Lab<f64, D65> = Lab::new(53.23, 80.11, 67.22);
@@ -0,0 +1,114 @@
+use crate::color::lab::Lab;
+use crate::color::white_point::{WhitePoint, D65};
+use crate::math::number::Float;
+use core::fmt::{Display, Formatter};
+use core::marker::PhantomData;
+
+/// Color in CIE LCh(ab) color space, the cylindrical representation of CIE L*a*b*.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lch<F: Float, W: WhitePoint<F> = D65> {
+    pub l: F,
+    pub c: F,
+    pub h: F,
+    _w: PhantomData<W>,
+}
+
+impl<F, W> Lch<F, W>
+where
+    F: Float,
+    W: WhitePoint<F>,
+{
+    /// Create a color in CIE LCh color space.
+    ///
+    /// The hue is given in degrees and normalized to the range [0, 360).
+    #[inline]
+    #[must_use]
+    pub fn new(l: F, c: F, h: F) -> Self {
+        Self {
+            l,
+            c,
+            h: Self::normalize_h(h),
+            _w: PhantomData,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    fn normalize_h(value: F) -> F {
+        let max = F::from_f64(360.0);
+        let h = value % max;
+        if h < F::zero() {
+            h + max
+        } else {
+            h
+        }
+    }
+}
+
+impl<F, W> Display for Lch<F, W>
+where
+    F: Float + Display,
+    W: WhitePoint<F>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Lch({l}, {c}, {h})", l = self.l, c = self.c, h = self.h)
+    }
+}
+
+impl<F, W> From<&Lab<F, W>> for Lch<F, W>
+where
+    F: Float,
+    W: WhitePoint<F>,
+{
+    #[inline]
+    fn from(lab: &Lab<F, W>) -> Self {
+        let c = (lab.a.powi(2) + lab.b.powi(2)).sqrt();
+        let h = lab.b.atan2(lab.a).to_degrees();
+        Lch::new(lab.l, c, h)
+    }
+}
+
+impl<F, W> From<&Lch<F, W>> for Lab<F, W>
+where
+    F: Float,
+    W: WhitePoint<F>,
+{
+    #[inline]
+    fn from(lch: &Lch<F, W>) -> Self {
+        let h = lch.h.to_radians();
+        Lab::new(lch.l, lch.c * h.cos(), lch.c * h.sin())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_should_normalize_hue() {
+        let lch: Lch<f64> = Lch::new(50.0, 20.0, 400.0);
+        assert_eq!(lch.h, 40.0);
+
+        let lch: Lch<f64> = Lch::new(50.0, 20.0, -90.0);
+        assert_eq!(lch.h, 270.0);
+    }
+
+    #[test]
+    fn from_lab_should_convert_to_lch() {
+        let lab: Lab<f64> = Lab::new(53.23, 80.11, 67.22);
+        let lch = Lch::from(&lab);
+        assert!((lch.l - 53.23).abs() < 1e-9);
+        assert!((lch.c - 104.58).abs() < 1e-2);
+        assert!((lch.h - 40.0).abs() < 1e-1);
+
+        let actual = Lab::from(&lch);
+        assert!((actual.a - lab.a).abs() < 1e-9);
+        assert!((actual.b - lab.b).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fmt_should_format_lch() {
+        let lch: Lch<f64> = Lch::new(50.0, 20.0, 120.0);
+        assert_eq!(format!("{}", lch), "Lch(50, 20, 120)");
+    }
+}
@@ -1,4 +1,46 @@
-pub mod lab;
-pub mod rgba;
-pub mod white_point;
-pub mod xyz;
+//! Color types, conversions between color spaces and color utilities.
+pub(crate) mod approx;
+pub(crate) mod cct;
+pub(crate) mod cvd;
+pub(crate) mod difference;
+pub(crate) mod gamut;
+pub(crate) mod hsl;
+pub(crate) mod key;
+pub(crate) mod lab;
+pub(crate) mod lch;
+pub(crate) mod names;
+pub(crate) mod oklab;
+pub(crate) mod rgba;
+pub(crate) mod space;
+pub(crate) mod transfer;
+pub(crate) mod white_point;
+pub(crate) mod xyz;
+
+#[doc(hidden)]
+pub use approx::default_epsilon_of;
+pub use approx::ApproxEq;
+pub use cct::correlated_color_temperature;
+pub use cvd::{simulate_deuteranopia, simulate_protanopia, simulate_tritanopia};
+pub use difference::{cie76, cie94, ciede2000, DeltaEFormula};
+pub use gamut::{is_in_gamut, map_to_srgb};
+pub use hsl::Hsl;
+pub use key::{ColorBits, ColorKey};
+pub use lab::Lab;
+pub use lch::Lch;
+pub use names::closest_css_name;
+pub use oklab::Oklab;
+pub use rgba::{ParseRgbaError, Rgba, RoundingMode};
+pub use space::{convert, ColorSpace, WorkingSpace};
+pub use transfer::TransferFunction;
+pub use white_point::{WhitePoint, D65};
+pub use xyz::XYZ;
+
+pub use crate::assert_color_eq;
+
+/// Return whether the given colors are equal within the given tolerance.
+#[cfg(test)]
+#[inline]
+#[must_use]
+pub(crate) fn approx_eq<T: ApproxEq>(a: &T, b: &T, epsilon: T::Epsilon) -> bool {
+    a.approx_eq(b, epsilon)
+}
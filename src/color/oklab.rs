@@ -0,0 +1,116 @@
+use crate::color::white_point::D65;
+use crate::color::xyz::XYZ;
+use crate::math::number::Float;
+use core::fmt::{Display, Formatter};
+
+/// Color in Oklab color space.
+///
+/// [A perceptual color space for image processing](https://bottosson.github.io/posts/oklab/)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Oklab<F: Float> {
+    pub l: F,
+    pub a: F,
+    pub b: F,
+}
+
+impl<F> Oklab<F>
+where
+    F: Float,
+{
+    /// Create a color in Oklab color space.
+    #[inline]
+    #[must_use]
+    pub fn new(l: F, a: F, b: F) -> Self {
+        Self { l, a, b }
+    }
+}
+
+impl<F> Display for Oklab<F>
+where
+    F: Float + Display,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Oklab({l}, {a}, {b})",
+            l = self.l,
+            a = self.a,
+            b = self.b
+        )
+    }
+}
+
+impl<F> From<&XYZ<F, D65>> for Oklab<F>
+where
+    F: Float,
+{
+    #[inline]
+    fn from(xyz: &XYZ<F, D65>) -> Self {
+        let c = F::from_f64;
+        let l =
+            (c(0.8189330101) * xyz.x + c(0.3618667424) * xyz.y - c(0.1288597137) * xyz.z).cbrt();
+        let m =
+            (c(0.0329845436) * xyz.x + c(0.9293118715) * xyz.y + c(0.0361456387) * xyz.z).cbrt();
+        let s =
+            (c(0.0482003018) * xyz.x + c(0.2643662691) * xyz.y + c(0.6338517070) * xyz.z).cbrt();
+        Oklab::new(
+            c(0.2104542553) * l + c(0.7936177850) * m - c(0.0040720468) * s,
+            c(1.9779984951) * l - c(2.4285922050) * m + c(0.4505937099) * s,
+            c(0.0259040371) * l + c(0.7827717662) * m - c(0.8086757660) * s,
+        )
+    }
+}
+
+impl<F> From<&Oklab<F>> for XYZ<F, D65>
+where
+    F: Float,
+{
+    #[inline]
+    fn from(oklab: &Oklab<F>) -> Self {
+        let c = F::from_f64;
+        let l = (oklab.l + c(0.3963377774) * oklab.a + c(0.2158037573) * oklab.b).powi(3);
+        let m = (oklab.l - c(0.1055613458) * oklab.a - c(0.0638541728) * oklab.b).powi(3);
+        let s = (oklab.l - c(0.0894841775) * oklab.a - c(1.2914855480) * oklab.b).powi(3);
+        XYZ::new(
+            c(1.2270138511) * l - c(0.5577999807) * m + c(0.2812561490) * s,
+            c(-0.0405801784) * l + c(1.1122568696) * m - c(0.0716766787) * s,
+            c(-0.0763812845) * l - c(0.4214819784) * m + c(1.5861632204) * s,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::rgba::Rgba;
+
+    #[test]
+    fn from_xyz_should_convert_to_oklab() {
+        let white: XYZ<f64> = XYZ::from(&Rgba::new(255, 255, 255, 255));
+        let oklab = Oklab::from(&white);
+        assert!((oklab.l - 1.0).abs() < 1e-3);
+        assert!(oklab.a.abs() < 1e-3);
+        assert!(oklab.b.abs() < 1e-3);
+
+        let red: XYZ<f64> = XYZ::from(&Rgba::new(255, 0, 0, 255));
+        let oklab = Oklab::from(&red);
+        assert!((oklab.l - 0.627955).abs() < 1e-3);
+        assert!((oklab.a - 0.224863).abs() < 1e-3);
+        assert!((oklab.b - 0.125846).abs() < 1e-3);
+    }
+
+    #[test]
+    fn from_oklab_should_convert_to_xyz() {
+        let xyz: XYZ<f64> = XYZ::from(&Rgba::new(30, 144, 255, 255));
+        let actual = XYZ::from(&Oklab::from(&xyz));
+        assert!((actual.x - xyz.x).abs() < 1e-6);
+        assert!((actual.y - xyz.y).abs() < 1e-6);
+        assert!((actual.z - xyz.z).abs() < 1e-6);
+    }
+
+    #[test]
+    fn fmt_should_format_oklab() {
+        let oklab: Oklab<f64> = Oklab::new(0.5, 0.1, -0.2);
+        assert_eq!(oklab.to_string(), "Oklab(0.5, 0.1, -0.2)");
+    }
+}
@@ -1,6 +1,38 @@
+use crate::color::lab::Lab;
+use crate::color::transfer::{linear_to_srgb, srgb_to_linear};
 use crate::color::xyz::XYZ;
 use crate::math::number::{Float, Number};
-use std::fmt::{Display, Formatter, Result};
+use crate::math::point::LinearRgbPoint;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter, Result};
+use core::str::FromStr;
+use num_traits::Zero;
+
+/// Rounding mode used to convert a channel value to an integer.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round half away from zero.
+    #[default]
+    Round,
+    /// Round toward negative infinity.
+    Floor,
+    /// Round toward zero.
+    Truncate,
+}
+
+impl RoundingMode {
+    /// Apply this rounding mode to the given value.
+    #[inline]
+    #[must_use]
+    pub fn apply<F: Float>(&self, value: F) -> F {
+        match *self {
+            RoundingMode::Round => value.round(),
+            RoundingMode::Floor => value.floor(),
+            RoundingMode::Truncate => value.trunc(),
+        }
+    }
+}
 
 /// Color in standard RGB color space.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -17,7 +49,7 @@ impl Rgba {
     /// Return the max value of RGBA.
     #[inline]
     #[must_use]
-    pub fn max_value<T: Number>() -> T {
+    pub(crate) fn max_value<T: Number>() -> T {
         T::from_u8(Self::MAX)
     }
 
@@ -56,7 +88,116 @@ impl Rgba {
         T::from_u8(self.a)
     }
 
+    /// Return whether the spread between the largest and smallest RGB channels is within the
+    /// given tolerance.
+    #[inline]
+    #[must_use]
+    pub fn is_grayscale(&self, tolerance: u8) -> bool {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        max - min <= tolerance
+    }
+
+    /// Return the squared Euclidean distance between the RGB channels of this color and the other.
+    ///
+    /// The distance is computed in integers without conversion to floating point, which suits
+    /// quantizers such as median cut or octree that compare many raw colors. The alpha is ignored.
+    #[inline]
+    #[must_use]
+    pub fn distance_squared(&self, other: &Rgba) -> u32 {
+        let delta = |lhs: u8, rhs: u8| u32::from(lhs.abs_diff(rhs)).pow(2);
+        delta(self.r, other.r) + delta(self.g, other.g) + delta(self.b, other.b)
+    }
+
+    /// Mix the given colors by their weights in linear light.
+    ///
+    /// The weights are normalized by their total, and the alpha is averaged linearly. Averaging in
+    /// linear light keeps the mix from being biased toward dark colors. Returns transparent black
+    /// if the total weight is not positive.
+    #[must_use]
+    pub fn mix<F: Float>(colors: &[(Rgba, F)]) -> Self {
+        let (total, weight) = colors.iter().fold(
+            (LinearRgbPoint::<F>::zero(), F::zero()),
+            |(total, weight), (color, color_weight)| {
+                (
+                    total + LinearRgbPoint::from(color) * *color_weight,
+                    weight + *color_weight,
+                )
+            },
+        );
+        if weight <= F::zero() {
+            return Rgba::new(0, 0, 0, 0);
+        }
+        Rgba::from(&(total / weight))
+    }
+
+    /// Return the chroma of this color in CIE L*a*b* color space.
+    #[inline]
+    #[must_use]
+    pub fn chroma<F: Float>(&self) -> F {
+        let lab: Lab<F> = Lab::from(&XYZ::from(self));
+        (lab.a.powi(2) + lab.b.powi(2)).sqrt()
+    }
+
+    /// Return the relative luminance of this color as defined by WCAG, ignoring the alpha.
+    #[inline]
+    #[must_use]
+    pub fn relative_luminance<F: Float>(&self) -> F {
+        let max = Self::max_value::<F>();
+        let [r, g, b] =
+            [self.r, self.g, self.b].map(|value| srgb_to_linear(F::from_u8(value) / max));
+        F::from_f64(0.2126) * r + F::from_f64(0.7152) * g + F::from_f64(0.0722) * b
+    }
+
+    /// Return the WCAG contrast ratio between this color and the other color, from 1 to 21.
+    ///
+    /// [Contrast ratio - WCAG 2.1](https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio)
+    #[must_use]
+    pub fn contrast_ratio<F: Float>(&self, other: &Rgba) -> F {
+        let luminance1: F = self.relative_luminance();
+        let luminance2: F = other.relative_luminance();
+        let offset = F::from_f64(0.05);
+        (luminance1.max(luminance2) + offset) / (luminance1.min(luminance2) + offset)
+    }
+
+    /// Create a color from the given color in CIE XYZ color space using the rounding mode.
+    #[must_use]
+    pub fn from_xyz_with<F: Float>(xyz: &XYZ<F>, mode: RoundingMode) -> Self {
+        let (r, g, b) = Self::linear_from_xyz(xyz);
+        let fr = linear_to_srgb(r);
+        let fg = linear_to_srgb(g);
+        let fb = linear_to_srgb(b);
+
+        let max_value = Rgba::max_value::<F>();
+        let r = Self::normalize_value(mode.apply(fr * max_value));
+        let g = Self::normalize_value(mode.apply(fg * max_value));
+        let b = Self::normalize_value(mode.apply(fb * max_value));
+        Self {
+            r,
+            g,
+            b,
+            a: Rgba::max_value(),
+        }
+    }
+
+    /// Convert the given color in CIE XYZ color space to linear RGB values.
+    ///
+    /// The values are not clamped, so any value outside the range [0, 1] is out of the sRGB gamut.
+    #[must_use]
+    pub(crate) fn linear_from_xyz<F: Float>(xyz: &XYZ<F>) -> (F, F, F) {
+        let r = F::from_f64(3.24097) * xyz.x
+            - F::from_f64(1.537383) * xyz.y
+            - F::from_f64(0.498611) * xyz.z;
+        let g = F::from_f64(-0.969244) * xyz.x
+            + F::from_f64(1.875968) * xyz.y
+            + F::from_f64(0.041555) * xyz.z;
+        let b = F::from_f64(0.05563) * xyz.x - F::from_f64(0.203977) * xyz.y
+            + F::from_f64(1.056972) * xyz.z;
+        (r, g, b)
+    }
+
     /// Create a white color.
+    #[cfg(test)]
     #[must_use]
     pub(crate) fn white() -> Self {
         Self {
@@ -68,6 +209,7 @@ impl Rgba {
     }
 
     /// Create a black color.
+    #[cfg(test)]
     #[must_use]
     pub(crate) fn black() -> Self {
         Self {
@@ -79,6 +221,7 @@ impl Rgba {
     }
 
     /// Create a red color.
+    #[cfg(test)]
     #[must_use]
     pub(crate) fn red() -> Self {
         Self {
@@ -90,6 +233,7 @@ impl Rgba {
     }
 
     /// Create a green color.
+    #[cfg(test)]
     #[must_use]
     pub(crate) fn green() -> Self {
         Self {
@@ -101,6 +245,7 @@ impl Rgba {
     }
 
     /// Create a blue color.
+    #[cfg(test)]
     #[must_use]
     pub(crate) fn blue() -> Self {
         Self {
@@ -112,6 +257,7 @@ impl Rgba {
     }
 
     /// Create a transparent color.
+    #[cfg(test)]
     #[must_use]
     pub(crate) fn transparent() -> Self {
         Self {
@@ -122,6 +268,42 @@ impl Rgba {
         }
     }
 
+    /// Parse the hexadecimal digits of the `rrggbb` or `rrggbbaa` notation.
+    #[must_use]
+    fn parse_hex(hex: &str) -> Option<Self> {
+        if !(hex.len() == 6 || hex.len() == 8) || !hex.bytes().all(|byte| byte.is_ascii_hexdigit())
+        {
+            return None;
+        }
+        let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+        let a = if hex.len() == 8 {
+            channel(6)?
+        } else {
+            Self::MAX
+        };
+        Some(Rgba::new(channel(0)?, channel(2)?, channel(4)?, a))
+    }
+
+    /// Parse the `rgb(r, g, b)` or `rgba(r, g, b, a)` functional notation.
+    #[must_use]
+    fn parse_function(input: &str) -> Option<Self> {
+        let (name, args) = input.strip_suffix(')')?.split_once('(')?;
+        let args: Vec<&str> = args.split(',').map(str::trim).collect();
+        let channel = |value: &str| value.parse::<u8>().ok();
+        match (name.trim().to_ascii_lowercase().as_str(), args.as_slice()) {
+            ("rgb", [r, g, b]) => Some(Rgba::new(channel(r)?, channel(g)?, channel(b)?, Self::MAX)),
+            ("rgba", [r, g, b, a]) => {
+                let alpha = a
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|alpha| (0.0..=1.0).contains(alpha))?;
+                let a = (alpha * Self::max_value::<f64>() + 0.5) as u8;
+                Some(Rgba::new(channel(r)?, channel(g)?, channel(b)?, a))
+            }
+            _ => None,
+        }
+    }
+
     #[must_use]
     fn normalize_value<F: Float>(value: F) -> u8 {
         value.to_u8().expect("The value could not be cast to u8")
@@ -141,38 +323,88 @@ impl Display for Rgba {
     }
 }
 
+/// Error returned when parsing a malformed color string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRgbaError {
+    input: String,
+}
+
+impl Display for ParseRgbaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(
+            f,
+            "Invalid color '{}', expected '#rrggbb', '#rrggbbaa', 'rgb(r, g, b)' or \
+             'rgba(r, g, b, a)'",
+            self.input
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseRgbaError {}
+
+impl FromStr for Rgba {
+    type Err = ParseRgbaError;
+
+    /// Parse a color from the hexadecimal or the `rgb()` and `rgba()` functional CSS notations.
+    ///
+    /// The channels of the functional notations are integers in the range [0, 255], and the alpha
+    /// of `rgba()` is a number in the range [0, 1] scaled to [0, 255]. The function names are
+    /// matched ignoring ASCII case, and whitespace around the values is ignored.
+    fn from_str(input: &str) -> core::result::Result<Self, Self::Err> {
+        let trimmed = input.trim();
+        let rgba = match trimmed.strip_prefix('#') {
+            Some(hex) => Self::parse_hex(hex),
+            None => Self::parse_function(trimmed),
+        };
+        rgba.ok_or_else(|| ParseRgbaError {
+            input: input.to_string(),
+        })
+    }
+}
+
 impl<F> From<&XYZ<F>> for Rgba
 where
     F: Float,
 {
     #[inline]
     fn from(xyz: &XYZ<F>) -> Self {
-        let f = |value: F| -> F {
-            if value <= F::from_f64(0.0031308) {
-                F::from_f64(12.92) * value
-            } else {
-                F::from_f64(1.055) * value.powf(F::from_f64(1.0 / 2.4)) - F::from_f64(0.055)
-            }
-        };
+        Rgba::from_xyz_with(xyz, RoundingMode::default())
+    }
+}
 
-        let fr = f(F::from_f64(3.24097) * xyz.x
-            - F::from_f64(1.537383) * xyz.y
-            - F::from_f64(0.498611) * xyz.z);
-        let fg = f(F::from_f64(-0.969244) * xyz.x
-            + F::from_f64(1.875968) * xyz.y
-            + F::from_f64(0.041555) * xyz.z);
-        let fb = f(F::from_f64(0.05563) * xyz.x - F::from_f64(0.203977) * xyz.y
-            + F::from_f64(1.056972) * xyz.z);
+impl<F> From<&Rgba> for LinearRgbPoint<F>
+where
+    F: Float,
+{
+    #[inline]
+    fn from(rgba: &Rgba) -> Self {
+        let max_value: F = Rgba::max_value();
+        LinearRgbPoint::new(
+            srgb_to_linear(rgba.r::<F>() / max_value),
+            srgb_to_linear(rgba.g::<F>() / max_value),
+            srgb_to_linear(rgba.b::<F>() / max_value),
+            rgba.a::<F>() / max_value,
+        )
+    }
+}
 
+impl<F> From<&LinearRgbPoint<F>> for Rgba
+where
+    F: Float,
+{
+    #[inline]
+    fn from(point: &LinearRgbPoint<F>) -> Self {
         let max_value = Rgba::max_value::<F>();
-        let r = Self::normalize_value((fr * max_value).round());
-        let g = Self::normalize_value((fg * max_value).round());
-        let b = Self::normalize_value((fb * max_value).round());
+        let f = |value: F| -> u8 {
+            let value = value.clamp(F::zero(), F::one());
+            Self::normalize_value((value * max_value).round())
+        };
         Self {
-            r,
-            g,
-            b,
-            a: Rgba::max_value(),
+            r: f(linear_to_srgb(point.0)),
+            g: f(linear_to_srgb(point.1)),
+            b: f(linear_to_srgb(point.2)),
+            a: f(point.3),
         }
     }
 }
@@ -180,6 +412,23 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::math::clustering::kmeans::cluster::Cluster;
+
+    #[test]
+    fn mix_should_average_in_linear_light() {
+        // A quarter of black and three quarters of white is 0.75 in linear light.
+        let gray = Rgba::mix(&[(Rgba::black(), 1.0_f64), (Rgba::white(), 3.0)]);
+        assert_eq!(gray, Rgba::new(225, 225, 225, 255));
+        assert_eq!(
+            gray,
+            Rgba::from(&LinearRgbPoint::new(0.75_f64, 0.75, 0.75, 1.0))
+        );
+
+        let red = Rgba::new(255, 0, 0, 255);
+        assert_eq!(Rgba::mix(&[(red.clone(), 0.5_f32)]), red);
+        assert_eq!(Rgba::mix::<f64>(&[]), Rgba::new(0, 0, 0, 0));
+        assert_eq!(Rgba::mix(&[(red, 0.0_f64)]), Rgba::new(0, 0, 0, 0));
+    }
 
     #[test]
     fn new_should_create_rgba_color() {
@@ -196,6 +445,37 @@ mod tests {
         assert_eq!(rgba.to_string(), "Rgba(0, 64, 255, 128)");
     }
 
+    #[test]
+    fn is_grayscale_should_check_channel_spread() {
+        assert!(Rgba::new(0x80, 0x80, 0x80, 255).is_grayscale(0));
+        assert!(Rgba::new(128, 130, 126, 255).is_grayscale(4));
+        assert!(!Rgba::new(128, 130, 126, 255).is_grayscale(3));
+        assert!(!Rgba::new(0xff, 0x00, 0x00, 255).is_grayscale(16));
+    }
+
+    #[test]
+    fn chroma_should_return_lab_chroma() {
+        let gray: f64 = Rgba::new(0x80, 0x80, 0x80, 255).chroma();
+        assert!(gray < 1e-3);
+
+        let red: f64 = Rgba::red().chroma();
+        assert!((red - 104.55).abs() < 0.1);
+    }
+
+    #[test]
+    fn contrast_ratio_should_follow_wcag() {
+        let ratio: f64 = Rgba::black().contrast_ratio(&Rgba::white());
+        assert!((ratio - 21.0).abs() < 1e-9);
+        assert_eq!(ratio, Rgba::white().contrast_ratio(&Rgba::black()));
+
+        let ratio: f64 = Rgba::red().contrast_ratio(&Rgba::red());
+        assert_eq!(ratio, 1.0);
+
+        // #767676 is the lightest gray with a ratio of at least 4.5 on white.
+        let ratio: f64 = Rgba::new(0x76, 0x76, 0x76, 255).contrast_ratio(&Rgba::white());
+        assert!((ratio - 4.54).abs() < 0.01);
+    }
+
     #[test]
     fn from_xyz_should_create_rgba_color() {
         let black = XYZ::new(0.0, 0.0, 0.0);
@@ -218,6 +498,38 @@ mod tests {
         assert_eq!(Rgba::from(&blue), Rgba::blue());
     }
 
+    #[test]
+    fn apply_should_round_value() {
+        assert_eq!(RoundingMode::Round.apply(127.6), 128.0);
+        assert_eq!(RoundingMode::Floor.apply(127.6), 127.0);
+        assert_eq!(RoundingMode::Truncate.apply(127.6), 127.0);
+        assert_eq!(RoundingMode::Round.apply(-1.5), -2.0);
+        assert_eq!(RoundingMode::Floor.apply(-1.5), -2.0);
+        assert_eq!(RoundingMode::Truncate.apply(-1.5), -1.0);
+        assert_eq!(RoundingMode::default(), RoundingMode::Round);
+    }
+
+    #[test]
+    fn from_xyz_with_should_use_rounding_mode() {
+        // Gray whose channel value is about 127.6.
+        let linear = srgb_to_linear(127.6 / 255.0);
+        let gray = XYZ::new(0.9504559 * linear, linear, 1.0890578 * linear);
+
+        assert_eq!(Rgba::from(&gray), Rgba::new(128, 128, 128, 255));
+        assert_eq!(
+            Rgba::from_xyz_with(&gray, RoundingMode::Round),
+            Rgba::new(128, 128, 128, 255)
+        );
+        assert_eq!(
+            Rgba::from_xyz_with(&gray, RoundingMode::Floor),
+            Rgba::new(127, 127, 127, 255)
+        );
+        assert_eq!(
+            Rgba::from_xyz_with(&gray, RoundingMode::Truncate),
+            Rgba::new(127, 127, 127, 255)
+        );
+    }
+
     #[test]
     fn should_return_value_to_be_cast() {
         let rgba = Rgba::new(0, 64, 255, 128);
@@ -226,4 +538,97 @@ mod tests {
         assert_eq!(rgba.b::<f64>(), 255.0);
         assert_eq!(rgba.a::<f64>(), 128.0);
     }
+
+    #[test]
+    fn from_linear_rgb_point_should_round_trip() {
+        for rgba in [Rgba::white(), Rgba::black(), Rgba::new(12, 128, 200, 64)] {
+            let point: LinearRgbPoint<f64> = LinearRgbPoint::from(&rgba);
+            assert_eq!(Rgba::from(&point), rgba);
+        }
+    }
+
+    #[test]
+    fn linear_rgb_point_should_average_in_linear_light() {
+        let black: LinearRgbPoint<f64> = LinearRgbPoint::from(&Rgba::black());
+        let white: LinearRgbPoint<f64> = LinearRgbPoint::from(&Rgba::white());
+        let mut cluster = Cluster::new(&black);
+        cluster.clear();
+        cluster.insert(0, &black);
+        cluster.insert(1, &white);
+        cluster.update_centroid();
+
+        let average = Rgba::from(cluster.centroid());
+        assert_eq!(average, Rgba::new(188, 188, 188, 255));
+    }
+
+    #[test]
+    fn distance_squared_should_sum_squared_channel_differences() {
+        let black: Rgba = "#000000".parse().unwrap();
+        let near_black: Rgba = "#010101".parse().unwrap();
+        assert_eq!(black.distance_squared(&near_black), 3);
+        assert_eq!(near_black.distance_squared(&black), 3);
+        assert_eq!(
+            Rgba::black().distance_squared(&Rgba::white()),
+            3 * 255 * 255
+        );
+        assert_eq!(
+            Rgba::new(10, 20, 30, 0).distance_squared(&Rgba::new(10, 20, 30, 255)),
+            0
+        );
+    }
+
+    #[test]
+    fn from_str_should_parse_hex_notation() {
+        assert_eq!("#ff8000".parse(), Ok(Rgba::new(255, 128, 0, 255)));
+        assert_eq!("#0010FF".parse(), Ok(Rgba::new(0, 16, 255, 255)));
+        assert_eq!(" #ff800080 ".parse(), Ok(Rgba::new(255, 128, 0, 128)));
+    }
+
+    #[test]
+    fn from_str_should_parse_rgb_notation() {
+        assert_eq!("rgb(255, 0, 0)".parse(), Ok(Rgba::new(255, 0, 0, 255)));
+        assert_eq!("RGB(0,128,255)".parse(), Ok(Rgba::new(0, 128, 255, 255)));
+    }
+
+    #[test]
+    fn from_str_should_parse_rgba_notation() {
+        assert_eq!("rgba(255,0,0,0.5)".parse(), Ok(Rgba::new(255, 0, 0, 128)));
+        assert_eq!("rgba(0, 0, 255, 1)".parse(), Ok(Rgba::new(0, 0, 255, 255)));
+        assert_eq!("rgba(0, 0, 255, 0)".parse(), Ok(Rgba::new(0, 0, 255, 0)));
+    }
+
+    #[test]
+    fn from_str_should_reject_malformed_string() {
+        for input in [
+            "",
+            "#",
+            "#ff80",
+            "#ff800",
+            "#gg8000",
+            "ff8000",
+            "rgb(255, 0)",
+            "rgb(256, 0, 0)",
+            "rgb(-1, 0, 0)",
+            "rgb(255, 0, 0",
+            "rgb(255, 0, 0, 0.5)",
+            "rgba(255, 0, 0)",
+            "rgba(255, 0, 0, 1.5)",
+            "rgba(255, 0, 0, half)",
+            "hsl(0, 100%, 50%)",
+        ] {
+            let actual: core::result::Result<Rgba, _> = input.parse();
+            assert_eq!(
+                actual,
+                Err(ParseRgbaError {
+                    input: input.to_string()
+                })
+            );
+        }
+        let error = "rgb(1, 2)".parse::<Rgba>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid color 'rgb(1, 2)', expected '#rrggbb', '#rrggbbaa', 'rgb(r, g, b)' or \
+             'rgba(r, g, b, a)'"
+        );
+    }
 }
@@ -0,0 +1,243 @@
+use crate::color::gamut::map_to_srgb;
+use crate::color::hsl::Hsl;
+use crate::color::lab::Lab;
+use crate::color::lch::Lch;
+use crate::color::oklab::Oklab;
+use crate::color::rgba::Rgba;
+use crate::color::xyz::XYZ;
+use crate::math::number::Float;
+
+/// The range of the a and b components of sRGB colors in Oklab color space.
+const OKLAB_DELTA_AB: f64 = 0.8;
+
+/// Trait for color spaces convertible through CIE XYZ color space with the D65 white point.
+pub trait ColorSpace<F: Float>: Sized {
+    /// Convert this color to CIE XYZ color space.
+    #[must_use]
+    fn to_xyz(&self) -> XYZ<F>;
+
+    /// Create a color from the given color in CIE XYZ color space.
+    #[must_use]
+    fn from_xyz(xyz: &XYZ<F>) -> Self;
+
+    /// Return the opacity of this color in [0, 1], which is 1 for color spaces without alpha.
+    #[must_use]
+    fn alpha(&self) -> F {
+        F::one()
+    }
+
+    /// Set the opacity of this color in [0, 1], which is ignored by color spaces without alpha.
+    #[must_use]
+    fn with_alpha(self, _alpha: F) -> Self {
+        self
+    }
+}
+
+/// Trait for color spaces in which the colors of an image are clustered.
+///
+/// Each component is normalized by the range of its axis, so that a single neighborhood radius
+/// suits every axis. The components are ordered as `[lightness, a, b]`:
+///
+/// - The lightness is in [0, 1], from black to white. It is the component to be equalized.
+/// - The a and b components are opponent axes that are 0 for a neutral color, so that their norm
+///   is the normalized chroma. It is the component raised by the vibrance weighting.
+pub trait WorkingSpace<F: Float>: ColorSpace<F> {
+    /// Return the normalized components of this color in the order `[lightness, a, b]`.
+    #[must_use]
+    fn to_components(&self) -> [F; 3];
+
+    /// Create a color from the given normalized components.
+    #[must_use]
+    fn from_components(components: [F; 3]) -> Self;
+
+    /// Convert this color to CIE L*a*b* color space, in which it is mapped into the sRGB gamut.
+    #[must_use]
+    fn to_lab(&self) -> Lab<F> {
+        Lab::from(&self.to_xyz())
+    }
+}
+
+/// Convert the given color to another color space.
+///
+/// The alpha is kept if both color spaces have alpha, and a color converted from a color space
+/// without alpha is opaque.
+#[inline]
+#[must_use]
+pub fn convert<F, A, B>(color: &A) -> B
+where
+    F: Float,
+    A: ColorSpace<F>,
+    B: ColorSpace<F>,
+{
+    B::from_xyz(&color.to_xyz()).with_alpha(color.alpha())
+}
+
+impl<F: Float> ColorSpace<F> for XYZ<F> {
+    fn to_xyz(&self) -> XYZ<F> {
+        self.clone()
+    }
+
+    fn from_xyz(xyz: &XYZ<F>) -> Self {
+        xyz.clone()
+    }
+}
+
+impl<F: Float> ColorSpace<F> for Rgba {
+    fn to_xyz(&self) -> XYZ<F> {
+        XYZ::from(self)
+    }
+
+    /// Create an opaque color, mapping out-of-gamut colors into the sRGB gamut.
+    fn from_xyz(xyz: &XYZ<F>) -> Self {
+        map_to_srgb(&Lab::from(xyz))
+    }
+
+    fn alpha(&self) -> F {
+        self.a::<F>() / Rgba::max_value::<F>()
+    }
+
+    fn with_alpha(mut self, alpha: F) -> Self {
+        self.a = (alpha.clamp(F::zero(), F::one()) * Rgba::max_value::<F>())
+            .round()
+            .to_u8()
+            .expect("Clamped alpha should be converted to u8");
+        self
+    }
+}
+
+impl<F: Float> ColorSpace<F> for Lab<F> {
+    fn to_xyz(&self) -> XYZ<F> {
+        XYZ::from(self)
+    }
+
+    fn from_xyz(xyz: &XYZ<F>) -> Self {
+        Lab::from(xyz)
+    }
+}
+
+impl<F: Float> WorkingSpace<F> for Lab<F> {
+    fn to_components(&self) -> [F; 3] {
+        [
+            self.l / (Lab::<F>::max_l::<F>() - Lab::<F>::min_l::<F>()),
+            self.a / (Lab::<F>::max_a::<F>() - Lab::<F>::min_a::<F>()),
+            self.b / (Lab::<F>::max_b::<F>() - Lab::<F>::min_b::<F>()),
+        ]
+    }
+
+    fn from_components([l, a, b]: [F; 3]) -> Self {
+        Lab::new(
+            l * (Lab::<F>::max_l::<F>() - Lab::<F>::min_l::<F>()),
+            a * (Lab::<F>::max_a::<F>() - Lab::<F>::min_a::<F>()),
+            b * (Lab::<F>::max_b::<F>() - Lab::<F>::min_b::<F>()),
+        )
+    }
+
+    /// Return this color as is, since the conversion through CIE XYZ color space would clamp a
+    /// color out of the sRGB gamut.
+    fn to_lab(&self) -> Lab<F> {
+        self.clone()
+    }
+}
+
+impl<F: Float> ColorSpace<F> for Lch<F> {
+    fn to_xyz(&self) -> XYZ<F> {
+        XYZ::from(&Lab::from(self))
+    }
+
+    fn from_xyz(xyz: &XYZ<F>) -> Self {
+        Lch::from(&Lab::from(xyz))
+    }
+}
+
+impl<F: Float> ColorSpace<F> for Oklab<F> {
+    fn to_xyz(&self) -> XYZ<F> {
+        XYZ::from(self)
+    }
+
+    fn from_xyz(xyz: &XYZ<F>) -> Self {
+        Oklab::from(xyz)
+    }
+}
+
+impl<F: Float> WorkingSpace<F> for Oklab<F> {
+    /// The lightness is already in [0, 1], and the a and b components are divided by their range.
+    fn to_components(&self) -> [F; 3] {
+        let delta_ab = F::from_f64(OKLAB_DELTA_AB);
+        [self.l, self.a / delta_ab, self.b / delta_ab]
+    }
+
+    fn from_components([l, a, b]: [F; 3]) -> Self {
+        let delta_ab = F::from_f64(OKLAB_DELTA_AB);
+        Oklab::new(l, a * delta_ab, b * delta_ab)
+    }
+}
+
+impl<F: Float> ColorSpace<F> for Hsl<F> {
+    fn to_xyz(&self) -> XYZ<F> {
+        XYZ::from(self)
+    }
+
+    fn from_xyz(xyz: &XYZ<F>) -> Self {
+        Hsl::from(xyz)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_should_convert_between_color_spaces() {
+        let rgba = Rgba::new(30, 144, 255, 255);
+        let oklab: Oklab<f64> = convert(&rgba);
+        let hsl: Hsl<f64> = convert(&oklab);
+        let expected: Hsl<f64> = Hsl::from(&rgba);
+        assert!((hsl.h - expected.h).abs() < 1e-3);
+        assert!((hsl.s - expected.s).abs() < 1e-3);
+        assert!((hsl.l - expected.l).abs() < 1e-3);
+
+        let actual: Rgba = convert::<f64, _, _>(&hsl);
+        assert_eq!(actual, rgba);
+    }
+
+    #[test]
+    fn convert_should_round_trip_through_every_color_space() {
+        let rgba = Rgba::new(200, 120, 40, 255);
+        let xyz: XYZ<f64> = convert(&rgba);
+        let lab: Lab<f64> = convert(&xyz);
+        let lch: Lch<f64> = convert(&lab);
+        let oklab: Oklab<f64> = convert(&lch);
+        let hsl: Hsl<f64> = convert(&oklab);
+        let actual: Rgba = convert::<f64, _, _>(&hsl);
+        assert_eq!(actual, rgba);
+    }
+
+    #[test]
+    fn convert_should_keep_alpha_between_color_spaces_with_alpha() {
+        let rgba = Rgba::new(200, 120, 40, 128);
+        let actual: Rgba = convert::<f64, _, _>(&rgba);
+        assert_eq!(actual, rgba);
+
+        let lab: Lab<f64> = convert(&rgba);
+        let actual: Rgba = convert::<f64, _, _>(&lab);
+        assert_eq!(actual, Rgba::new(200, 120, 40, 255));
+    }
+
+    #[test]
+    fn components_should_round_trip_in_working_spaces() {
+        let rgba = Rgba::new(30, 144, 255, 255);
+        let lab: Lab<f64> = convert(&rgba);
+        let actual = Lab::from_components(lab.to_components());
+        assert!((actual.l - lab.l).abs() < 1e-12);
+        assert!((actual.a - lab.a).abs() < 1e-12);
+        assert!((actual.b - lab.b).abs() < 1e-12);
+
+        let oklab: Oklab<f64> = convert(&rgba);
+        let components = oklab.to_components();
+        assert!(components.iter().all(|value| value.abs() <= 1.0));
+        let actual = Oklab::from_components(components);
+        assert!((actual.l - oklab.l).abs() < 1e-12);
+        assert!((actual.a - oklab.a).abs() < 1e-12);
+        assert!((actual.b - oklab.b).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::math::number::Float;
+
+/// Convert a gamma-encoded sRGB value in [0, 1] to linear light.
+///
+/// [sRGB - Wikipedia](https://en.wikipedia.org/wiki/SRGB)
+#[inline]
+#[must_use]
+pub(crate) fn srgb_to_linear<F: Float>(value: F) -> F {
+    if value <= F::from_f64(0.04045) {
+        value / F::from_f64(12.92)
+    } else {
+        ((value + F::from_f64(0.055)) / F::from_f64(1.055)).powf(F::from_f64(2.4))
+    }
+}
+
+/// Convert a linear light value in [0, 1] to a gamma-encoded sRGB value.
+#[inline]
+#[must_use]
+pub(crate) fn linear_to_srgb<F: Float>(value: F) -> F {
+    if value <= F::from_f64(0.0031308) {
+        F::from_f64(12.92) * value
+    } else {
+        F::from_f64(1.055) * value.powf(F::from_f64(1.0 / 2.4)) - F::from_f64(0.055)
+    }
+}
+
+/// Transfer function decoding the encoded channel values of a color into linear light.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum TransferFunction<F: Float> {
+    /// The piecewise sRGB transfer function.
+    #[default]
+    Srgb,
+    /// A pure power function with the given gamma, such as 2.2 for many displays.
+    Gamma(F),
+    /// The identity function for linearly encoded values.
+    Linear,
+}
+
+impl<F> TransferFunction<F>
+where
+    F: Float,
+{
+    /// Decode the given encoded value in [0, 1] to linear light.
+    #[inline]
+    #[must_use]
+    pub fn decode(&self, value: F) -> F {
+        match self {
+            TransferFunction::Srgb => srgb_to_linear(value),
+            TransferFunction::Gamma(gamma) => value.powf(*gamma),
+            TransferFunction::Linear => value,
+        }
+    }
+
+    /// Encode the given linear light value in [0, 1].
+    #[inline]
+    #[must_use]
+    pub fn encode(&self, value: F) -> F {
+        match self {
+            TransferFunction::Srgb => linear_to_srgb(value),
+            TransferFunction::Gamma(gamma) => value.powf(gamma.recip()),
+            TransferFunction::Linear => value,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn srgb_to_linear_should_decode_value() {
+        assert_eq!(srgb_to_linear(0.0), 0.0);
+        assert_eq!(srgb_to_linear(0.04045), 0.04045 / 12.92);
+        assert_eq!(srgb_to_linear(1.0), 1.0);
+    }
+
+    #[test]
+    fn linear_to_srgb_should_encode_value() {
+        assert_eq!(linear_to_srgb(0.0), 0.0);
+        assert!((linear_to_srgb(1.0_f64) - 1.0).abs() < 1e-12);
+        assert!((linear_to_srgb(srgb_to_linear(0.5_f64)) - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn decode_should_apply_transfer_function() {
+        let srgb = TransferFunction::Srgb;
+        let gamma = TransferFunction::Gamma(2.2);
+        let linear = TransferFunction::Linear;
+        assert_eq!(TransferFunction::default(), srgb);
+        assert_eq!(srgb.decode(0.5_f64), srgb_to_linear(0.5));
+        assert!((gamma.decode(0.5_f64) - 0.5_f64.powf(2.2)).abs() < 1e-12);
+        assert_eq!(linear.decode(0.5_f64), 0.5);
+
+        for function in [srgb, gamma, linear] {
+            assert!((function.encode(function.decode(0.25_f64)) - 0.25).abs() < 1e-12);
+        }
+    }
+}
@@ -1,13 +1,15 @@
 use crate::color::lab::Lab;
 use crate::color::rgba::Rgba;
+use crate::color::transfer::TransferFunction;
 use crate::color::white_point::{WhitePoint, D65};
 use crate::math::number::Float;
-use std::fmt::{Display, Formatter, Result};
-use std::marker::PhantomData;
+use core::fmt::{Display, Formatter, Result};
+use core::marker::PhantomData;
 
 /// Color in CIE XYZ color space.
 ///
 /// [CIE 1931 color space - Wikipedia](https://en.wikipedia.org/wiki/CIE_1931_color_space)
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone, PartialEq)]
 pub struct XYZ<F: Float, W: WhitePoint<F> = D65> {
     pub x: F,
@@ -29,10 +31,33 @@ where
             x: Self::normalize_x(x),
             y: Self::normalize_y(y),
             z: Self::normalize_z(z),
-            _w: PhantomData::default(),
+            _w: PhantomData,
         }
     }
 
+    /// Create a color from the given RGB color encoded with the given transfer function.
+    ///
+    /// The color is assumed to have the sRGB primaries, so [`TransferFunction::Srgb`] is
+    /// equivalent to the conversion by `From<&Rgba>`.
+    #[must_use]
+    pub fn from_rgba_with(rgba: &Rgba, transfer: &TransferFunction<F>) -> XYZ<F, W> {
+        let max_value: F = Rgba::max_value();
+        let r = transfer.decode(rgba.r::<F>() / max_value);
+        let g = transfer.decode(rgba.g::<F>() / max_value);
+        let b = transfer.decode(rgba.b::<F>() / max_value);
+        XYZ::from_linear_rgb(r, g, b)
+    }
+
+    /// Create a color from the given linear RGB values.
+    #[inline]
+    #[must_use]
+    pub(crate) fn from_linear_rgb(r: F, g: F, b: F) -> XYZ<F, W> {
+        let x = F::from_f64(0.412391) * r + F::from_f64(0.357584) * g + F::from_f64(0.180481) * b;
+        let y = F::from_f64(0.212639) * r + F::from_f64(0.715169) * g + F::from_f64(0.072192) * b;
+        let z = F::from_f64(0.019331) * r + F::from_f64(0.119195) * g + F::from_f64(0.950532) * b;
+        XYZ::new(x, y, z)
+    }
+
     /// Return min value of x.
     #[inline]
     #[must_use]
@@ -111,23 +136,7 @@ where
 {
     #[inline]
     fn from(rgba: &Rgba) -> Self {
-        let f = |value: F| -> F {
-            if value <= F::from_f64(0.04045) {
-                value / F::from_f64(12.92)
-            } else {
-                ((value + F::from_f64(0.055)) / F::from_f64(1.055)).powf(F::from_f64(2.4))
-            }
-        };
-
-        let max_value: F = Rgba::max_value();
-        let r = f(rgba.r::<F>() / max_value);
-        let g = f(rgba.g::<F>() / max_value);
-        let b = f(rgba.b::<F>() / max_value);
-
-        let x = F::from_f64(0.412391) * r + F::from_f64(0.357584) * g + F::from_f64(0.180481) * b;
-        let y = F::from_f64(0.212639) * r + F::from_f64(0.715169) * g + F::from_f64(0.072192) * b;
-        let z = F::from_f64(0.019331) * r + F::from_f64(0.119195) * g + F::from_f64(0.950532) * b;
-        XYZ::new(x, y, z)
+        XYZ::from_rgba_with(rgba, &TransferFunction::Srgb)
     }
 }
 
@@ -163,6 +172,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::color::approx_eq;
 
     #[test]
     fn new_should_create_xyz_color() {
@@ -221,33 +231,56 @@ mod tests {
         assert_eq!(XYZ::from(&transparent), XYZ::<f64, D65>::new(0.0, 0.0, 0.0));
     }
 
+    #[test]
+    fn from_rgba_with_should_decode_with_transfer_function() {
+        let gray = Rgba::new(128, 128, 128, 255);
+        let srgb: XYZ<f64> = XYZ::from_rgba_with(&gray, &TransferFunction::Srgb);
+        assert_eq!(srgb, XYZ::from(&gray));
+
+        let gamma: XYZ<f64> = XYZ::from_rgba_with(&gray, &TransferFunction::Gamma(2.2));
+        assert!((gamma.y - srgb.y).abs() > 1e-3);
+
+        let linear: XYZ<f64> = XYZ::from_rgba_with(&gray, &TransferFunction::Linear);
+        let value = 128.0 / 255.0;
+        assert_eq!(linear, XYZ::from_linear_rgb(value, value, value));
+        assert!((linear.y - value).abs() < 1e-3);
+    }
+
     #[test]
     fn from_lab_should_convert_to_xyz() {
         let black = Lab::new(0.0, 0.0, 0.0);
-        assert_eq!(XYZ::from(&black), XYZ::<f64, D65>::new(0.0, 0.0, 0.0));
+        assert!(approx_eq(
+            &XYZ::from(&black),
+            &XYZ::<f64, D65>::new(0.0, 0.0, 0.0),
+            1e-12
+        ));
 
         let white = Lab::new(100.0, 0.0, 0.0);
-        assert_eq!(
-            XYZ::from(&white),
-            XYZ::<f64, D65>::new(0.9504560232162476, 1.0, 1.088644027709961)
-        );
+        assert!(approx_eq(
+            &XYZ::from(&white),
+            &XYZ::<f64, D65>::new(0.9504560232162476, 1.0, 1.088644027709961),
+            1e-12
+        ));
 
         let red = Lab::new(53.23711495815769, 80.08963699438709, 67.2031352432351);
-        assert_eq!(
-            XYZ::from(&red),
-            XYZ::<f64, D65>::new(0.412391, 0.212639, 0.01933100000000002)
-        );
+        assert!(approx_eq(
+            &XYZ::from(&red),
+            &XYZ::<f64, D65>::new(0.412391, 0.212639, 0.019331),
+            1e-12
+        ));
 
         let green = Lab::new(87.73553464128194, -86.18229362351477, 83.1866539998871);
-        assert_eq!(
-            XYZ::from(&green),
-            XYZ::<f64, D65>::new(0.35758400000000007, 0.715169, 0.11919500000000006)
-        );
+        assert!(approx_eq(
+            &XYZ::from(&green),
+            &XYZ::<f64, D65>::new(0.357584, 0.715169, 0.119195),
+            1e-12
+        ));
 
         let blue = Lab::new(32.30080257229819, 79.1952752634909, -107.85544501392465);
-        assert_eq!(
-            XYZ::from(&blue),
-            XYZ::<f64, D65>::new(0.18048099999999997, 0.072192, 0.9505320000000002)
-        );
+        assert!(approx_eq(
+            &XYZ::from(&blue),
+            &XYZ::<f64, D65>::new(0.180481, 0.072192, 0.950532),
+            1e-12
+        ));
     }
 }
@@ -1,83 +1,732 @@
+use crate::color::gamut::to_rgba;
 use crate::color::lab::Lab;
 use crate::color::rgba::Rgba;
+use crate::color::space::WorkingSpace;
 use crate::color::white_point::D65;
 use crate::color::xyz::XYZ;
 use crate::math::clustering::dbscan::algorithm::DBSCAN;
 use crate::math::clustering::dbscan::params::Params;
+use crate::math::clustering::kmeans::algorithm::Kmeans;
+use crate::math::clustering::kmeans::init::Initializer;
+use crate::math::clustering::kmeans::params::KmeansParams;
 use crate::math::clustering::traits::Fit;
 use crate::math::distance::metric::DistanceMetric;
+use crate::math::neighbors::kdtree::KDTree;
 use crate::math::number::{Float, Number};
-use crate::math::point::Point5;
+use crate::math::point::{LinearRgbPoint, Point3, Point5};
+use crate::options::ExtractOptions;
+use crate::palette::Palette;
 use crate::swatch::Swatch;
+use num_traits::Zero;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::fmt::{Display, Formatter};
 
+/// Error returned when extracting a palette from invalid image data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtractError {
+    /// The channel value at the given index of the data is NaN or infinite.
+    NonFinite { index: usize },
+}
+
+impl Display for ExtractError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExtractError::NonFinite { index } => {
+                write!(f, "Non-finite channel value at index {index}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExtractError {}
+
+/// Image data holding RGBA pixels in row-major order.
+#[derive(Debug, Clone)]
 pub struct ImageData<'a> {
     data: &'a [u8],
     pub width: u32,
     pub height: u32,
+    /// The number of pixels between the starts of consecutive rows in the data.
+    stride: u32,
 }
 
 impl<'a> ImageData<'a> {
+    /// The maximum number of nearest pixels averaged by the smoothing.
+    const SMOOTHING_NEIGHBORS: usize = 32;
+
     #[must_use]
     pub fn new(data: &'a [u8], width: u32, height: u32) -> Self {
         Self {
             data,
             width,
             height,
+            stride: width,
+        }
+    }
+
+    /// Return a view of the given rectangular region of this image without copying the pixels.
+    ///
+    /// # Panics
+    /// Panics if the region is out of bounds.
+    fn region(&self, x: u32, y: u32, width: u32, height: u32) -> ImageData<'a> {
+        assert!(
+            x.checked_add(width)
+                .is_some_and(|right| right <= self.width)
+                && y.checked_add(height)
+                    .is_some_and(|bottom| bottom <= self.height),
+            "Region is out of bounds"
+        );
+        let start = ((y as usize * self.stride as usize + x as usize) * 4).min(self.data.len());
+        Self {
+            data: &self.data[start..],
+            width,
+            height,
+            stride: self.stride,
         }
     }
 
+    /// Return the raw RGBA bytes of this image.
     #[must_use]
-    pub fn extract<F: Float>(&self) -> Vec<Swatch<F>> {
-        let width_u64: u64 = self.width();
-        let height_u64: u64 = self.height();
-        let width_f: F = self.width();
-        let height_f: F = self.height();
+    pub fn data(&self) -> &[u8] {
+        self.data
+    }
 
-        let delta_l: F = Lab::<F>::max_l::<F>() - Lab::<F>::min_l::<F>();
-        let delta_a: F = Lab::<F>::max_a::<F>() - Lab::<F>::min_a::<F>();
-        let delta_b: F = Lab::<F>::max_b::<F>() - Lab::<F>::min_b::<F>();
-
-        let mut index = 0;
-        let mut pixels = Vec::with_capacity(self.data.len() / 4);
-        while index < self.data.len() {
-            let rgba = Rgba::new(
-                self.data[index],
-                self.data[index + 1],
-                self.data[index + 2],
-                self.data[index + 3],
-            );
+    /// Return the color of the pixel at the given coordinates.
+    ///
+    /// # Panics
+    /// Panics if the coordinates are out of bounds.
+    #[must_use]
+    pub fn pixel(&self, x: u32, y: u32) -> Rgba {
+        assert!(x < self.width && y < self.height, "Pixel is out of bounds");
+        let index = (y as usize * self.stride as usize + x as usize) * 4;
+        Rgba::new(
+            self.data[index],
+            self.data[index + 1],
+            self.data[index + 2],
+            self.data[index + 3],
+        )
+    }
+
+    /// Return an iterator over the pixels of this image in row-major order.
+    ///
+    /// Each item is a tuple of the x coordinate, the y coordinate and the color of the pixel.
+    pub fn pixels(&self) -> impl Iterator<Item = (u32, u32, Rgba)> + '_ {
+        let width = self.width as usize;
+        let row_len = (self.stride as usize * 4).max(1);
+        self.data
+            .chunks(row_len)
+            .take(self.height as usize)
+            .enumerate()
+            .flat_map(move |(y, row)| {
+                row.chunks_exact(4)
+                    .take(width)
+                    .enumerate()
+                    .map(move |(x, chunk)| {
+                        let rgba = Rgba::new(chunk[0], chunk[1], chunk[2], chunk[3]);
+                        (x as u32, y as u32, rgba)
+                    })
+            })
+    }
+
+    /// Extract a palette from the given RGBA pixels with floating-point channels in [0, 1].
+    ///
+    /// The channels are validated before any conversion, since a NaN would propagate through the
+    /// color conversion and the clustering and corrupt the palette. The values out of range are
+    /// clamped, and the pixels are quantized to 8 bits per channel and extracted as
+    /// [`extract_with`](Self::extract_with) does.
+    ///
+    /// # Errors
+    /// Returns [`ExtractError::NonFinite`] if any channel value is NaN or infinite.
+    pub fn extract_from_float<F: Float>(
+        data: &[F],
+        width: u32,
+        height: u32,
+        options: &ExtractOptions<F>,
+    ) -> Result<Palette<F>, ExtractError> {
+        // Both NaN and infinite values fail the comparison.
+        let is_finite = |value: &F| value.abs() <= F::max_value();
+        if let Some(index) = data.iter().position(|value| !is_finite(value)) {
+            return Err(ExtractError::NonFinite { index });
+        }
+
+        let max_value: F = Rgba::max_value();
+        let bytes: Vec<u8> = data
+            .iter()
+            .map(|&value| {
+                (value.clamp(F::zero(), F::one()) * max_value)
+                    .round()
+                    .to_u8()
+                    .expect("Clamped value should be converted to u8")
+            })
+            .collect();
+        Ok(ImageData::new(&bytes, width, height).extract_with(options))
+    }
+
+    /// Extract a palette from this image with the default options.
+    ///
+    /// The extraction is deterministic, so the same image always yields the same palette.
+    #[must_use]
+    pub fn extract<F: Float>(&self) -> Palette<F> {
+        self.extract_with(&ExtractOptions::default())
+    }
+
+    /// Extract a palette from this image with the given options.
+    ///
+    /// If the image has fewer pixels than the minimum number of points, the pixels are grouped by
+    /// color only so that small images still yield a palette. If downscaling is enabled, the
+    /// pixels are clustered in a thumbnail, and each pixel of the thumbnail counts the pixels of
+    /// its block in the populations and percentages.
+    #[must_use]
+    pub fn extract_with<F: Float>(&self, options: &ExtractOptions<F>) -> Palette<F> {
+        self.extract_in::<F, Lab<F>>(options)
+    }
+
+    /// Extract a palette from this image with the given options, clustering the pixels in the
+    /// given working color space.
+    ///
+    /// [`extract_with`](Self::extract_with) clusters the pixels in CIE L*a*b* color space. The
+    /// recommended epsilon is tuned for it, so another working space may need its own epsilon.
+    #[must_use]
+    pub fn extract_in<F, S>(&self, options: &ExtractOptions<F>) -> Palette<F>
+    where
+        F: Float,
+        S: WorkingSpace<F>,
+    {
+        self.extract_with_buffers::<F, S>(options, &mut Vec::new(), &mut Vec::new())
+    }
+
+    /// Extract a palette as [`extract_in`](Self::extract_in) does, using the given buffers for the
+    /// points and the weights of the pixels.
+    fn extract_with_buffers<F: Float, S: WorkingSpace<F>>(
+        &self,
+        options: &ExtractOptions<F>,
+        pixels: &mut Vec<Point5<F>>,
+        weights: &mut Vec<F>,
+    ) -> Palette<F> {
+        let factor = options.downscale();
+        if factor > 1 {
+            let (data, width, height, counts) = self.downscale(factor);
+            let thumbnail = ImageData::new(&data, width, height);
+            let palette = thumbnail.extract_blocks::<F, S>(options, pixels, weights, Some(&counts));
+            // Map each position to the center of its block in the original image.
+            let scale = |value: u32, max: u32| -> u32 {
+                (value.saturating_mul(factor) + factor / 2).min(max.saturating_sub(1))
+            };
+            return palette
+                .iter()
+                .map(|swatch| {
+                    let (x, y) = swatch.position();
+                    swatch
+                        .clone()
+                        .with_position((scale(x, self.width), scale(y, self.height)))
+                })
+                .collect::<Palette<F>>()
+                .with_outliers_of(&palette);
+        }
+        self.extract_blocks::<F, S>(options, pixels, weights, None)
+    }
+
+    /// Extract a palette without downscaling, where each pixel stands for the given number of
+    /// pixels of the original image.
+    ///
+    /// The counts only scale the swatches, so the pixels are clustered as they are.
+    fn extract_blocks<F: Float, S: WorkingSpace<F>>(
+        &self,
+        options: &ExtractOptions<F>,
+        pixels: &mut Vec<Point5<F>>,
+        weights: &mut Vec<F>,
+        counts: Option<&[usize]>,
+    ) -> Palette<F> {
+        self.weights_into(options.alpha_weighted(), weights);
+        self.points_into::<F, S>(pixels);
+        let dbscan = Self::cluster_points(options, pixels, weights);
+        if let Some(counts) = counts {
+            for (weight, &count) in weights.iter_mut().zip(counts) {
+                *weight *= F::from_usize(count);
+            }
+        }
+        let mut swatches =
+            self.swatches_with::<F, S>(pixels, weights, &Self::members(&dbscan), counts);
+        swatches.sort();
+
+        // The outliers are summarized like a swatch of their own.
+        let outliers = dbscan.outliers();
+        let outlier_weight = outliers
+            .iter()
+            .fold(F::zero(), |total, &index| total + weights[index]);
+        let outlier = (outlier_weight > F::zero())
+            .then(|| {
+                self.swatches_with::<F, S>(pixels, weights, &[outliers.as_slice()], counts)
+                    .pop()
+            })
+            .flatten();
+        Palette::new(swatches).with_outliers(
+            outlier.as_ref().map_or(F::zero(), Swatch::percentage),
+            outlier.as_ref().map(Swatch::color),
+        )
+    }
+
+    /// Extract a palette from the given rectangular region of this image with the given options.
+    ///
+    /// Only the pixels of the region are clustered, without copying them into a cropped buffer,
+    /// and the positions of the swatches are in the coordinates of the whole image.
+    ///
+    /// # Panics
+    /// Panics if the region is out of bounds.
+    #[must_use]
+    pub fn extract_region<F: Float>(
+        &self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        options: &ExtractOptions<F>,
+    ) -> Palette<F> {
+        let palette = self.region(x, y, width, height).extract_with(options);
+        palette
+            .iter()
+            .map(|swatch| {
+                let (swatch_x, swatch_y) = swatch.position();
+                swatch.clone().with_position((x + swatch_x, y + swatch_y))
+            })
+            .collect::<Palette<F>>()
+            .with_outliers_of(&palette)
+    }
+
+    /// Extract a palette of exactly `k` colors from this image with k-means clustering.
+    ///
+    /// The pixels are clustered by color only in the normalized CIE L*a*b* color space. Fewer
+    /// swatches are returned if the image has fewer than `k` pixels, or if a cluster ends up
+    /// without any pixel. The minimum number of points, the epsilon, the smoothing and the alpha
+    /// weighting of the options are not used, and the initial centroids are chosen randomly unless
+    /// a seed is set.
+    #[must_use]
+    pub fn extract_kmeans<F: Float>(&self, k: usize, options: &ExtractOptions<F>) -> Palette<F> {
+        let pixels = self.points::<F, Lab<F>>();
+        let equalized = options.equalize().then(|| Self::equalize(&pixels));
+        let colors: Vec<Point3<F>> = equalized
+            .as_deref()
+            .unwrap_or(&pixels)
+            .iter()
+            .map(|pixel| Point3::new(pixel[0], pixel[1], pixel[2]))
+            .collect();
+
+        let rng = options
+            .seed()
+            .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+        let params = KmeansParams::new(
+            k,
+            DistanceMetric::SquaredEuclidean,
+            Initializer::KmeansPlusPlus(rng),
+        );
+        let kmeans = Kmeans::fit(&colors, &params);
+
+        let mut members: Vec<Vec<usize>> = vec![Vec::new(); kmeans.centroids().len()];
+        for (index, label) in kmeans.labels().into_iter().enumerate() {
+            members[label].push(index);
+        }
+        let members: Vec<&[usize]> = members
+            .iter()
+            .filter(|indices| !indices.is_empty())
+            .map(Vec::as_slice)
+            .collect();
+
+        let weights = vec![F::one(); pixels.len()];
+        let mut swatches = self.swatches::<F, Lab<F>>(&pixels, &weights, &members);
+        swatches.sort();
+        Palette::new(swatches)
+    }
+
+    /// Extract a palette and a quantized copy of this image in a single pass.
+    ///
+    /// Each pixel of the returned RGBA buffer is replaced by the color of the swatch its cluster
+    /// belongs to, keeping the original alpha. Outlier pixels take the color of the nearest swatch
+    /// in CIE L*a*b* color space. If no swatch is found, the original pixels are returned as is.
+    #[must_use]
+    pub fn extract_and_quantize<F: Float>(
+        &self,
+        options: &ExtractOptions<F>,
+    ) -> (Palette<F>, Vec<u8>) {
+        let weights = self.weights(options.alpha_weighted());
+        let (pixels, dbscan) = self.cluster::<F, Lab<F>>(options, &weights);
+        let members = Self::members(&dbscan);
+        let mut swatches = self.swatches::<F, Lab<F>>(&pixels, &weights, &members);
+
+        let mut buffer: Vec<u8> = self
+            .pixels()
+            .flat_map(|(_, _, rgba)| [rgba.r, rgba.g, rgba.b, rgba.a])
+            .collect();
+        if !swatches.is_empty() {
+            let centroids = Self::centroids(&pixels, &weights, &members);
+            let mut labels = vec![0; pixels.len()];
+            for (cluster_id, indices) in members.iter().enumerate() {
+                for &index in indices.iter() {
+                    labels[index] = cluster_id;
+                }
+            }
+            let colors: Vec<Point3<F>> = centroids
+                .iter()
+                .map(|centroid| Point3::new(centroid[0], centroid[1], centroid[2]))
+                .collect();
+            let nns = KDTree::new(&colors, &DistanceMetric::SquaredEuclidean);
+            for index in dbscan.outliers() {
+                let pixel = &pixels[index];
+                // No radius is imposed, so every outlier finds the nearest centroid.
+                labels[index] = nns
+                    .search_nearest_within(
+                        &Point3::new(pixel[0], pixel[1], pixel[2]),
+                        F::max_value(),
+                    )
+                    .map_or(0, |neighbor| neighbor.index);
+            }
+
+            for (chunk, &cluster_id) in buffer.chunks_exact_mut(4).zip(labels.iter()) {
+                let color = swatches[cluster_id].color();
+                chunk[0] = color.r;
+                chunk[1] = color.g;
+                chunk[2] = color.b;
+            }
+        }
+
+        swatches.sort();
+        (Palette::new(swatches), buffer)
+    }
+
+    /// Extract a palette whose percentages are computed from soft assignments of the pixels.
+    ///
+    /// After the swatches are found, each pixel contributes to every swatch with a softmax weight
+    /// of the negative squared color distance divided by the temperature, so a pixel on a color
+    /// boundary is split between the swatches. The temperature is measured in the squared
+    /// normalized CIE L*a*b* color space, and a lower temperature approaches hard assignment. The
+    /// populations of the swatches are still counted by hard assignment.
+    ///
+    /// # Panics
+    /// Panics if the temperature is not positive.
+    #[must_use]
+    pub fn extract_soft<F: Float>(
+        &self,
+        options: &ExtractOptions<F>,
+        temperature: F,
+    ) -> Palette<F> {
+        assert!(temperature > F::zero(), "Temperature should be positive");
+        let weights = self.weights(options.alpha_weighted());
+        let (pixels, dbscan) = self.cluster::<F, Lab<F>>(options, &weights);
+        let members = Self::members(&dbscan);
+        let swatches = self.swatches::<F, Lab<F>>(&pixels, &weights, &members);
+        if swatches.is_empty() {
+            return Palette::new(swatches);
+        }
+
+        let centroids = Self::centroids(&pixels, &weights, &members);
+        let mut totals = vec![F::zero(); centroids.len()];
+        for (pixel, &weight) in pixels.iter().zip(weights.iter()) {
+            let memberships = Self::soft_memberships(pixel, &centroids, temperature);
+            for (total, membership) in totals.iter_mut().zip(memberships) {
+                *total += membership * weight;
+            }
+        }
+
+        let population = weights
+            .iter()
+            .fold(F::zero(), |total, &weight| total + weight);
+        let mut swatches: Vec<Swatch<F>> = swatches
+            .into_iter()
+            .zip(totals)
+            .map(|(swatch, total)| swatch.with_percentage(total / population))
+            .collect();
+        swatches.sort();
+        Palette::new(swatches)
+    }
+
+    /// Compute the softmax membership of the pixel to each centroid by the color distance.
+    fn soft_memberships<F: Float>(
+        pixel: &Point5<F>,
+        centroids: &[Point5<F>],
+        temperature: F,
+    ) -> Vec<F> {
+        let distances: Vec<F> = centroids
+            .iter()
+            .map(|centroid| {
+                (0..3).fold(F::zero(), |total, axis| {
+                    total + (pixel[axis] - centroid[axis]).powi(2)
+                })
+            })
+            .collect();
+        // Shift by the minimum distance to avoid underflow of the exponentials.
+        let min = distances
+            .iter()
+            .copied()
+            .min_by(|d1, d2| d1.total_order(d2))
+            .unwrap_or(F::zero());
+        let weights: Vec<F> = distances
+            .iter()
+            .map(|&distance| (-(distance - min) / temperature).exp())
+            .collect();
+        let total = weights
+            .iter()
+            .fold(F::zero(), |total, &weight| total + weight);
+        weights.into_iter().map(|weight| weight / total).collect()
+    }
+
+    /// Convert each pixel into a point of the normalized color in the given working space and
+    /// the normalized position.
+    fn points<F: Float, S: WorkingSpace<F>>(&self) -> Vec<Point5<F>> {
+        let mut pixels = Vec::with_capacity(self.width as usize * self.height as usize);
+        self.points_into::<F, S>(&mut pixels);
+        pixels
+    }
+
+    /// Convert each pixel into a point as [`points`](Self::points) does, replacing the contents
+    /// of the given buffer.
+    fn points_into<F: Float, S: WorkingSpace<F>>(&self, pixels: &mut Vec<Point5<F>>) {
+        pixels.clear();
+        pixels.reserve(self.width as usize * self.height as usize);
+        for (x, y, rgba) in self.pixels() {
             let xyz: XYZ<F, D65> = XYZ::from(&rgba);
-            let Lab { l, a, b, .. } = Lab::from(&xyz);
-
-            let index_u64 = u64::from_usize(index);
-            let x = F::from_u64(index_u64 / 4 % width_u64);
-            let y = F::from_u64((index_u64 / 4 / width_u64) % height_u64);
-            // Normalize each value and convert as a point.
-            pixels.push(Point5::new(
-                l / delta_l,
-                a / delta_a,
-                b / delta_b,
-                x / width_f,
-                y / height_f,
-            ));
-            index += 4;
-        }
-
-        let params = Params::new(25, F::from_f64(0.025), DistanceMetric::Euclidean);
-        let dbscan = DBSCAN::fit(&pixels, &params);
-        let mut swatches: Vec<Swatch<F>> = dbscan
-            .centroids()
+            let [c1, c2, c3] = S::from_xyz(&xyz).to_components();
+
+            // Normalize the position and convert as a point.
+            let x = Self::ratio(x as usize, self.width as usize);
+            let y = Self::ratio(y as usize, self.height as usize);
+            pixels.push(Point5::new(c1, c2, c3, x, y));
+        }
+    }
+
+    /// Downscale this image by averaging each block of `factor` x `factor` pixels in linear light.
+    ///
+    /// The blocks on the right and bottom edges may be smaller. Returns the RGBA bytes, the width
+    /// and the height of the downscaled image, and the number of pixels in the block of each pixel.
+    fn downscale(&self, factor: u32) -> (Vec<u8>, u32, u32, Vec<usize>) {
+        let width = self.width.div_ceil(factor);
+        let height = self.height.div_ceil(factor);
+        let mut totals = vec![(LinearRgbPoint::<f64>::zero(), 0_usize); (width * height) as usize];
+        for (x, y, rgba) in self.pixels() {
+            let index = ((y / factor) * width + x / factor) as usize;
+            let (total, count) = &mut totals[index];
+            *total += LinearRgbPoint::from(&rgba);
+            *count += 1;
+        }
+
+        let mut data = Vec::with_capacity(totals.len() * 4);
+        let mut counts = Vec::with_capacity(totals.len());
+        for (total, count) in totals {
+            let rgba = Rgba::from(&(total / count.max(1) as f64));
+            data.extend_from_slice(&[rgba.r, rgba.g, rgba.b, rgba.a]);
+            counts.push(count);
+        }
+        (data, width, height, counts)
+    }
+
+    /// Return the weight of each pixel, which is its alpha in [0, 1] if alpha weighting is enabled.
+    fn weights<F: Float>(&self, alpha_weighted: bool) -> Vec<F> {
+        let mut weights = Vec::new();
+        self.weights_into(alpha_weighted, &mut weights);
+        weights
+    }
+
+    /// Compute the weight of each pixel as [`weights`](Self::weights) does, replacing the contents
+    /// of the given buffer.
+    fn weights_into<F: Float>(&self, alpha_weighted: bool, weights: &mut Vec<F>) {
+        let max_value: F = Rgba::max_value();
+        weights.clear();
+        weights.extend(self.pixels().map(|(_, _, rgba)| {
+            if alpha_weighted {
+                rgba.a::<F>() / max_value
+            } else {
+                F::one()
+            }
+        }));
+    }
+
+    fn cluster<F: Float, S: WorkingSpace<F>>(
+        &self,
+        options: &ExtractOptions<F>,
+        weights: &[F],
+    ) -> (Vec<Point5<F>>, DBSCAN<F, Point5<F>>) {
+        let pixels = self.points::<F, S>();
+        let dbscan = Self::cluster_points(options, &pixels, weights);
+        (pixels, dbscan)
+    }
+
+    /// Cluster the given points of the pixels of an image.
+    ///
+    /// If the vibrance weighting is enabled, the weights are raised by the chroma of the pixels
+    /// for the clustering only.
+    fn cluster_points<F: Float>(
+        options: &ExtractOptions<F>,
+        pixels: &[Point5<F>],
+        weights: &[F],
+    ) -> DBSCAN<F, Point5<F>> {
+        let vibrance = options.vibrance();
+        let boosted: Option<Vec<F>> = (vibrance > F::zero()).then(|| {
+            pixels
+                .iter()
+                .zip(weights)
+                .map(|(pixel, &weight)| {
+                    let chroma = (pixel[1].powi(2) + pixel[2].powi(2)).sqrt();
+                    weight * (F::one() + vibrance * chroma)
+                })
+                .collect()
+        });
+        let weights = boosted.as_deref().unwrap_or(weights);
+
+        let params = options.params(5, pixels.len());
+        if pixels.len() < params.min_points() {
+            // Too few pixels to form a dense cluster, so group the pixels by color only.
+            let colors = Self::colors(pixels);
+            let epsilon = options.params(3, pixels.len()).epsilon();
+            let params = Params::new(1, epsilon, DistanceMetric::Euclidean);
+            // Pixels of the same color are identical points without their positions, so flat
+            // regions collapse into a few weighted points.
+            return DBSCAN::fit_weighted_deduplicated(&colors, weights, &params);
+        }
+
+        let smoothed =
+            (options.smoothing() > F::zero()).then(|| Self::smooth(pixels, options.smoothing()));
+        let points = smoothed.as_deref().unwrap_or(pixels);
+        if options.equalize() {
+            DBSCAN::fit_weighted(&Self::equalize(points), weights, &params)
+        } else {
+            DBSCAN::fit_weighted(points, weights, &params)
+        }
+    }
+
+    /// Return the given points of the pixels with their positions zeroed.
+    fn colors<F: Float>(pixels: &[Point5<F>]) -> Vec<Point5<F>> {
+        pixels
+            .iter()
+            .map(|pixel| Point5::new(pixel[0], pixel[1], pixel[2], F::zero(), F::zero()))
+            .collect()
+    }
+
+    /// Replace the color of each pixel with the mean color of the nearest pixels within the given
+    /// radius.
+    ///
+    /// The neighborhood is measured in the normalized color and position space, so only pixels
+    /// that are both spatially and perceptually close are averaged and edges between distinct
+    /// colors are preserved like a bilateral filter. The neighborhood is capped at
+    /// [`SMOOTHING_NEIGHBORS`](Self::SMOOTHING_NEIGHBORS) pixels, so a flat region covered by a
+    /// large radius costs no more than a detailed one.
+    fn smooth<F: Float>(pixels: &[Point5<F>], radius: F) -> Vec<Point5<F>> {
+        let nns = KDTree::new(pixels, &DistanceMetric::Euclidean);
+        pixels
+            .iter()
+            .map(|pixel| {
+                let neighbors =
+                    nns.search_knn_within_radius(pixel, Self::SMOOTHING_NEIGHBORS, radius);
+                let total = neighbors.iter().fold(Point5::zero(), |total, neighbor| {
+                    total + pixels[neighbor.index]
+                });
+                // The neighborhood always contains the pixel itself.
+                let mean = total / F::from_usize(neighbors.len());
+                Point5::new(mean[0], mean[1], mean[2], pixel[3], pixel[4])
+            })
+            .collect()
+    }
+
+    /// Equalize the histogram of the normalized lightness of the given pixels.
+    ///
+    /// [Histogram equalization - Wikipedia](https://en.wikipedia.org/wiki/Histogram_equalization)
+    fn equalize<F: Float>(pixels: &[Point5<F>]) -> Vec<Point5<F>> {
+        const BINS: usize = 256;
+        let max_bin = F::from_usize(BINS - 1);
+        let bin = |l: F| -> usize {
+            (l.clamp(F::zero(), F::one()) * max_bin)
+                .round()
+                .to_usize()
+                .expect("Clamped lightness should be converted to usize")
+        };
+
+        let mut cdf = [0_usize; BINS];
+        for pixel in pixels {
+            cdf[bin(pixel[0])] += 1;
+        }
+        for index in 1..BINS {
+            cdf[index] += cdf[index - 1];
+        }
+
+        let cdf_min = cdf.iter().copied().find(|&count| count > 0).unwrap_or(0);
+        if pixels.len() == cdf_min {
+            // All pixels have the same lightness.
+            return pixels.to_vec();
+        }
+
+        let range = pixels.len() - cdf_min;
+        pixels
+            .iter()
+            .map(|pixel| {
+                let l = Self::ratio(cdf[bin(pixel[0])] - cdf_min, range);
+                Point5::new(l, pixel[1], pixel[2], pixel[3], pixel[4])
+            })
+            .collect()
+    }
+
+    /// Return the members of each cluster ordered by cluster ID.
+    fn members<F: Float>(dbscan: &DBSCAN<F, Point5<F>>) -> Vec<&[usize]> {
+        (0..dbscan.centroids().len())
+            .map(|cluster_id| dbscan.members_at(cluster_id))
+            .collect()
+    }
+
+    /// Compute the weighted centroid of each cluster from the given pixels and members.
+    fn centroids<F: Float>(
+        pixels: &[Point5<F>],
+        weights: &[F],
+        members: &[&[usize]],
+    ) -> Vec<Point5<F>> {
+        members
+            .iter()
+            .map(|members| {
+                let (total, weight) =
+                    members
+                        .iter()
+                        .fold((Point5::zero(), F::zero()), |(total, weight), &index| {
+                            (
+                                total + pixels[index] * weights[index],
+                                weight + weights[index],
+                            )
+                        });
+                total / weight
+            })
+            .collect()
+    }
+
+    /// Convert each cluster into a swatch in the order of the given members.
+    ///
+    /// The color, position, spread and percentage of each swatch are weighted by the weights of
+    /// the pixels, while the population counts the member pixels.
+    fn swatches<F: Float, S: WorkingSpace<F>>(
+        &self,
+        pixels: &[Point5<F>],
+        weights: &[F],
+        members: &[&[usize]],
+    ) -> Vec<Swatch<F>> {
+        self.swatches_with::<F, S>(pixels, weights, members, None)
+    }
+
+    /// Convert each cluster into a swatch as [`swatches`](Self::swatches) does.
+    ///
+    /// If the number of pixels each pixel stands for is given, the populations sum the counts of
+    /// the member pixels instead.
+    fn swatches_with<F: Float, S: WorkingSpace<F>>(
+        &self,
+        pixels: &[Point5<F>],
+        weights: &[F],
+        members: &[&[usize]],
+        counts: Option<&[usize]>,
+    ) -> Vec<Swatch<F>> {
+        let width_f: F = self.width();
+        let height_f: F = self.height();
+        let total_weight = weights
+            .iter()
+            .fold(F::zero(), |total, &weight| total + weight);
+        Self::centroids(pixels, weights, members)
             .into_iter()
-            .enumerate()
-            .map(|(cluster_id, centroid)| {
-                let lab = Lab::new(
-                    centroid[0] * delta_l,
-                    centroid[1] * delta_a,
-                    centroid[2] * delta_b,
-                );
-                let xyz = XYZ::from(&lab);
-                let rgb = Rgba::from(&xyz);
-                let color = (rgb.r, rgb.g, rgb.b);
+            .zip(members)
+            .map(|(centroid, members)| {
+                let color =
+                    to_rgba(&S::from_components([centroid[0], centroid[1], centroid[2]]).to_lab());
 
                 let x = (centroid[3] * width_f)
                     .to_u32()
@@ -87,18 +736,33 @@ impl<'a> ImageData<'a> {
                     .expect("Height should be converted to u32");
                 let position = (x, y);
 
-                let count = dbscan.count_at(cluster_id);
-                let percentage = F::from_usize(count) / F::from_usize(pixels.len());
-                Swatch {
-                    color,
-                    position,
-                    percentage,
-                }
+                let weight = members
+                    .iter()
+                    .fold(F::zero(), |total, &index| total + weights[index]);
+                let spread = members.iter().fold(F::zero(), |total, &index| {
+                    (0..3).fold(total, |total, axis| {
+                        total + (pixels[index][axis] - centroid[axis]).powi(2) * weights[index]
+                    })
+                }) / weight;
+
+                let population = counts.map_or(members.len(), |counts| {
+                    members.iter().map(|&index| counts[index]).sum()
+                });
+                let percentage = weight / total_weight;
+                Swatch::new(color, position, population, percentage).with_spread(spread)
             })
-            .collect();
+            .collect()
+    }
 
-        swatches.sort();
-        swatches
+    /// Divide the given counts, such as a coordinate by the size of the image.
+    ///
+    /// A count that F cannot represent exactly would be rounded before the division, so the
+    /// division is done in f64 in that case.
+    fn ratio<F: Float>(numerator: usize, denominator: usize) -> F {
+        match (F::try_from_usize(numerator), F::try_from_usize(denominator)) {
+            (Some(numerator), Some(denominator)) => numerator / denominator,
+            _ => F::from_f64(numerator as f64 / denominator as f64),
+        }
     }
 
     fn width<N: Number>(&self) -> N {
@@ -109,3 +773,689 @@ impl<'a> ImageData<'a> {
         N::from_u32(self.height)
     }
 }
+
+/// Extractor reusing its buffers across repeated extractions, such as the frames of a video.
+///
+/// The points and the weights of the pixels are written into buffers kept between calls, so
+/// extracting from images of the same size does not reallocate them. The neighbor search index
+/// depends on the pixels and is still built for each image.
+#[derive(Debug, Clone)]
+pub struct Extractor<F>
+where
+    F: Float,
+{
+    options: ExtractOptions<F>,
+    pixels: Vec<Point5<F>>,
+    weights: Vec<F>,
+}
+
+impl<F> Extractor<F>
+where
+    F: Float,
+{
+    /// Create a new Extractor with the given options.
+    #[must_use]
+    pub fn new(options: ExtractOptions<F>) -> Self {
+        Self {
+            options,
+            pixels: Vec::new(),
+            weights: Vec::new(),
+        }
+    }
+
+    /// Return the options of this extractor.
+    #[must_use]
+    pub fn options(&self) -> &ExtractOptions<F> {
+        &self.options
+    }
+
+    /// Extract a palette from the given image, reusing the buffers of this extractor.
+    ///
+    /// The palette is the same as the one extracted by [`ImageData::extract_with`].
+    #[must_use]
+    pub fn extract_into(&mut self, image_data: &ImageData) -> Palette<F> {
+        image_data.extract_with_buffers::<F, Lab<F>>(
+            &self.options,
+            &mut self.pixels,
+            &mut self.weights,
+        )
+    }
+}
+
+impl<F> Default for Extractor<F>
+where
+    F: Float,
+{
+    fn default() -> Self {
+        Self::new(ExtractOptions::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::oklab::Oklab;
+
+    fn solid_image(width: u32, height: u32, colors: &[(u8, u8, u8)]) -> Vec<u8> {
+        let band = width as usize / colors.len();
+        let mut data = Vec::with_capacity((width * height * 4) as usize);
+        for _ in 0..height {
+            for x in 0..width as usize {
+                let (r, g, b) = colors[(x / band).min(colors.len() - 1)];
+                data.extend_from_slice(&[r, g, b, 255]);
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn data_should_return_underlying_bytes() {
+        let data = [255, 0, 0, 255, 0, 0, 255, 128];
+        let image_data = ImageData::new(&data, 2, 1);
+        assert_eq!(image_data.data(), &data);
+    }
+
+    #[test]
+    fn pixel_should_return_color_at_coordinates() {
+        let data = [255, 0, 0, 255, 0, 255, 0, 255, 0, 0, 255, 128, 9, 9, 9, 0];
+        let image_data = ImageData::new(&data, 2, 2);
+        assert_eq!(image_data.pixel(0, 0), Rgba::new(255, 0, 0, 255));
+        assert_eq!(image_data.pixel(1, 0), Rgba::new(0, 255, 0, 255));
+        assert_eq!(image_data.pixel(0, 1), Rgba::new(0, 0, 255, 128));
+        assert_eq!(image_data.pixel(1, 1), Rgba::new(9, 9, 9, 0));
+    }
+
+    #[test]
+    fn pixels_should_iterate_pixels_in_row_major_order() {
+        let data: Vec<u8> = (0..24).collect();
+        let image_data = ImageData::new(&data, 3, 2);
+        let pixels: Vec<(u32, u32, Rgba)> = image_data.pixels().collect();
+        assert_eq!(
+            pixels,
+            vec![
+                (0, 0, Rgba::new(0, 1, 2, 3)),
+                (1, 0, Rgba::new(4, 5, 6, 7)),
+                (2, 0, Rgba::new(8, 9, 10, 11)),
+                (0, 1, Rgba::new(12, 13, 14, 15)),
+                (1, 1, Rgba::new(16, 17, 18, 19)),
+                (2, 1, Rgba::new(20, 21, 22, 23)),
+            ]
+        );
+    }
+
+    #[test]
+    fn pixels_should_ignore_trailing_bytes() {
+        let data = [1, 2, 3, 4, 5, 6];
+        let image_data = ImageData::new(&data, 1, 1);
+        assert_eq!(image_data.pixels().count(), 1);
+
+        let image_data = ImageData::new(&data, 0, 0);
+        assert_eq!(image_data.pixels().count(), 0);
+    }
+
+    #[test]
+    fn region_should_view_sub_rectangle() {
+        let data: Vec<u8> = (0..48).collect();
+        let image_data = ImageData::new(&data, 4, 3);
+        let region = image_data.region(1, 1, 2, 2);
+        assert_eq!(region.pixel(0, 0), Rgba::new(20, 21, 22, 23));
+        assert_eq!(region.pixel(1, 1), Rgba::new(40, 41, 42, 43));
+        let pixels: Vec<(u32, u32, Rgba)> = region.pixels().collect();
+        assert_eq!(
+            pixels,
+            vec![
+                (0, 0, Rgba::new(20, 21, 22, 23)),
+                (1, 0, Rgba::new(24, 25, 26, 27)),
+                (0, 1, Rgba::new(36, 37, 38, 39)),
+                (1, 1, Rgba::new(40, 41, 42, 43)),
+            ]
+        );
+    }
+
+    #[test]
+    fn extract_region_should_extract_only_region() {
+        let mut data = solid_image(128, 64, &[(255, 0, 0), (0, 0, 255)]);
+        // Paint a green square from (8, 8) to (55, 55) inside the red band.
+        for y in 8..56 {
+            for x in 8..56 {
+                let index = (y * 128 + x) * 4;
+                data[index..index + 4].copy_from_slice(&[0, 255, 0, 255]);
+            }
+        }
+        let image_data = ImageData::new(&data, 128, 64);
+
+        let options = ExtractOptions::new().with_min_points(4);
+        let palette: Palette<f64> = image_data.extract_region(8, 8, 48, 48, &options);
+        assert_eq!(palette.len(), 1);
+        let swatch = &palette.swatches()[0];
+        assert_eq!(swatch.color(), Rgba::new(0, 255, 0, 255));
+        assert_eq!(swatch.population(), 48 * 48);
+        let (x, y) = swatch.position();
+        assert!((8..56).contains(&x) && (8..56).contains(&y));
+        assert!(x > 24 && y > 24);
+    }
+
+    #[test]
+    #[should_panic]
+    fn extract_region_should_panic_if_out_of_bounds() {
+        let data = [0; 16];
+        let image_data = ImageData::new(&data, 2, 2);
+        let _: Palette<f64> = image_data.extract_region(1, 1, 2, 1, &ExtractOptions::new());
+    }
+
+    #[test]
+    #[should_panic]
+    fn pixel_should_panic_if_out_of_bounds() {
+        let data = [0; 16];
+        let image_data = ImageData::new(&data, 2, 2);
+        let _ = image_data.pixel(2, 0);
+    }
+
+    #[test]
+    fn extract_should_collapse_noisy_block_with_smoothing() {
+        // A single color block with deterministic pseudo-random noise on each channel.
+        let mut state: u32 = 12345;
+        let mut data = Vec::with_capacity(48 * 48 * 4);
+        for _ in 0..48 * 48 {
+            for base in [120_u8, 90, 60] {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                data.push(base - 8 + ((state >> 16) % 17) as u8);
+            }
+            data.push(255);
+        }
+        let image_data = ImageData::new(&data, 48, 48);
+
+        let options = ExtractOptions::new().with_epsilon(0.04).with_min_points(5);
+        let palette: Palette<f64> = image_data.extract_with(&options);
+        assert!(palette.len() > 1);
+
+        let palette: Palette<f64> = image_data.extract_with(&options.with_smoothing(0.08));
+        assert_eq!(palette.len(), 1);
+        assert_eq!(palette.swatches()[0].population(), 48 * 48);
+    }
+
+    #[test]
+    fn extract_from_float_should_match_extract_with() {
+        let data = solid_image(4, 4, &[(255, 0, 0), (0, 0, 255)]);
+        let floats: Vec<f64> = data.iter().map(|&value| f64::from(value) / 255.0).collect();
+        let options = ExtractOptions::new();
+        let palette = ImageData::extract_from_float(&floats, 4, 4, &options).unwrap();
+        assert_eq!(palette.len(), 2);
+        assert_eq!(palette, ImageData::new(&data, 4, 4).extract_with(&options));
+    }
+
+    #[test]
+    fn extract_from_float_should_reject_non_finite_values() {
+        let mut floats = vec![0.5_f64; 4 * 4 * 4];
+        floats[5] = f64::NAN;
+        let result = ImageData::extract_from_float(&floats, 4, 4, &ExtractOptions::new());
+        assert_eq!(result, Err(ExtractError::NonFinite { index: 5 }));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Non-finite channel value at index 5"
+        );
+
+        floats[5] = 0.5;
+        floats[18] = f64::INFINITY;
+        let result = ImageData::extract_from_float(&floats, 4, 4, &ExtractOptions::new());
+        assert_eq!(result, Err(ExtractError::NonFinite { index: 18 }));
+    }
+
+    #[test]
+    fn extract_into_should_reuse_buffers() {
+        let data = solid_image(64, 64, &[(255, 0, 0), (0, 0, 255)]);
+        let image_data = ImageData::new(&data, 64, 64);
+        let mut extractor = Extractor::new(ExtractOptions::new().with_min_points(5));
+
+        let palette1: Palette<f64> = extractor.extract_into(&image_data);
+        let (pixels, weights) = (extractor.pixels.as_ptr(), extractor.weights.as_ptr());
+        let capacity = extractor.pixels.capacity();
+        let palette2: Palette<f64> = extractor.extract_into(&image_data);
+        assert_eq!(palette1.len(), 2);
+        assert_eq!(palette1, palette2);
+        assert_eq!(palette1, image_data.extract_with(extractor.options()));
+        assert_eq!(extractor.pixels.as_ptr(), pixels);
+        assert_eq!(extractor.weights.as_ptr(), weights);
+        assert_eq!(extractor.pixels.capacity(), capacity);
+    }
+
+    #[test]
+    fn ratio_should_not_round_counts_before_division() {
+        let numerator = (1 << 24) + 1;
+        let denominator = (1 << 24) + 2;
+        let actual: f32 = ImageData::ratio(numerator, denominator);
+        assert_eq!(actual, (numerator as f64 / denominator as f64) as f32);
+        assert_ne!(actual, numerator as f32 / denominator as f32);
+        assert_eq!(ImageData::ratio::<f32>(3, 4), 0.75);
+    }
+
+    #[test]
+    fn extract_should_report_outliers() {
+        // A red block with every 9th pixel replaced by a random color too rare to form a swatch.
+        let mut data = solid_image(64, 64, &[(255, 0, 0)]);
+        let mut state: u32 = 42;
+        for pixel in data.chunks_exact_mut(4).step_by(9) {
+            for channel in pixel.iter_mut().take(3) {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                *channel = (state >> 16) as u8;
+            }
+        }
+        let image_data = ImageData::new(&data, 64, 64);
+
+        let options = ExtractOptions::new().with_min_points(5);
+        let palette: Palette<f64> = image_data.extract_with(&options);
+        assert_eq!(palette.len(), 1);
+        assert!((palette.outlier_percentage() - 456.0 / 4096.0).abs() < 1e-12);
+        assert!(palette.outlier_color().is_some());
+        let total = palette.swatches()[0].percentage() + palette.outlier_percentage();
+        assert!((total - 1.0).abs() < 1e-12);
+
+        // The neighborhoods are too small for the default minimum number of points.
+        let palette: Palette<f64> = image_data.extract();
+        assert!(palette.is_empty());
+        assert_eq!(palette.outlier_percentage(), 1.0);
+
+        let data = solid_image(64, 64, &[(255, 0, 0)]);
+        let palette: Palette<f64> = ImageData::new(&data, 64, 64).extract_with(&options);
+        assert_eq!(palette.outlier_percentage(), 0.0);
+        assert_eq!(palette.outlier_color(), None);
+    }
+
+    #[test]
+    fn extract_should_ignore_mostly_transparent_pixel_with_alpha_weighting() {
+        let mut data = solid_image(4, 4, &[(255, 0, 0)]);
+        data[20..24].copy_from_slice(&[0, 0, 255, 20]);
+        let image_data = ImageData::new(&data, 4, 4);
+
+        let palette: Palette<f64> = image_data.extract();
+        assert_eq!(palette.len(), 2);
+
+        let options = ExtractOptions::new().with_alpha_weighted(true);
+        let palette: Palette<f64> = image_data.extract_with(&options);
+        assert_eq!(palette.len(), 1);
+        assert_eq!(palette.swatches()[0].color(), Rgba::new(255, 0, 0, 255));
+        assert_eq!(palette.swatches()[0].population(), 15);
+        assert!((palette.swatches()[0].percentage() - 15.0 / (15.0 + 20.0 / 255.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn downscale_should_average_blocks() {
+        let data = [
+            255, 0, 0, 255, 255, 0, 0, 255, 0, 0, 255, 255, //
+            255, 0, 0, 255, 255, 0, 0, 255, 0, 0, 255, 255, //
+            0, 0, 0, 255, 255, 255, 255, 255, 0, 255, 0, 255, //
+        ];
+        let image_data = ImageData::new(&data, 3, 3);
+        let (data, width, height, counts) = image_data.downscale(2);
+        assert_eq!((width, height), (2, 2));
+        assert_eq!(
+            data,
+            vec![
+                255, 0, 0, 255, 0, 0, 255, 255, //
+                188, 188, 188, 255, 0, 255, 0, 255, //
+            ]
+        );
+        assert_eq!(counts, vec![4, 2, 2, 1]);
+    }
+
+    #[test]
+    fn extract_should_report_original_positions_with_downscale() {
+        let data = solid_image(64, 64, &[(255, 0, 0), (0, 0, 255)]);
+        let image_data = ImageData::new(&data, 64, 64);
+        // The epsilon covers the spacing between the pixels of the 32x32 thumbnail.
+        let options = ExtractOptions::new().with_min_points(4).with_epsilon(0.04);
+        let expected: Palette<f64> = image_data.extract_with(&options);
+
+        let palette: Palette<f64> = image_data.extract_with(&options.with_downscale(2));
+        assert_eq!(palette.len(), expected.len());
+        for (actual, expected) in palette.iter().zip(expected.iter()) {
+            assert_eq!(actual.color(), expected.color());
+            assert_eq!(actual.population(), expected.population());
+            assert!((actual.percentage() - expected.percentage()).abs() < 1e-12);
+
+            let (x, y) = actual.position();
+            let (expected_x, expected_y) = expected.position();
+            assert!(x < 64 && y < 64);
+            assert!(x.abs_diff(expected_x) <= 1 && y.abs_diff(expected_y) <= 1);
+        }
+    }
+
+    #[test]
+    fn extract_should_count_pixels_of_smaller_edge_blocks_with_downscale() {
+        // The 4x4 block of red and the 2x4 block of blue on the right edge.
+        let data = solid_image(6, 4, &[(255, 0, 0), (255, 0, 0), (0, 0, 255)]);
+        let image_data = ImageData::new(&data, 6, 4);
+        let palette: Palette<f64> =
+            image_data.extract_with(&ExtractOptions::new().with_downscale(4));
+        let swatches: Vec<(Rgba, usize)> = palette
+            .iter()
+            .map(|swatch| (swatch.color(), swatch.population()))
+            .collect();
+        assert_eq!(
+            swatches,
+            vec![
+                (Rgba::new(0, 0, 255, 255), 8),
+                (Rgba::new(255, 0, 0, 255), 16)
+            ]
+        );
+        assert!((palette.swatches()[1].percentage() - 2.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn extract_should_populate_swatch_population() {
+        let data = solid_image(128, 128, &[(255, 0, 0), (0, 0, 255)]);
+        let image_data = ImageData::new(&data, 128, 128);
+        let palette: Palette<f64> = image_data.extract();
+        assert!(!palette.is_empty());
+
+        let total: usize = palette.swatches().iter().map(|s| s.population()).sum();
+        assert_eq!(total, 128 * 128);
+        for swatch in palette.swatches().iter() {
+            assert_eq!(
+                swatch.percentage(),
+                swatch.population() as f64 / (128.0 * 128.0)
+            );
+        }
+    }
+
+    #[test]
+    fn extract_and_quantize_should_use_only_palette_colors() {
+        let data = solid_image(128, 128, &[(255, 0, 0), (0, 0, 255)]);
+        let image_data = ImageData::new(&data, 128, 128);
+        let options = ExtractOptions::default();
+        let (palette, buffer): (Palette<f64>, Vec<u8>) = image_data.extract_and_quantize(&options);
+        assert_eq!(palette, image_data.extract_with(&options));
+        assert_eq!(buffer.len(), data.len());
+
+        let colors: Vec<Rgba> = palette.iter().map(|s| s.color()).collect();
+        for chunk in buffer.chunks_exact(4) {
+            assert!(colors.contains(&Rgba::new(chunk[0], chunk[1], chunk[2], 255)));
+            assert_eq!(chunk[3], 255);
+        }
+    }
+
+    #[test]
+    fn extract_with_should_separate_low_contrast_colors_if_equalized() {
+        let colors = [
+            (120, 120, 120),
+            (124, 124, 124),
+            (128, 128, 128),
+            (132, 132, 132),
+        ];
+        let data = solid_image(128, 128, &colors);
+        let image_data = ImageData::new(&data, 128, 128);
+
+        let palette: Palette<f64> = image_data.extract_with(&ExtractOptions::default());
+        let equalized: Palette<f64> =
+            image_data.extract_with(&ExtractOptions::default().with_equalize(true));
+        assert!(equalized.len() > palette.len());
+        for swatch in equalized.swatches().iter() {
+            let Rgba { r, g, b, .. } = swatch.color();
+            assert!(r == g && g == b);
+            assert!((119..=133).contains(&r));
+        }
+    }
+
+    #[test]
+    fn extract_should_return_single_swatch_for_single_pixel() {
+        let data = [255, 0, 0, 255];
+        let image_data = ImageData::new(&data, 1, 1);
+        let palette: Palette<f64> = image_data.extract();
+        assert_eq!(
+            palette.swatches(),
+            &[Swatch::new(Rgba::new(255, 0, 0, 255), (0, 0), 1, 1.0)]
+        );
+    }
+
+    #[test]
+    fn cluster_points_should_match_full_clustering_of_flat_regions() {
+        let mut data = solid_image(32, 32, &[(255, 0, 0), (0, 0, 255), (255, 255, 255)]);
+        for (index, pixel) in data.chunks_exact_mut(4).enumerate().step_by(37) {
+            pixel[..3].copy_from_slice(&[(index % 256) as u8, 128, 64]);
+        }
+        let image_data = ImageData::new(&data, 32, 32);
+        let options = ExtractOptions::<f64>::new().with_min_points(2048);
+        let pixels = image_data.points::<f64, Lab<f64>>();
+        let weights = image_data.weights(false);
+
+        let actual = ImageData::cluster_points(&options, &pixels, &weights);
+        let epsilon = options.params(3, pixels.len()).epsilon();
+        let params = Params::new(1, epsilon, DistanceMetric::Euclidean);
+        let expected = DBSCAN::fit_weighted(&ImageData::colors(&pixels), &weights, &params);
+        assert!(actual.stats().expansion_steps < expected.stats().expansion_steps);
+        assert_eq!(ImageData::members(&actual), ImageData::members(&expected));
+        assert_eq!(actual.outliers(), expected.outliers());
+        assert_eq!(
+            image_data.swatches::<f64, Lab<f64>>(&pixels, &weights, &ImageData::members(&actual)),
+            image_data.swatches::<f64, Lab<f64>>(&pixels, &weights, &ImageData::members(&expected))
+        );
+    }
+
+    #[test]
+    fn extract_should_group_colors_of_small_image() {
+        let data = solid_image(2, 2, &[(255, 0, 0), (0, 0, 255)]);
+        let image_data = ImageData::new(&data, 2, 2);
+        let palette: Palette<f64> = image_data.extract();
+        let swatches: Vec<(Rgba, usize, f64)> = palette
+            .iter()
+            .map(|swatch| (swatch.color(), swatch.population(), swatch.percentage()))
+            .collect();
+        assert_eq!(
+            swatches,
+            vec![
+                // Swatches of the same size are ordered by their normalized Lab colors.
+                (Rgba::new(0, 0, 255, 255), 2, 0.5),
+                (Rgba::new(255, 0, 0, 255), 2, 0.5)
+            ]
+        );
+    }
+
+    #[test]
+    fn extract_should_return_empty_palette_for_empty_image() {
+        let image_data = ImageData::new(&[], 0, 0);
+        let palette: Palette<f64> = image_data.extract();
+        assert!(palette.is_empty());
+    }
+
+    #[test]
+    fn extract_kmeans_should_be_reproducible_with_seed() {
+        let colors: Vec<(u8, u8, u8)> = (0..8).map(|i| (i * 32, 255 - i * 24, i * 16)).collect();
+        let data = solid_image(64, 64, &colors);
+        let image_data = ImageData::new(&data, 64, 64);
+        let options = ExtractOptions::default().with_seed(7);
+
+        let palette1: Palette<f64> = image_data.extract_kmeans(3, &options);
+        let palette2: Palette<f64> = image_data.extract_kmeans(3, &options);
+        assert_eq!(palette1.len(), 3);
+        assert_eq!(palette1.swatches(), palette2.swatches());
+
+        // The seed picks the initial centroids, so other seeds can end in other clusters.
+        assert!((0..16).any(|seed| {
+            let palette: Palette<f64> =
+                image_data.extract_kmeans(3, &ExtractOptions::default().with_seed(seed));
+            palette.swatches() != palette1.swatches()
+        }));
+    }
+
+    #[test]
+    fn extract_in_should_cluster_in_given_working_space() {
+        let data = solid_image(128, 128, &[(255, 0, 0), (0, 0, 255)]);
+        let image_data = ImageData::new(&data, 128, 128);
+        let options = ExtractOptions::default();
+
+        let palette: Palette<f64> = image_data.extract_in::<_, Lab<f64>>(&options);
+        assert_eq!(palette.len(), 2);
+        assert_eq!(palette, image_data.extract_with(&options));
+
+        let palette: Palette<f64> = image_data.extract_in::<_, Oklab<f64>>(&options);
+        let mut colors: Vec<Rgba> = palette.iter().map(Swatch::color).collect();
+        colors.sort_by_key(|color| (color.r, color.g, color.b));
+        assert_eq!(
+            colors,
+            vec![Rgba::new(0, 0, 255, 255), Rgba::new(255, 0, 0, 255)]
+        );
+    }
+
+    #[test]
+    fn extract_with_should_keep_small_saturated_accent_with_vibrance() {
+        let mut data = solid_image(128, 128, &[(128, 128, 128)]);
+        // A red accent of 4 x 5 pixels, too few to form a swatch by default.
+        for y in 60..65 {
+            for x in 60..64 {
+                let offset = (y * 128 + x) * 4;
+                data[offset..offset + 4].copy_from_slice(&[255, 0, 0, 255]);
+            }
+        }
+        let image_data = ImageData::new(&data, 128, 128);
+        let red = Rgba::new(255, 0, 0, 255);
+
+        let palette: Palette<f64> = image_data.extract();
+        assert!(palette.iter().all(|swatch| swatch.color() != red));
+
+        let palette: Palette<f64> = image_data.extract_with(&ExtractOptions::vivid());
+        let accent = palette.iter().find(|swatch| swatch.color() == red).unwrap();
+        // The vibrance affects only the clustering, not the percentage.
+        assert_eq!(accent.population(), 20);
+        assert!((accent.percentage() - 20.0 / 16_384.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn extract_in_should_weight_and_equalize_components_of_working_space() {
+        let mut data = solid_image(128, 128, &[(128, 128, 128)]);
+        for y in 60..65 {
+            for x in 60..64 {
+                let offset = (y * 128 + x) * 4;
+                data[offset..offset + 4].copy_from_slice(&[255, 0, 0, 255]);
+            }
+        }
+        let image_data = ImageData::new(&data, 128, 128);
+        let red = Rgba::new(255, 0, 0, 255);
+        let palette: Palette<f64> =
+            image_data.extract_in::<_, Oklab<f64>>(&ExtractOptions::vivid());
+        assert!(palette.iter().any(|swatch| swatch.color() == red));
+
+        let data = solid_image(
+            128,
+            128,
+            &[(120, 120, 120), (124, 124, 124), (128, 128, 128)],
+        );
+        let image_data = ImageData::new(&data, 128, 128);
+        let options = ExtractOptions::default();
+        let palette: Palette<f64> = image_data.extract_in::<_, Oklab<f64>>(&options);
+        let equalized: Palette<f64> =
+            image_data.extract_in::<_, Oklab<f64>>(&options.with_equalize(true));
+        assert!(equalized.len() > palette.len());
+    }
+
+    #[test]
+    fn extract_with_should_compute_spread_of_swatches() {
+        let mut data = Vec::with_capacity(32 * 32 * 4);
+        for _ in 0..32 {
+            for x in 0..32_u8 {
+                if x < 16 {
+                    data.extend_from_slice(&[255, 0, 0, 255]);
+                } else {
+                    let gray = (x - 16) * 4;
+                    data.extend_from_slice(&[gray, gray, gray, 255]);
+                }
+            }
+        }
+        let image_data = ImageData::new(&data, 32, 32);
+        let options = ExtractOptions::new().with_min_points(4).with_epsilon(0.1);
+
+        let palette: Palette<f64> = image_data.extract_with(&options);
+        assert_eq!(palette.len(), 2);
+        let red = palette
+            .iter()
+            .find(|swatch| swatch.color() == Rgba::new(255, 0, 0, 255))
+            .unwrap();
+        assert!(red.spread() < 1e-12);
+        let gray = palette
+            .iter()
+            .find(|swatch| swatch.color() != Rgba::new(255, 0, 0, 255))
+            .unwrap();
+        assert!(gray.spread() > 1e-3);
+    }
+
+    #[test]
+    fn extract_kmeans_should_return_exactly_k_swatches() {
+        let data = solid_image(
+            64,
+            16,
+            &[
+                (255, 0, 0),
+                (0, 255, 0),
+                (0, 0, 255),
+                (255, 255, 0),
+                (0, 0, 0),
+                (255, 255, 255),
+                (128, 0, 128),
+                (0, 128, 128),
+            ],
+        );
+        let image_data = ImageData::new(&data, 64, 16);
+        let options = ExtractOptions::new().with_seed(1);
+
+        let palette: Palette<f64> = image_data.extract_kmeans(5, &options);
+        assert_eq!(palette.len(), 5);
+        let population: usize = palette.iter().map(|swatch| swatch.population()).sum();
+        assert_eq!(population, 64 * 16);
+        assert_eq!(palette, image_data.extract_kmeans(5, &options));
+
+        let palette: Palette<f64> = image_data.extract_kmeans(8, &options);
+        assert_eq!(palette.len(), 8);
+        assert!(palette.iter().all(|swatch| swatch.spread() < 1e-12));
+    }
+
+    #[test]
+    fn extract_kmeans_should_return_fewer_swatches_for_small_image() {
+        let data = [255, 0, 0, 255, 0, 0, 255, 255];
+        let image_data = ImageData::new(&data, 2, 1);
+        let palette: Palette<f64> = image_data.extract_kmeans(5, &ExtractOptions::new());
+        assert_eq!(palette.len(), 2);
+
+        let palette: Palette<f64> = image_data.extract_kmeans(0, &ExtractOptions::new());
+        assert!(palette.is_empty());
+    }
+
+    #[test]
+    fn soft_memberships_should_split_equidistant_pixel() {
+        let centroids: [Point5<f64>; 2] = [
+            Point5::new(0.2, 0.0, 0.0, 0.0, 0.0),
+            Point5::new(0.6, 0.0, 0.0, 1.0, 1.0),
+        ];
+        let pixel = Point5::new(0.4, 0.0, 0.0, 0.5, 0.5);
+        let memberships = ImageData::soft_memberships(&pixel, &centroids, 0.01);
+        assert!((memberships[0] - 0.5).abs() < 1e-12);
+        assert!((memberships[1] - 0.5).abs() < 1e-12);
+
+        let pixel = Point5::new(0.25, 0.0, 0.0, 0.0, 0.0);
+        let memberships = ImageData::soft_memberships(&pixel, &centroids, 0.01);
+        assert!(memberships[0] > 0.99);
+    }
+
+    #[test]
+    fn extract_soft_should_split_boundary_pixels() {
+        let mut data = solid_image(16, 16, &[(0, 0, 0), (255, 255, 255)]);
+        // A middle gray column that is an outlier of both swatches.
+        for y in 0..16 {
+            let index = (y * 16 + 8) * 4;
+            data[index..index + 3].copy_from_slice(&[119, 119, 119]);
+        }
+        let image_data = ImageData::new(&data, 16, 16);
+        let options = ExtractOptions::new().with_min_points(5).with_epsilon(0.1);
+
+        let hard: Palette<f64> = image_data.extract_with(&options);
+        let soft: Palette<f64> = image_data.extract_soft(&options, 0.05);
+        assert_eq!(hard.len(), 2);
+        assert_eq!(soft.len(), 2);
+        // The gray pixels are outliers of the hard assignment.
+        let total: f64 = hard.iter().map(|swatch| swatch.percentage()).sum();
+        assert!((total - 0.9375).abs() < 1e-9);
+        let total: f64 = soft.iter().map(|swatch| swatch.percentage()).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        for swatch in soft.iter() {
+            assert!((swatch.percentage() - 0.5).abs() < 0.05);
+        }
+    }
+}
@@ -1,4 +1,31 @@
-mod color;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("Either the `std` or `libm` feature must be enabled for floating point math");
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod clustering;
+pub mod color;
+#[cfg(feature = "std")]
 pub mod image;
 mod math;
+#[cfg(feature = "std")]
+pub mod options;
+#[cfg(feature = "std")]
+pub mod palette;
+#[cfg(feature = "std")]
 pub mod swatch;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "std")]
+pub use math::clustering::kmeans::init::Initializer;
+pub use math::distance::cylindrical::CylindricalDistance;
+pub use math::distance::mahalanobis::MahalanobisDistance;
+pub use math::distance::measure::DistanceMeasure;
+pub use math::distance::metric::{DistanceMetric, ParseDistanceMetricError};
+pub use math::distance::projected::ProjectedDistance;
+pub use math::point::{Point, Point2, Point3, Point5};
+pub use math::preprocessing::{MinMaxScaler, StandardScaler};
@@ -1,14 +1,19 @@
 use crate::math::clustering::dbscan::label::Label;
 use crate::math::clustering::dbscan::params::Params;
+use crate::math::clustering::dbscan::stats::Stats;
 use crate::math::clustering::traits::Fit;
 use crate::math::neighbors::kdtree::KDTree;
-use crate::math::neighbors::nns::{Neighbor, NeighborSearch};
+use crate::math::neighbors::linear::LinearSearch;
+use crate::math::neighbors::nns::NeighborSearch;
+use crate::math::neighbors::LINEAR_SEARCH_THRESHOLD;
 use crate::math::number::Float;
 use crate::math::point::Point;
+use std::cmp::Ordering;
 use std::collections::{HashMap, VecDeque};
 use std::marker::PhantomData;
 
 /// DBSCAN clustering algorithm.
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone)]
 pub struct DBSCAN<F, P>
 where
@@ -19,6 +24,7 @@ where
     centroids: HashMap<usize, P>,
     membership: HashMap<usize, Vec<usize>>,
     outliers: Vec<usize>,
+    expansion_steps: usize,
 }
 
 impl<F, P> DBSCAN<F, P>
@@ -26,11 +32,11 @@ where
     F: Float,
     P: Point<F>,
 {
-    /// Return a set of centroid.
+    /// Return a set of centroid ordered by cluster ID.
     pub fn centroids(&self) -> Vec<P> {
-        self.centroids
-            .values()
-            .map(|centroid| centroid.clone())
+        (0..self.centroids.len())
+            .filter_map(|cluster_id| self.centroids.get(&cluster_id))
+            .copied()
             .collect()
     }
 
@@ -41,93 +47,221 @@ where
             .map_or(0, |children| children.len())
     }
 
+    /// Return a set of indices assigned to the given cluster ID.
+    pub fn members_at(&self, cluster_id: usize) -> &[usize] {
+        self.membership
+            .get(&cluster_id)
+            .map_or(&[], |children| children.as_slice())
+    }
+
     /// Return a set of indices of outliers.
     pub fn outliers(&self) -> Vec<usize> {
         self.outliers.clone()
     }
 
-    fn expand_cluster<N>(
-        cluster_id: usize,
-        dataset: &[P],
-        params: &Params<F>,
-        ns: &N,
-        neighbors: &[Neighbor<F>],
-        labels: &mut [Label],
-    ) where
-        N: NeighborSearch<F, P>,
-    {
-        let mut queue = VecDeque::new();
-        queue.extend(neighbors.iter().map(|n| n.index));
-        while let Some(current_index) = queue.pop_front() {
-            if labels[current_index].is_assigned() {
-                continue;
-            }
+    /// Return the statistics of this clustering.
+    #[must_use]
+    pub fn stats(&self) -> Stats {
+        Stats {
+            clusters: self.centroids.len(),
+            outliers: self.outliers.len(),
+            expansion_steps: self.expansion_steps,
+        }
+    }
 
-            if labels[current_index].is_outlier() {
-                labels[current_index] = Label::Assigned(cluster_id);
-                continue;
+    /// Create a clustering result, renumbering the clusters in descending order of their sizes.
+    ///
+    /// The largest cluster gets the ID 0, so the IDs do not depend on the order in which the
+    /// clusters were found. Clusters of the same size are ordered by their centroids.
+    #[must_use]
+    fn new(
+        centroids: HashMap<usize, P>,
+        membership: HashMap<usize, Vec<usize>>,
+        outliers: Vec<usize>,
+        expansion_steps: usize,
+    ) -> Self {
+        let mut cluster_ids: Vec<usize> = membership.keys().copied().collect();
+        cluster_ids.sort_by(|id1, id2| {
+            let (centroid1, centroid2) = (&centroids[id1], &centroids[id2]);
+            membership[id2]
+                .len()
+                .cmp(&membership[id1].len())
+                .then_with(|| {
+                    (0..centroid1.dim())
+                        .map(|axis| centroid1[axis].total_order(&centroid2[axis]))
+                        .find(|ordering| ordering.is_ne())
+                        .unwrap_or(Ordering::Equal)
+                })
+                .then_with(|| id1.cmp(id2))
+        });
+
+        let mut canonical_centroids = HashMap::with_capacity(cluster_ids.len());
+        let mut canonical_membership = HashMap::with_capacity(cluster_ids.len());
+        let (mut centroids, mut membership) = (centroids, membership);
+        for (canonical_id, cluster_id) in cluster_ids.into_iter().enumerate() {
+            if let Some(centroid) = centroids.remove(&cluster_id) {
+                canonical_centroids.insert(canonical_id, centroid);
+            }
+            if let Some(children) = membership.remove(&cluster_id) {
+                canonical_membership.insert(canonical_id, children);
             }
+        }
 
-            labels[current_index] = Label::Assigned(cluster_id);
+        DBSCAN {
+            _t: PhantomData,
+            centroids: canonical_centroids,
+            membership: canonical_membership,
+            outliers,
+            expansion_steps,
+        }
+    }
 
-            let point = dataset[current_index];
-            let secondary_neighbors = ns.search_radius(&point, params.epsilon());
-            if secondary_neighbors.len() < params.min_points() {
-                continue;
+    /// Fit the dataset using the given function to find the neighbors of each point.
+    ///
+    /// The function returns the indices of the points in the neighborhood of the point at the
+    /// given index, including the point itself as a radius search does. This allows the density
+    /// connectivity to come from any source such as a graph of adjacent pixels, in which case the
+    /// epsilon of the parameters is not used.
+    #[must_use]
+    pub fn fit_with_neighbors<N>(dataset: &[P], params: &Params<F>, neighbors_fn: N) -> Self
+    where
+        N: Fn(usize) -> Vec<usize>,
+    {
+        let min_points = params.min_points();
+        Self::fit_with_core(dataset, params, |index| {
+            let neighbors = neighbors_fn(index);
+            (neighbors.len() >= min_points).then_some(neighbors)
+        })
+    }
+
+    /// Fit the dataset where each point contributes the given weight to the density.
+    ///
+    /// A point is a core point if the total weight of its neighborhood is at least the minimum
+    /// number of points, so points with a weight of zero never form a cluster on their own.
+    ///
+    /// # Panics
+    /// Panics if the number of weights differs from the number of points.
+    #[must_use]
+    pub fn fit_weighted(dataset: &[P], weights: &[F], params: &Params<F>) -> Self {
+        assert_eq!(
+            dataset.len(),
+            weights.len(),
+            "Weights should be given for each point"
+        );
+        Self::fit_with_weights(dataset, Some(weights), params)
+    }
+
+    /// Fit the dataset as [`fit_weighted`](Self::fit_weighted) does, collapsing identical points
+    /// into a single representative carrying their total weight before clustering.
+    ///
+    /// Identical points share their neighborhood and density, so the clusters and outliers are the
+    /// same as clustering every point, while datasets with many duplicates are clustered faster.
+    ///
+    /// # Panics
+    /// Panics if the number of weights differs from the number of points.
+    #[must_use]
+    pub fn fit_weighted_deduplicated(dataset: &[P], weights: &[F], params: &Params<F>) -> Self {
+        assert_eq!(
+            dataset.len(),
+            weights.len(),
+            "Weights should be given for each point"
+        );
+        let mut indices: HashMap<Vec<u64>, usize> = HashMap::new();
+        let mut uniques: Vec<P> = Vec::new();
+        let mut unique_weights: Vec<F> = Vec::new();
+        let mut owners: Vec<usize> = Vec::with_capacity(dataset.len());
+        for (point, &weight) in dataset.iter().zip(weights) {
+            let key = point
+                .to_vec()
+                .into_iter()
+                .map(|value| value.to_f64().unwrap_or(f64::NAN).to_bits())
+                .collect();
+            let owner = *indices.entry(key).or_insert_with(|| {
+                uniques.push(*point);
+                unique_weights.push(F::zero());
+                uniques.len() - 1
+            });
+            unique_weights[owner] += weight;
+            owners.push(owner);
+        }
+        if uniques.len() == dataset.len() {
+            return Self::fit_weighted(dataset, weights, params);
+        }
+
+        let unique = Self::fit_weighted(&uniques, &unique_weights, params);
+        let mut labels = vec![None; uniques.len()];
+        for cluster_id in 0..unique.centroids.len() {
+            for &index in unique.members_at(cluster_id) {
+                labels[index] = Some(cluster_id);
             }
+        }
 
-            for secondary_neighbor in secondary_neighbors.into_iter() {
-                let secondary_index = secondary_neighbor.index;
-                match labels[secondary_index] {
-                    Label::Undefined => {
-                        labels[secondary_index] = Label::Marked;
-                        queue.push_back(secondary_index);
-                    }
-                    Label::Outlier => {
-                        queue.push_back(secondary_index);
-                    }
-                    _ => {}
+        let mut centroids: HashMap<usize, P> = HashMap::new();
+        let mut membership: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut outliers: Vec<usize> = Vec::new();
+        for (index, owner) in owners.into_iter().enumerate() {
+            match labels[owner] {
+                Some(cluster_id) => {
+                    centroids
+                        .entry(cluster_id)
+                        .or_insert(P::zero())
+                        .add_assign(dataset[index]);
+                    membership.entry(cluster_id).or_default().push(index);
                 }
+                None => outliers.push(index),
             }
         }
+        for (cluster_id, centroid) in centroids.iter_mut() {
+            centroid.div_assign(F::from_usize(membership[cluster_id].len()));
+        }
+
+        Self::new(centroids, membership, outliers, unique.expansion_steps)
     }
-}
 
-impl<F, P> Fit<F, P, Params<F>> for DBSCAN<F, P>
-where
-    F: Float,
-    P: Point<F>,
-{
-    #[must_use]
-    fn fit(dataset: &[P], params: &Params<F>) -> Self {
+    fn fit_with_weights(dataset: &[P], weights: Option<&[F]>, params: &Params<F>) -> Self {
         if dataset.is_empty() {
-            return DBSCAN {
-                _t: PhantomData::default(),
-                centroids: HashMap::new(),
-                membership: HashMap::new(),
-                outliers: Vec::new(),
-            };
+            return Self::new(HashMap::new(), HashMap::new(), Vec::new(), 0);
+        }
+
+        if dataset.len() < LINEAR_SEARCH_THRESHOLD {
+            let nns = LinearSearch::new(dataset, *params.metric());
+            Self::fit_with(dataset, params, &nns, weights)
+        } else {
+            let nns = KDTree::new(dataset, params.metric());
+            Self::fit_with(dataset, params, &nns, weights)
         }
+    }
 
-        let dataset_vec = dataset.to_vec();
-        let nns = KDTree::new(&dataset_vec, params.metric());
+    /// Fit the dataset using the given function returning the neighbors of a core point, or `None`
+    /// if the point at the given index is not a core point.
+    fn fit_with_core<C>(dataset: &[P], params: &Params<F>, core_fn: C) -> Self
+    where
+        C: Fn(usize) -> Option<Vec<usize>>,
+    {
         let mut labels = vec![Label::Undefined; dataset.len()];
         let mut cluster_id: usize = 0;
-        for (index, point) in dataset.iter().enumerate() {
+        let mut expansion_steps: usize = 0;
+        for index in 0..dataset.len() {
             if !labels[index].is_undefined() {
                 continue;
             }
 
-            let neighbors = nns.search_radius(point, params.epsilon());
-            if neighbors.len() < params.min_points() {
+            let Some(neighbors) = core_fn(index) else {
                 labels[index] = Label::Outlier;
                 continue;
-            }
+            };
 
-            neighbors.iter().for_each(|neighbor| {
-                labels[neighbor.index] = Label::Marked;
+            let neighbors = Self::sample_neighbors(neighbors, params.max_neighbors());
+            neighbors.iter().for_each(|&neighbor| {
+                labels[neighbor] = Label::Marked;
             });
-            Self::expand_cluster(cluster_id, dataset, params, &nns, &neighbors, &mut labels);
+            expansion_steps += Self::expand_cluster(
+                cluster_id,
+                params.max_neighbors(),
+                &core_fn,
+                &neighbors,
+                &mut labels,
+            );
             cluster_id += 1;
         }
 
@@ -145,7 +279,7 @@ where
 
                     let children = {
                         let entry = membership.entry(cluster_id);
-                        entry.or_insert(Vec::new())
+                        entry.or_default()
                     };
                     children.push(index);
                 }
@@ -163,20 +297,111 @@ where
             centroid.div_assign(F::from_usize(children.len()));
         }
 
-        DBSCAN {
-            _t: PhantomData::default(),
-            centroids,
-            membership,
-            outliers,
+        Self::new(centroids, membership, outliers, expansion_steps)
+    }
+
+    fn fit_with<N>(dataset: &[P], params: &Params<F>, nns: &N, weights: Option<&[F]>) -> Self
+    where
+        N: NeighborSearch<F, P>,
+    {
+        let neighbors_fn = |index: usize| -> Vec<usize> {
+            nns.search_radius(&dataset[index], params.epsilon())
+                .into_iter()
+                .map(|neighbor| neighbor.index)
+                .collect()
+        };
+        match weights {
+            Some(weights) => {
+                let min_points = F::from_usize(params.min_points());
+                Self::fit_with_core(dataset, params, |index| {
+                    let neighbors = neighbors_fn(index);
+                    let density = neighbors
+                        .iter()
+                        .fold(F::zero(), |total, &neighbor| total + weights[neighbor]);
+                    (density >= min_points).then_some(neighbors)
+                })
+            }
+            // Test the core point by counting before collecting its neighbors, since most points
+            // are outliers or already assigned when the neighbors are not needed.
+            None => Self::fit_with_core(dataset, params, |index| {
+                nns.has_at_least(&dataset[index], params.epsilon(), params.min_points())
+                    .then(|| neighbors_fn(index))
+            }),
+        }
+    }
+
+    /// Expand the cluster from the given neighbors and return the number of expanded points.
+    fn expand_cluster<C>(
+        cluster_id: usize,
+        max_neighbors: Option<usize>,
+        core_fn: &C,
+        neighbors: &[usize],
+        labels: &mut [Label],
+    ) -> usize
+    where
+        C: Fn(usize) -> Option<Vec<usize>>,
+    {
+        let mut steps = 0;
+        let mut queue = VecDeque::new();
+        queue.extend(neighbors.iter().copied());
+        while let Some(current_index) = queue.pop_front() {
+            // Only undefined points are enqueued and marked, so each point is expanded once.
+            if labels[current_index].is_assigned() {
+                continue;
+            }
+
+            labels[current_index] = Label::Assigned(cluster_id);
+            steps += 1;
+
+            let Some(secondary_neighbors) = core_fn(current_index) else {
+                continue;
+            };
+
+            let mut candidates = Vec::new();
+            for index in secondary_neighbors {
+                if labels[index].is_undefined() {
+                    candidates.push(index);
+                } else if labels[index].is_outlier() {
+                    // An outlier within the neighborhood of a core point is a border point, which
+                    // is assigned without being expanded.
+                    labels[index] = Label::Assigned(cluster_id);
+                }
+            }
+            for secondary_index in Self::sample_neighbors(candidates, max_neighbors) {
+                labels[secondary_index] = Label::Marked;
+                queue.push_back(secondary_index);
+            }
+        }
+        steps
+    }
+
+    /// Return an evenly spaced sample of the given neighbors if there are more than the maximum.
+    #[must_use]
+    fn sample_neighbors(neighbors: Vec<usize>, max_neighbors: Option<usize>) -> Vec<usize> {
+        match max_neighbors {
+            Some(max) if neighbors.len() > max => (0..max)
+                .map(|i| neighbors[i * neighbors.len() / max])
+                .collect(),
+            _ => neighbors,
         }
     }
 }
 
+impl<F, P> Fit<F, P, Params<F>> for DBSCAN<F, P>
+where
+    F: Float,
+    P: Point<F>,
+{
+    fn fit(dataset: &[P], params: &Params<F>) -> Self {
+        Self::fit_with_weights(dataset, None, params)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::math::distance::metric::DistanceMetric;
-    use crate::math::point::Point2;
+    use crate::math::point::{Point2, Point3};
 
     const DATASET: [Point2<f64>; 16] = [
         Point2(0.0, 0.0), // 0
@@ -203,12 +428,217 @@ mod tests {
         let params = Params::new(4, 2.0_f64.sqrt(), DistanceMetric::Euclidean);
         let dbscan = DBSCAN::fit(&dataset, &params);
 
-        let mut centroids = dbscan.centroids();
-        centroids.sort_by(|point1, point2| point1.0.total_cmp(&point2.0));
         assert_eq!(
-            centroids,
-            Vec::from([Point2(0.5, 7.5), Point2(1.0, 1.0), Point2(4.4, 3.8)])
+            dbscan.centroids(),
+            Vec::from([Point2(1.0, 1.0), Point2(4.4, 3.8), Point2(0.5, 7.5)])
         );
+        assert_eq!(dbscan.members_at(2), &[2, 3, 7, 8]);
+        assert_eq!(dbscan.members_at(3), &[]);
         assert_eq!(dbscan.outliers(), Vec::new());
     }
+
+    #[test]
+    fn stats_should_report_clusters_outliers_and_steps() {
+        let dataset = Vec::from(DATASET);
+        let params = Params::new(4, 2.0_f64.sqrt(), DistanceMetric::Euclidean);
+        let dbscan = DBSCAN::fit(&dataset, &params);
+        assert_eq!(
+            dbscan.stats(),
+            Stats {
+                clusters: 3,
+                outliers: 0,
+                expansion_steps: 16,
+            }
+        );
+
+        let params = Params::new(2, 0.5, DistanceMetric::Euclidean);
+        let stats = DBSCAN::fit(&dataset, &params).stats();
+        assert_eq!(stats.clusters, 0);
+        assert_eq!(stats.outliers, 16);
+        assert_eq!(stats.expansion_steps, 0);
+
+        let stats = DBSCAN::<f64, Point2<f64>>::fit(&[], &params).stats();
+        assert_eq!(stats.expansion_steps, 0);
+    }
+
+    #[test]
+    fn fit_should_fit_array_slice() {
+        let params = Params::new(4, 2.0_f64.sqrt(), DistanceMetric::Euclidean);
+        let dbscan = DBSCAN::fit(&DATASET[..], &params);
+        assert_eq!(
+            dbscan.centroids(),
+            DBSCAN::fit(&Vec::from(DATASET), &params).centroids()
+        );
+        assert_eq!(dbscan.members_at(2), &[2, 3, 7, 8]);
+    }
+
+    #[test]
+    fn fit_should_cluster_dense_block_with_max_neighbors() {
+        // A uniform block where every point is within the epsilon of every other point.
+        let dataset: Vec<Point2<f64>> = (0..1600)
+            .map(|i| Point2(f64::from(i % 40), f64::from(i / 40)))
+            .collect();
+        let params = Params::new(8, 100.0, DistanceMetric::Euclidean).with_max_neighbors(16);
+        let dbscan = DBSCAN::fit(&dataset, &params);
+        assert_eq!(dbscan.centroids(), vec![Point2(19.5, 19.5)]);
+        assert_eq!(dbscan.count_at(0), dataset.len());
+        assert!(dbscan.outliers().is_empty());
+    }
+
+    #[test]
+    fn sample_neighbors_should_limit_number_of_neighbors() {
+        let neighbors: Vec<usize> = (0..10).collect();
+        assert_eq!(
+            DBSCAN::<f64, Point2<f64>>::sample_neighbors(neighbors.clone(), Some(4)),
+            vec![0, 2, 5, 7]
+        );
+        assert_eq!(
+            DBSCAN::<f64, Point2<f64>>::sample_neighbors(neighbors.clone(), None),
+            neighbors
+        );
+    }
+
+    #[test]
+    fn fit_weighted_should_count_weights_as_density() {
+        let dataset = Vec::from(DATASET);
+        let params = Params::new(4, 2.0_f64.sqrt(), DistanceMetric::Euclidean);
+
+        let weights = vec![1.0; DATASET.len()];
+        let dbscan = DBSCAN::fit_weighted(&dataset, &weights, &params);
+        assert_eq!(
+            dbscan.centroids(),
+            DBSCAN::fit(&dataset, &params).centroids()
+        );
+
+        // The second cluster is too light to be dense.
+        let mut weights = vec![1.0; DATASET.len()];
+        for index in [2, 3, 7, 8] {
+            weights[index] = 0.5;
+        }
+        let dbscan = DBSCAN::fit_weighted(&dataset, &weights, &params);
+        assert_eq!(dbscan.centroids().len(), 2);
+        assert_eq!(dbscan.outliers(), vec![2, 3, 7, 8]);
+    }
+
+    #[test]
+    fn fit_weighted_deduplicated_should_match_fit_weighted() {
+        let dataset: Vec<Point2<f64>> = DATASET
+            .iter()
+            .flat_map(|point| [*point; 3])
+            .chain([Point2(9.0, 9.0), Point2(9.0, 9.0)])
+            .collect();
+        let weights: Vec<f64> = (0..dataset.len()).map(|i| (i % 4) as f64 / 2.0).collect();
+        let params = Params::new(4, 2.0, DistanceMetric::Euclidean);
+
+        let expected = DBSCAN::fit_weighted(&dataset, &weights, &params);
+        let actual = DBSCAN::fit_weighted_deduplicated(&dataset, &weights, &params);
+        assert_eq!(actual.centroids(), expected.centroids());
+        for cluster_id in 0..expected.centroids().len() {
+            assert_eq!(
+                actual.members_at(cluster_id),
+                expected.members_at(cluster_id)
+            );
+        }
+        assert_eq!(actual.outliers(), expected.outliers());
+        assert!(actual.stats().expansion_steps < expected.stats().expansion_steps);
+    }
+
+    #[test]
+    #[should_panic(expected = "Weights should be given for each point")]
+    fn fit_weighted_should_panic_if_weights_mismatch() {
+        let dataset = Vec::from(DATASET);
+        let params = Params::new(4, 2.0_f64.sqrt(), DistanceMetric::Euclidean);
+        let _ = DBSCAN::fit_weighted(&dataset, &[1.0], &params);
+    }
+
+    #[test]
+    fn fit_with_neighbors_should_use_given_adjacency() {
+        // Two chains of adjacent points regardless of their coordinates.
+        let dataset = vec![Point2(0.0, 0.0); 7];
+        let adjacency: [&[usize]; 7] = [
+            &[0, 1],
+            &[0, 1, 2],
+            &[1, 2],
+            &[3, 4],
+            &[3, 4, 5],
+            &[4, 5],
+            &[6],
+        ];
+        let params = Params::new(3, 0.0, DistanceMetric::Euclidean);
+        let dbscan =
+            DBSCAN::fit_with_neighbors(&dataset, &params, |index| adjacency[index].to_vec());
+
+        assert_eq!(dbscan.centroids().len(), 2);
+        assert_eq!(dbscan.members_at(0), &[0, 1, 2]);
+        assert_eq!(dbscan.members_at(1), &[3, 4, 5]);
+        assert_eq!(dbscan.outliers(), vec![6]);
+    }
+
+    #[test]
+    fn fit_should_number_clusters_by_size_regardless_of_order() {
+        let params = Params::new(4, 2.0_f64.sqrt(), DistanceMetric::Euclidean);
+        let expected = DBSCAN::fit(&DATASET[..], &params);
+        assert_eq!(
+            (0..3).map(|id| expected.count_at(id)).collect::<Vec<_>>(),
+            vec![7, 5, 4]
+        );
+
+        // Visit the points in a different order, where the smallest cluster is found first.
+        let order: Vec<usize> = (0..DATASET.len()).map(|i| (i * 7 + 3) % 16).collect();
+        let shuffled: Vec<Point2<f64>> = order.iter().map(|&index| DATASET[index]).collect();
+        let actual = DBSCAN::fit(&shuffled, &params);
+        assert_eq!(actual.centroids(), expected.centroids());
+        for cluster_id in 0..3 {
+            let mut members: Vec<usize> = actual
+                .members_at(cluster_id)
+                .iter()
+                .map(|&index| order[index])
+                .collect();
+            members.sort_unstable();
+            assert_eq!(members, expected.members_at(cluster_id));
+        }
+    }
+
+    #[test]
+    fn fit_should_produce_same_clusters_with_linear_search_and_kdtree() {
+        let dataset = Vec::from(DATASET);
+        let params = Params::new(4, 2.0_f64.sqrt(), DistanceMetric::Euclidean);
+
+        let linear = DBSCAN::fit_with(
+            &dataset,
+            &params,
+            &LinearSearch::new(&dataset, *params.metric()),
+            None,
+        );
+        let kdtree = DBSCAN::fit_with(
+            &dataset,
+            &params,
+            &KDTree::new(&dataset, params.metric()),
+            None,
+        );
+        assert_eq!(linear.centroids(), kdtree.centroids());
+        assert_eq!(linear.membership, kdtree.membership);
+        assert_eq!(linear.outliers(), kdtree.outliers());
+    }
+
+    #[test]
+    fn fit_should_merge_perceptually_close_colors_with_ciede2000() {
+        // Pairs of saturated colors differing only in chroma in CIE L*a*b* color space.
+        let dataset = vec![
+            Point3(50.0, 100.0, 0.0),
+            Point3(50.0, 110.0, 0.0),
+            Point3(60.0, 0.0, -100.0),
+            Point3(60.0, 0.0, -110.0),
+        ];
+
+        let params = Params::new(1, 3.0, DistanceMetric::Euclidean);
+        let dbscan = DBSCAN::fit(&dataset, &params);
+        assert_eq!(dbscan.centroids().len(), 4);
+
+        let params = Params::new(1, 3.0, DistanceMetric::CIEDE2000);
+        let dbscan = DBSCAN::fit(&dataset, &params);
+        assert_eq!(dbscan.centroids().len(), 2);
+        assert_eq!(dbscan.members_at(0), &[0, 1]);
+        assert_eq!(dbscan.members_at(1), &[2, 3]);
+    }
 }
@@ -30,25 +30,25 @@ mod tests {
 
     #[test]
     fn is_assigned_should_return_true_if_label_is_assigned() {
-        assert_eq!(Label::Assigned(0).is_assigned(), true);
-        assert_eq!(Label::Outlier.is_assigned(), false);
-        assert_eq!(Label::Marked.is_assigned(), false);
-        assert_eq!(Label::Undefined.is_assigned(), false);
+        assert!(Label::Assigned(0).is_assigned());
+        assert!(!Label::Outlier.is_assigned());
+        assert!(!Label::Marked.is_assigned());
+        assert!(!Label::Undefined.is_assigned());
     }
 
     #[test]
     fn is_outlier_should_return_true_if_label_is_outlier() {
-        assert_eq!(Label::Assigned(0).is_outlier(), false);
-        assert_eq!(Label::Outlier.is_outlier(), true);
-        assert_eq!(Label::Marked.is_outlier(), false);
-        assert_eq!(Label::Undefined.is_outlier(), false);
+        assert!(!Label::Assigned(0).is_outlier());
+        assert!(Label::Outlier.is_outlier());
+        assert!(!Label::Marked.is_outlier());
+        assert!(!Label::Undefined.is_outlier());
     }
 
     #[test]
     fn is_undefined_should_return_true_if_label_is_undefined() {
-        assert_eq!(Label::Assigned(0).is_undefined(), false);
-        assert_eq!(Label::Outlier.is_undefined(), false);
-        assert_eq!(Label::Marked.is_undefined(), false);
-        assert_eq!(Label::Undefined.is_undefined(), true);
+        assert!(!Label::Assigned(0).is_undefined());
+        assert!(!Label::Outlier.is_undefined());
+        assert!(!Label::Marked.is_undefined());
+        assert!(Label::Undefined.is_undefined());
     }
 }
@@ -1,3 +1,4 @@
 pub(crate) mod algorithm;
 mod label;
 pub(crate) mod params;
+pub(crate) mod stats;
@@ -10,12 +10,20 @@ where
     min_points: usize,
     epsilon: F,
     metric: DistanceMetric,
+    max_neighbors: Option<usize>,
 }
 
 impl<F> Params<F>
 where
     F: Float,
 {
+    /// The minimum number of points recommended per dimension.
+    const MIN_POINTS_PER_DIM: usize = 5;
+    /// The epsilon recommended for points of color and position.
+    const EPSILON_5D: f64 = 0.025;
+    /// The dataset size above which the minimum number of points is raised.
+    const BASE_DATASET_LEN: usize = 1_000_000;
+
     /// Create a new Params with required parameters.
     #[must_use]
     pub fn new(min_points: usize, epsilon: F, metric: DistanceMetric) -> Self {
@@ -23,9 +31,46 @@ where
             min_points,
             epsilon,
             metric,
+            max_neighbors: None,
         }
     }
 
+    /// Set the maximum number of neighbors enqueued per expansion step.
+    ///
+    /// Neighborhoods of dense regions, such as a large area of a single color, may hold a huge
+    /// number of points. If more neighbors than the maximum are to be enqueued, an evenly spaced
+    /// sample of them is enqueued instead and the rest are reached through the sampled neighbors.
+    /// This bounds the memory at the cost of a little accuracy. There is no limit by default.
+    #[must_use]
+    pub fn with_max_neighbors(mut self, max_neighbors: usize) -> Self {
+        self.max_neighbors = Some(max_neighbors.max(1));
+        self
+    }
+
+    /// Create a new Params with the parameters recommended for the given dimension and dataset.
+    ///
+    /// The minimum number of points is 5 per dimension, e.g. 15 for colors and 25 for colors with
+    /// positions. For datasets larger than a million points, such as the pixels of a megapixel
+    /// image, it is raised by the square root of the ratio, since the neighborhoods of denser data
+    /// hold more points. The epsilon is 0.025 for 5-D points in a space normalized to the unit range
+    /// and scales with the square root of the dimension, since the distance between points grows
+    /// with it. The metric is Euclidean.
+    #[must_use]
+    pub fn recommended(dim: usize, dataset_len: usize) -> Self {
+        let dim = dim.max(1);
+        let base = F::from_usize(Self::MIN_POINTS_PER_DIM * dim);
+        let scale = (F::from_usize(dataset_len) / F::from_usize(Self::BASE_DATASET_LEN))
+            .sqrt()
+            .max(F::one());
+        let min_points = (base * scale)
+            .round()
+            .to_usize()
+            .expect("Minimum number of points should be converted to usize");
+        let epsilon =
+            F::from_f64(Self::EPSILON_5D) * (F::from_usize(dim) / F::from_f64(5.0)).sqrt();
+        Self::new(min_points, epsilon, DistanceMetric::Euclidean)
+    }
+
     /// Return the minimum number of points.
     #[must_use]
     pub fn min_points(&self) -> usize {
@@ -43,6 +88,12 @@ where
     pub fn metric(&self) -> &DistanceMetric {
         &self.metric
     }
+
+    /// Return the maximum number of neighbors enqueued per expansion step if limited.
+    #[must_use]
+    pub fn max_neighbors(&self) -> Option<usize> {
+        self.max_neighbors
+    }
 }
 
 #[cfg(test)]
@@ -55,5 +106,33 @@ mod tests {
         assert_eq!(params.min_points(), 16);
         assert_eq!(params.epsilon(), 5.0);
         assert_eq!(params.metric(), &DistanceMetric::SquaredEuclidean);
+        assert_eq!(params.max_neighbors(), None);
+
+        let params = params.with_max_neighbors(64);
+        assert_eq!(params.max_neighbors(), Some(64));
+        assert_eq!(params.with_max_neighbors(0).max_neighbors(), Some(1));
+    }
+
+    #[test]
+    fn recommended_should_scale_with_dimension() {
+        let dataset_len = 10_000;
+        let params3: Params<f64> = Params::recommended(3, dataset_len);
+        let params4: Params<f64> = Params::recommended(4, dataset_len);
+        let params5: Params<f64> = Params::recommended(5, dataset_len);
+        assert_eq!(params3.min_points(), 15);
+        assert_eq!(params4.min_points(), 20);
+        assert_eq!(params5.min_points(), 25);
+        assert!(params3.epsilon() < params4.epsilon() && params4.epsilon() < params5.epsilon());
+        assert!((params5.epsilon() - 0.025).abs() < 1e-12);
+        assert_eq!(params5.metric(), &DistanceMetric::Euclidean);
+    }
+
+    #[test]
+    fn recommended_should_raise_min_points_for_large_dataset() {
+        let params: Params<f64> = Params::recommended(5, 100);
+        assert_eq!(params.min_points(), 25);
+
+        let params: Params<f64> = Params::recommended(5, 4_000_000);
+        assert_eq!(params.min_points(), 50);
     }
 }
@@ -0,0 +1,12 @@
+/// Statistics of a DBSCAN clustering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of clusters.
+    pub clusters: usize,
+    /// The number of points not belonging to any cluster.
+    pub outliers: usize,
+    /// The number of points whose neighborhoods were searched while expanding the clusters.
+    ///
+    /// Each point is expanded at most once, so this never exceeds the number of points.
+    pub expansion_steps: usize,
+}
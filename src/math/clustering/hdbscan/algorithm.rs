@@ -5,6 +5,7 @@ use crate::math::number::Float;
 use crate::math::point::Point;
 
 /// HDBSCAN clustering algorithm.
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Debug, Clone)]
 struct HDBSCAN {}
 
@@ -29,8 +30,3 @@ where
         todo!()
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-}
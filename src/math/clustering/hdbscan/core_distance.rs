@@ -23,20 +23,49 @@ where
         }
 
         let k = dataset.len().min(min_samples + 1);
-        let dataset_vec = dataset.to_vec();
-        let neighbor_search = KDTree::new(&dataset_vec, metric);
-        let mut distances = Vec::with_capacity(dataset.len());
-        for (index, point) in dataset.iter().enumerate() {
-            let neighbors = neighbor_search.search(point, k);
-            if let Some(core_neighbor) = neighbors.last() {
-                distances.insert(index, core_neighbor.distance);
-            } else {
-                distances.insert(index, F::max_value());
-            }
+        let neighbor_search = KDTree::new(dataset, metric);
+        let distances = dataset
+            .iter()
+            .map(|point| Self::core_distance(&neighbor_search, point, k))
+            .collect();
+        Self { distances }
+    }
+
+    /// Create a core distance for the given dataset, computing the distance of each point in
+    /// parallel.
+    ///
+    /// The distances are the same as [`CoreDistance::new`] and are stored in the same order.
+    #[cfg(feature = "rayon")]
+    pub fn new_parallel<P>(dataset: &[P], min_samples: usize, metric: &DistanceMetric) -> Self
+    where
+        F: Send + Sync,
+        P: Point<F> + Send + Sync,
+    {
+        use rayon::prelude::*;
+
+        if dataset.is_empty() {
+            return Self {
+                distances: Vec::new(),
+            };
         }
+
+        let k = dataset.len().min(min_samples + 1);
+        let neighbor_search = KDTree::new(dataset, metric);
+        let distances = dataset
+            .par_iter()
+            .map(|point| Self::core_distance(&neighbor_search, point, k))
+            .collect();
         Self { distances }
     }
 
+    /// Return the distance to the k-th nearest neighbor of the given point.
+    fn core_distance<P: Point<F>>(neighbor_search: &KDTree<F, P>, point: &P, k: usize) -> F {
+        neighbor_search
+            .search(point, k)
+            .last()
+            .map_or(F::max_value(), |core_neighbor| core_neighbor.distance)
+    }
+
     /// Returns the distance corresponding to the index.
     pub fn distance_at(&self, index: usize) -> F {
         assert!(index < self.distances.len());
@@ -68,4 +97,23 @@ mod tests {
         assert_eq!(core_distance.distance_at(4), 2.4200000000000004);
         assert_eq!(core_distance.distance_at(5), 4.5);
     }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn new_parallel_should_match_serial() {
+        let dataset: Vec<Point2<f64>> = (0..500)
+            .map(|i| {
+                let t = f64::from(i);
+                Point2::new((t * 0.37).sin() * 10.0, (t * 0.11).cos() * 5.0 + t * 0.01)
+            })
+            .collect();
+        for metric in [DistanceMetric::Euclidean, DistanceMetric::SquaredEuclidean] {
+            let serial = CoreDistance::new(&dataset, 5, &metric);
+            let parallel = CoreDistance::new_parallel(&dataset, 5, &metric);
+            assert_eq!(parallel.distances.len(), serial.distances.len());
+            for index in 0..dataset.len() {
+                assert_eq!(parallel.distance_at(index), serial.distance_at(index));
+            }
+        }
+    }
 }
@@ -1,10 +1,8 @@
 use crate::math::clustering::hierarchical::node::Node;
 use crate::math::clustering::hierarchical::union_find::UnionFind;
 use crate::math::graph::edge::Edge;
-use crate::math::graph::spanning_tree;
 use crate::math::graph::spanning_tree::{MinimumSpanningTree, SpanningTree};
 use crate::math::number::Float;
-use std::cmp::Ordering;
 use std::collections::{BinaryHeap, VecDeque};
 
 #[derive(Debug, PartialEq)]
@@ -29,12 +27,7 @@ where
 
         let spanning_tree = MinimumSpanningTree::build(dataset, weight_fn);
         let mut edges = spanning_tree.edges().to_vec();
-        edges.sort_unstable_by(|edge1, edge2| {
-            edge1
-                .weight()
-                .partial_cmp(&edge2.weight())
-                .unwrap_or(Ordering::Greater)
-        });
+        edges.sort_unstable_by(|edge1, edge2| edge1.weight().total_order(&edge2.weight()));
 
         let n_edge = edges.len();
         let n_node = n_edge + 1;
@@ -1,3 +1,3 @@
-mod algorithm;
+pub(crate) mod algorithm;
 mod node;
 mod union_find;
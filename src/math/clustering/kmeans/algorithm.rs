@@ -1,14 +1,18 @@
 use crate::math::clustering::kmeans::cluster::Cluster;
 use crate::math::clustering::kmeans::params::KmeansParams;
+use crate::math::clustering::metrics::adjusted_rand_index;
 use crate::math::clustering::traits::Fit;
 use crate::math::distance::metric::DistanceMetric;
 use crate::math::neighbors::kdtree::KDTree;
+use crate::math::neighbors::linear::LinearSearch;
 use crate::math::neighbors::nns::NeighborSearch;
+use crate::math::neighbors::LINEAR_SEARCH_THRESHOLD;
 use crate::math::number::Float;
 use crate::math::point::Point;
-use rand::Rng;
+use rand::{Rng, SeedableRng};
 use std::marker::PhantomData;
 
+/// Kmeans clustering algorithm.
 pub struct Kmeans<F, P>
 where
     F: Float,
@@ -23,52 +27,163 @@ where
     F: Float,
     P: Point<F>,
 {
-    pub(crate) fn centroids(&self) -> Vec<P> {
+    /// Return the centroid of each cluster.
+    #[must_use]
+    pub fn centroids(&self) -> Vec<P> {
         self.clusters
             .iter()
             .map(|cluster| -> P { *cluster.centroid() })
             .collect()
     }
 
-    pub(crate) fn count_at(&self, index: usize) -> usize {
+    /// Count the number of points assigned to the cluster at the given index.
+    #[must_use]
+    pub fn count_at(&self, index: usize) -> usize {
         let cluster = self.clusters.get(index);
         cluster.map_or(0, |c| c.size())
     }
 
+    /// Return the mean squared distance of the points of the cluster at the given index to its
+    /// centroid, or zero if there is no such cluster.
+    ///
+    /// A high variance indicates a cluster whose points vary a lot.
+    #[must_use]
+    pub fn variance_at(&self, index: usize, dataset: &[P], metric: &DistanceMetric) -> F {
+        let cluster = self.clusters.get(index);
+        cluster.map_or(F::zero(), |c| c.variance(dataset, metric))
+    }
+
+    /// Return the cluster index assigned to each point of the fitted dataset.
+    #[must_use]
+    pub fn labels(&self) -> Vec<usize> {
+        let size = self.clusters.iter().map(|cluster| cluster.size()).sum();
+        let mut labels = vec![0; size];
+        for (cluster_id, cluster) in self.clusters.iter().enumerate() {
+            cluster
+                .children()
+                .for_each(|&index| labels[index] = cluster_id);
+        }
+        labels
+    }
+
+    /// Return the sum of the distances from each point to the centroid of its cluster.
+    ///
+    /// The distances are not squared, so the cost can be compared between runs with different
+    /// numbers of clusters or seeds.
+    #[must_use]
+    pub fn total_cost(&self, dataset: &[P], metric: &DistanceMetric) -> F {
+        let centroids = self.centroids();
+        dataset
+            .iter()
+            .zip(self.labels())
+            .fold(F::zero(), |total, (point, label)| {
+                total + metric.measure(point, &centroids[label])
+            })
+    }
+
+    /// Fit the dataset the given number of times and return the model with the lowest cost.
+    ///
+    /// Each run after the first starts from centroids chosen by a random number generator seeded
+    /// independently from the one of the initializer. The first run is the same as [`Fit::fit`].
+    #[must_use]
+    pub fn fit_best_of<R>(dataset: &[P], params: &KmeansParams<F, R>, n_runs: usize) -> Self
+    where
+        R: Rng + SeedableRng + Clone,
+    {
+        Self::reseeded_params(params, n_runs.max(1))
+            .into_iter()
+            .map(|params| {
+                let kmeans = Self::fit(dataset, &params);
+                (kmeans.total_cost(dataset, params.metric()), kmeans)
+            })
+            .reduce(|best, run| if run.0 < best.0 { run } else { best })
+            .map(|(_, kmeans)| kmeans)
+            .expect("At least one run should be fitted")
+    }
+
+    /// Return the parameters of the given number of runs, each with its own random number
+    /// generator.
+    ///
+    /// The first run keeps the parameters as they are, and each following run seeds a new random
+    /// number generator with a value drawn from the one of the initializer, so that the runs do
+    /// not share a shifted random stream.
+    fn reseeded_params<R>(params: &KmeansParams<F, R>, n_runs: usize) -> Vec<KmeansParams<F, R>>
+    where
+        R: Rng + SeedableRng + Clone,
+    {
+        let mut seeds = params.initializer().rng().clone();
+        (0..n_runs)
+            .map(|run| {
+                if run == 0 {
+                    return params.clone();
+                }
+                let rng = R::seed_from_u64(seeds.next_u64());
+                let initializer = params.initializer().with_rng(rng);
+                params.clone().with_initializer(initializer)
+            })
+            .collect()
+    }
+
+    /// Fit the dataset the given number of times and return how consistently the runs agree.
+    ///
+    /// Each run is seeded independently as in [`Kmeans::fit_best_of`], and the score is the mean
+    /// adjusted Rand index of the labels over all pairs of runs. A score near 1 means the
+    /// clustering hardly depends on the seed, while a low score warns that the centroids are
+    /// sensitive to the initialization.
+    #[must_use]
+    pub fn stability<R>(dataset: &[P], params: &KmeansParams<F, R>, n_runs: usize) -> F
+    where
+        R: Rng + SeedableRng + Clone,
+    {
+        let runs: Vec<Vec<usize>> = Self::reseeded_params(params, n_runs)
+            .iter()
+            .map(|params| Self::fit(dataset, params).labels())
+            .collect();
+
+        let mut total = F::zero();
+        let mut count = 0;
+        for (i, labels1) in runs.iter().enumerate() {
+            for labels2 in runs.iter().skip(i + 1) {
+                total += adjusted_rand_index(labels1, labels2);
+                count += 1;
+            }
+        }
+        if count == 0 {
+            return F::one();
+        }
+        total / F::from_usize(count)
+    }
+
+    /// Reassign each point to the cluster of its nearest centroid and update the centroids.
+    ///
+    /// Only the points whose nearest centroid changed since the previous iteration are moved, and
+    /// the running sums of the affected clusters are adjusted instead of summing all members again.
     fn reassign(
         dataset: &[P],
         clusters: &mut [Cluster<F, P>],
+        labels: &mut [Option<usize>],
         metric: &DistanceMetric,
         tolerance: F,
     ) -> bool {
-        let mut centroids = Vec::with_capacity(clusters.len());
-        for cluster in clusters.iter_mut() {
-            centroids.push(*cluster.centroid());
-            cluster.clear();
+        let centroids: Vec<P> = clusters.iter().map(|cluster| *cluster.centroid()).collect();
+        if centroids.len() < LINEAR_SEARCH_THRESHOLD {
+            let nns = LinearSearch::new(&centroids, *metric);
+            Self::assign(dataset, clusters, labels, &nns);
+        } else {
+            let nns = KDTree::new(&centroids, metric);
+            Self::assign(dataset, clusters, labels, &nns);
         }
 
-        let nns = KDTree::new(&centroids, metric);
-        dataset.iter().enumerate().for_each(|(index, data)| {
-            let result = nns.search_nearest(data);
-            if let Some(nearest) = result {
-                let cluster = clusters
-                    .get_mut(nearest.index)
-                    .expect("No cluster is found");
-                cluster.insert(index, data);
-            }
-        });
-
         let mut converged = false;
         clusters
             .iter_mut()
             .zip(centroids)
             .for_each(|(cluster, old_centroid)| {
+                cluster.update_centroid();
                 if cluster.is_empty() {
                     return;
                 }
 
-                cluster.update_centroid();
-
                 let difference = metric.measure(&old_centroid, cluster.centroid());
                 if difference < tolerance {
                     converged = true;
@@ -76,6 +191,32 @@ where
             });
         converged
     }
+
+    /// Move each point whose nearest centroid differs from its label to the nearest cluster.
+    fn assign<N>(
+        dataset: &[P],
+        clusters: &mut [Cluster<F, P>],
+        labels: &mut [Option<usize>],
+        nns: &N,
+    ) where
+        N: NeighborSearch<F, P>,
+    {
+        dataset.iter().enumerate().for_each(|(index, data)| {
+            let nearest = nns.search_nearest(data).map(|nearest| nearest.index);
+            if nearest == labels[index] {
+                return;
+            }
+
+            if let Some(previous) = labels[index] {
+                clusters[previous].remove(index, data);
+            }
+            if let Some(nearest) = nearest {
+                let cluster = clusters.get_mut(nearest).expect("No cluster is found");
+                cluster.insert(index, data);
+            }
+            labels[index] = nearest;
+        });
+    }
 }
 
 impl<F, P, R> Fit<F, P, KmeansParams<F, R>> for Kmeans<F, P>
@@ -87,7 +228,7 @@ where
     fn fit(dataset: &[P], params: &KmeansParams<F, R>) -> Self {
         if params.k() == 0 {
             return Self {
-                _t: PhantomData::default(),
+                _t: PhantomData,
                 clusters: Vec::with_capacity(0),
             };
         }
@@ -103,7 +244,7 @@ where
                 })
                 .collect();
             return Self {
-                _t: PhantomData::default(),
+                _t: PhantomData,
                 clusters,
             };
         }
@@ -114,15 +255,25 @@ where
             .iter()
             .map(|centroid| Cluster::new(centroid))
             .collect();
+        let mut labels = vec![None; dataset.len()];
         for _ in 0..params.max_iterations() {
-            let converged =
-                Self::reassign(dataset, &mut clusters, params.metric(), params.tolerance());
+            let converged = Self::reassign(
+                dataset,
+                &mut clusters,
+                &mut labels,
+                params.metric(),
+                params.tolerance(),
+            );
             if converged {
                 break;
             }
         }
+        // The running sums drift with each move, so the final centroids are computed from scratch.
+        clusters
+            .iter_mut()
+            .for_each(|cluster| cluster.refresh(dataset));
         Kmeans {
-            _t: PhantomData::default(),
+            _t: PhantomData,
             clusters,
         }
     }
@@ -134,7 +285,8 @@ mod tests {
     use crate::math::clustering::kmeans::init::Initializer;
     use crate::math::clustering::traits::Fit;
     use crate::math::point::Point2;
-    use rand::thread_rng;
+    use rand::rngs::StdRng;
+    use rand::{thread_rng, SeedableRng};
 
     #[test]
     fn new_should_create_kmeans() {
@@ -147,7 +299,219 @@ mod tests {
         ];
         let metric = DistanceMetric::SquaredEuclidean;
         let initializer = Initializer::KmeansPlusPlus(thread_rng());
-        let mut params = KmeansParams::new(2, metric, initializer);
-        let _kmeans = Kmeans::fit(&dataset, &mut params);
+        let params = KmeansParams::new(2, metric, initializer);
+        let _kmeans = Kmeans::fit(&dataset, &params);
+    }
+
+    #[test]
+    fn assign_should_produce_same_clusters_with_linear_search_and_kdtree() {
+        let dataset = vec![
+            Point2(1.0, 2.0),
+            Point2(3.0, 1.0),
+            Point2(4.0, 5.0),
+            Point2(5.0, 5.0),
+            Point2(2.0, 4.0),
+        ];
+        let centroids = vec![Point2(1.0, 1.0), Point2(5.0, 4.0)];
+        let metric = DistanceMetric::SquaredEuclidean;
+        let new_clusters =
+            || -> Vec<Cluster<f64, Point2<f64>>> { centroids.iter().map(Cluster::new).collect() };
+
+        let mut linear_clusters = new_clusters();
+        Kmeans::assign(
+            &dataset,
+            &mut linear_clusters,
+            &mut [None; 5],
+            &LinearSearch::new(&centroids, metric),
+        );
+        let mut kdtree_clusters = new_clusters();
+        Kmeans::assign(
+            &dataset,
+            &mut kdtree_clusters,
+            &mut [None; 5],
+            &KDTree::new(&centroids, &metric),
+        );
+        assert_eq!(linear_clusters, kdtree_clusters);
+        assert_eq!(linear_clusters[0].size(), 2);
+        assert_eq!(linear_clusters[1].size(), 3);
+    }
+
+    #[test]
+    fn total_cost_should_sum_distances_to_centroids() {
+        let dataset = vec![
+            Point2(0.0, 0.0),
+            Point2(0.0, 2.0),
+            Point2(10.0, 0.0),
+            Point2(10.0, 4.0),
+        ];
+        let initializer = Initializer::KmeansPlusPlus(StdRng::seed_from_u64(0));
+        let params = KmeansParams::new(2, DistanceMetric::Euclidean, initializer);
+        let kmeans = Kmeans::fit(&dataset, &params);
+        assert_eq!(kmeans.total_cost(&dataset, &DistanceMetric::Euclidean), 6.0);
+        assert_eq!(
+            kmeans.total_cost(&dataset, &DistanceMetric::SquaredEuclidean),
+            10.0
+        );
+    }
+
+    #[test]
+    fn variance_at_should_return_mean_squared_distance_to_centroid() {
+        let dataset = vec![
+            Point2(0.0, 0.0),
+            Point2(0.0, 2.0),
+            Point2(10.0, 0.0),
+            Point2(10.0, 4.0),
+        ];
+        let initializer = Initializer::KmeansPlusPlus(StdRng::seed_from_u64(0));
+        let params = KmeansParams::new(2, DistanceMetric::Euclidean, initializer);
+        let kmeans = Kmeans::fit(&dataset, &params);
+
+        let metric = DistanceMetric::Euclidean;
+        let mut variances = [
+            kmeans.variance_at(0, &dataset, &metric),
+            kmeans.variance_at(1, &dataset, &metric),
+        ];
+        variances.sort_by(f64::total_cmp);
+        assert_eq!(variances, [1.0, 4.0]);
+        assert_eq!(kmeans.variance_at(2, &dataset, &metric), 0.0);
+    }
+
+    #[test]
+    fn fit_best_of_should_escape_bad_seed() {
+        let dataset: Vec<Point2<f64>> = (0..60)
+            .map(|i| {
+                let center = [(0.0, 0.0), (8.0, 1.0), (4.0, 9.0), (9.0, 9.0)][i % 4];
+                let offset = ((i * 7 % 11) as f64 / 5.0, (i * 5 % 13) as f64 / 6.0);
+                Point2(center.0 + offset.0, center.1 + offset.1)
+            })
+            .collect();
+        let metric = DistanceMetric::Euclidean;
+        // The seed 0 merges two of the four blobs in a single run.
+        let initializer = Initializer::Random(StdRng::seed_from_u64(0));
+        let params = KmeansParams::new(4, metric, initializer);
+
+        let single = Kmeans::fit(&dataset, &params).total_cost(&dataset, &metric);
+        let best = Kmeans::fit_best_of(&dataset, &params, 5).total_cost(&dataset, &metric);
+        assert!(single > 2.0 * best, "single: {single}, best: {best}");
+        assert!((best - 50.2749).abs() < 1e-4, "best: {best}");
+        assert_eq!(
+            Kmeans::fit_best_of(&dataset, &params, 1).total_cost(&dataset, &metric),
+            single
+        );
+    }
+
+    #[test]
+    fn reseeded_params_should_start_runs_from_different_centroids() {
+        let dataset: Vec<Point2<f64>> = (0..64)
+            .map(|i| Point2((i % 8) as f64, (i / 8) as f64))
+            .collect();
+        let metric = DistanceMetric::Euclidean;
+        let initializer = Initializer::KmeansPlusPlus(StdRng::seed_from_u64(0));
+        let params = KmeansParams::new(3, metric, initializer);
+
+        let runs = Kmeans::<f64, Point2<f64>>::reseeded_params(&params, 6);
+        assert_eq!(runs[0], params);
+        let centroids: Vec<Vec<Point2<f64>>> = runs
+            .iter()
+            .map(|run| run.initializer().initialize(&dataset, 3, &metric))
+            .collect();
+        for (i, centroids1) in centroids.iter().enumerate() {
+            for centroids2 in centroids.iter().skip(i + 1) {
+                assert_ne!(centroids1, centroids2);
+            }
+        }
+    }
+
+    #[test]
+    fn stability_should_be_high_for_separated_blobs_and_low_for_ring() {
+        let blobs: Vec<Point2<f64>> = (0..90)
+            .map(|i| {
+                let center = [(0.0, 0.0), (50.0, 0.0), (25.0, 40.0)][i % 3];
+                let offset = ((i * 7 % 11) as f64 / 5.0, (i * 5 % 13) as f64 / 6.0);
+                Point2(center.0 + offset.0, center.1 + offset.1)
+            })
+            .collect();
+        // Points evenly spaced on a circle have no preferred partition.
+        let ring: Vec<Point2<f64>> = (0..90)
+            .map(|i| {
+                let angle = i as f64 / 90.0 * std::f64::consts::TAU;
+                Point2(angle.cos() * 10.0, angle.sin() * 10.0)
+            })
+            .collect();
+
+        let initializer = Initializer::KmeansPlusPlus(StdRng::seed_from_u64(3));
+        let params = KmeansParams::new(3, DistanceMetric::Euclidean, initializer);
+        let separated = Kmeans::stability(&blobs, &params, 6);
+        let overlapping = Kmeans::stability(&ring, &params, 6);
+        assert!(separated > 0.9, "separated: {separated}");
+        assert!(overlapping < 0.8, "overlapping: {overlapping}");
+        assert_eq!(Kmeans::stability(&blobs, &params, 1), 1.0);
+    }
+
+    #[test]
+    fn fit_should_produce_same_centroids_as_full_recompute() {
+        // Non-integer coordinates, so that the running sums accumulate rounding errors.
+        let dataset: Vec<Point2<f64>> = (0..200)
+            .map(|i| {
+                let center = [(0.0, 0.0), (40.0, 8.0), (16.0, 48.0), (48.0, 40.0)][i % 4];
+                Point2(
+                    center.0 + (i * 7 % 23) as f64 * 0.731 + (i as f64 * 0.37).sin(),
+                    center.1 + (i * 5 % 19) as f64 * 1.113 + (i as f64 * 0.91).cos() / 3.0,
+                )
+            })
+            .collect();
+        let metric = DistanceMetric::SquaredEuclidean;
+        let initializer = Initializer::KmeansPlusPlus(StdRng::seed_from_u64(7));
+        let params = KmeansParams::new(4, metric, initializer.clone());
+        let kmeans = Kmeans::fit(&dataset, &params);
+
+        // Reference implementation clearing every cluster and summing all members each iteration.
+        let mut clusters: Vec<Cluster<f64, Point2<f64>>> = initializer
+            .initialize(&dataset, params.k(), &metric)
+            .iter()
+            .map(Cluster::new)
+            .collect();
+        for _ in 0..params.max_iterations() {
+            let centroids: Vec<Point2<f64>> = clusters.iter().map(|c| *c.centroid()).collect();
+            clusters.iter_mut().for_each(Cluster::clear);
+            let nns = LinearSearch::new(&centroids, metric);
+            Kmeans::assign(&dataset, &mut clusters, &mut vec![None; 200], &nns);
+
+            let mut converged = false;
+            for (cluster, old_centroid) in clusters.iter_mut().zip(centroids) {
+                if cluster.is_empty() {
+                    continue;
+                }
+                cluster.update_centroid();
+                if metric.measure(&old_centroid, cluster.centroid()) < params.tolerance() {
+                    converged = true;
+                }
+            }
+            if converged {
+                break;
+            }
+        }
+
+        let expected: Vec<Point2<f64>> = clusters.iter().map(|c| *c.centroid()).collect();
+        assert_eq!(kmeans.centroids(), expected);
+    }
+
+    #[test]
+    fn labels_should_return_assigned_cluster_of_each_point() {
+        let dataset = vec![
+            Point2(0.0, 0.0),
+            Point2(9.0, 9.0),
+            Point2(0.0, 1.0),
+            Point2(9.0, 8.0),
+        ];
+        let initializer = Initializer::KmeansPlusPlus(thread_rng());
+        let params = KmeansParams::new(2, DistanceMetric::SquaredEuclidean, initializer);
+        let kmeans = Kmeans::fit(&dataset, &params);
+
+        let labels = kmeans.labels();
+        assert_eq!(labels.len(), 4);
+        assert_eq!(labels[0], labels[2]);
+        assert_eq!(labels[1], labels[3]);
+        assert_ne!(labels[0], labels[1]);
     }
 }
@@ -1,3 +1,4 @@
+use crate::math::distance::metric::DistanceMetric;
 use crate::math::number::Float;
 use crate::math::point::Point;
 use std::collections::HashSet;
@@ -11,6 +12,7 @@ where
 {
     _t: PhantomData<F>,
     centroid: P,
+    sum: P,
     children: HashSet<usize>,
 }
 
@@ -21,8 +23,9 @@ where
 {
     pub fn new(initial_centroid: &P) -> Self {
         Self {
-            _t: PhantomData::default(),
+            _t: PhantomData,
             centroid: *initial_centroid,
+            sum: P::zero(),
             children: HashSet::new(),
         }
     }
@@ -39,22 +42,125 @@ where
         self.children.len()
     }
 
+    pub fn children(&self) -> impl Iterator<Item = &usize> {
+        self.children.iter()
+    }
+
+    /// Return the mean squared distance of the members of this cluster to its centroid.
+    ///
+    /// The centroid must be updated before calling this method. Returns zero if this cluster is
+    /// empty.
+    #[must_use]
+    pub fn variance(&self, dataset: &[P], metric: &DistanceMetric) -> F {
+        if self.is_empty() {
+            return F::zero();
+        }
+        let total = self.children.iter().fold(F::zero(), |total, &index| {
+            let distance = metric.measure(&self.centroid, &dataset[index]);
+            total + distance * distance
+        });
+        total / F::from_usize(self.children.len())
+    }
+
+    /// Update the centroid to the mean of the members from their running sum.
+    ///
+    /// Adding and removing members accumulates rounding errors in the running sum, which
+    /// [`refresh`](Self::refresh) discards.
     pub fn update_centroid(&mut self) {
         if self.is_empty() {
             self.centroid.set_zero();
         } else {
             let size = F::from_usize(self.children.len());
+            self.centroid = self.sum;
             self.centroid.div_assign(size);
         }
     }
 
+    /// Recompute the running sum from the members in ascending order of their indices and update
+    /// the centroid, as if the members were inserted into an empty cluster.
+    pub fn refresh(&mut self, dataset: &[P]) {
+        let mut indices: Vec<usize> = self.children.iter().copied().collect();
+        indices.sort_unstable();
+        self.sum = indices.into_iter().fold(P::zero(), |mut sum, index| {
+            sum.add_assign(dataset[index]);
+            sum
+        });
+        self.update_centroid();
+    }
+
     pub fn insert(&mut self, index: usize, data: &P) {
-        self.centroid.add_assign(*data);
-        self.children.insert(index);
+        if self.children.insert(index) {
+            self.sum.add_assign(*data);
+        }
+    }
+
+    /// Remove the member at the given index and subtract it from the running sum.
+    pub fn remove(&mut self, index: usize, data: &P) {
+        if self.children.remove(&index) {
+            self.sum.sub_assign(*data);
+        }
     }
 
+    #[cfg(test)]
     pub fn clear(&mut self) {
         self.centroid.set_zero();
+        self.sum.set_zero();
         self.children.clear();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::point::Point2;
+
+    fn cluster(dataset: &[Point2<f64>]) -> Cluster<f64, Point2<f64>> {
+        let mut cluster = Cluster::new(&Point2(0.0, 0.0));
+        for (index, point) in dataset.iter().enumerate() {
+            cluster.insert(index, point);
+        }
+        cluster.update_centroid();
+        cluster
+    }
+
+    #[test]
+    fn variance_should_return_mean_squared_distance_to_centroid() {
+        let tight = [Point2(1.0, 1.0), Point2(1.0, 1.001), Point2(1.001, 1.0)];
+        let cluster1 = cluster(&tight);
+        assert!(cluster1.variance(&tight, &DistanceMetric::Euclidean) < 1e-6);
+
+        let dispersed = [
+            Point2(0.0, 0.0),
+            Point2(4.0, 0.0),
+            Point2(0.0, 4.0),
+            Point2(4.0, 4.0),
+        ];
+        let cluster2 = cluster(&dispersed);
+        assert!((cluster2.variance(&dispersed, &DistanceMetric::Euclidean) - 8.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn remove_should_update_running_sum() {
+        let dataset = [Point2(0.0, 0.0), Point2(4.0, 2.0), Point2(2.0, 4.0)];
+        let mut cluster = cluster(&dataset);
+        assert_eq!(cluster.centroid(), &Point2(2.0, 2.0));
+
+        cluster.remove(0, &dataset[0]);
+        cluster.remove(0, &dataset[0]);
+        cluster.update_centroid();
+        assert_eq!(cluster.size(), 2);
+        assert_eq!(cluster.centroid(), &Point2(3.0, 3.0));
+
+        cluster.remove(1, &dataset[1]);
+        cluster.remove(2, &dataset[2]);
+        cluster.update_centroid();
+        assert!(cluster.is_empty());
+        assert_eq!(cluster.centroid(), &Point2(0.0, 0.0));
+    }
+
+    #[test]
+    fn variance_should_return_zero_if_cluster_is_empty() {
+        let cluster: Cluster<f64, Point2<f64>> = Cluster::new(&Point2(1.0, 2.0));
+        assert_eq!(cluster.variance(&[], &DistanceMetric::Euclidean), 0.0);
+    }
+}
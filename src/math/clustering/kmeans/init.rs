@@ -2,16 +2,16 @@ use crate::math::distance::metric::DistanceMetric;
 use crate::math::number::Float;
 use crate::math::point::Point;
 use rand::Rng;
-use std::cmp::Ordering;
 
+/// Strategy to choose the initial centroids of Kmeans.
 #[derive(PartialEq, Eq, Clone, Debug)]
-pub(crate) enum Initializer<R>
+pub enum Initializer<R>
 where
     R: Rng + Clone,
 {
-    #[allow(unused)]
+    /// Choose the initial centroids uniformly at random.
     Random(R),
-    #[allow(unused)]
+    /// Choose the initial centroids far from each other using Kmeans++.
     KmeansPlusPlus(R),
 }
 
@@ -19,6 +19,22 @@ impl<R> Initializer<R>
 where
     R: Rng + Clone,
 {
+    /// Return the random number generator of this initializer.
+    pub(crate) fn rng(&self) -> &R {
+        match self {
+            Self::Random(rng) | Self::KmeansPlusPlus(rng) => rng,
+        }
+    }
+
+    /// Return an initializer of the same strategy with the given random number generator.
+    #[must_use]
+    pub(crate) fn with_rng(&self, rng: R) -> Self {
+        match self {
+            Self::Random(_) => Self::Random(rng),
+            Self::KmeansPlusPlus(_) => Self::KmeansPlusPlus(rng),
+        }
+    }
+
     pub(crate) fn initialize<F: Float, P: Point<F>>(
         &self,
         dataset: &[P],
@@ -83,14 +99,16 @@ where
                     let min_distance = centroids
                         .iter()
                         .map(|centroid| metric.measure(point, centroid))
-                        .min_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Greater));
+                        .min_by(|a, b| a.total_order(b));
                     if let Some(min) = min_distance {
                         (index, min)
                     } else {
                         (index, F::zero())
                     }
                 })
-                .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Greater));
+                // Skip NaN distances, which would otherwise be selected as the furthest.
+                .filter(|(_, distance)| !distance.is_nan())
+                .max_by(|(_, a), (_, b)| a.total_order(b));
 
             if let Some((index, _)) = furthest {
                 selected.insert(index, true);
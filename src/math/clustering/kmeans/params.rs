@@ -5,7 +5,7 @@ use rand::Rng;
 
 /// A struct representing the parameters of Kmeans.
 #[derive(Clone, Debug, PartialEq)]
-pub(crate) struct KmeansParams<F, R>
+pub struct KmeansParams<F, R>
 where
     F: Float,
     R: Rng + Clone,
@@ -42,6 +42,11 @@ where
         self
     }
 
+    pub fn with_initializer(mut self, initializer: Initializer<R>) -> Self {
+        self.initializer = initializer;
+        self
+    }
+
     pub fn k(&self) -> usize {
         self.k
     }
@@ -0,0 +1,227 @@
+use crate::math::distance::metric::DistanceMetric;
+use crate::math::number::Float;
+use crate::math::point::Point;
+use std::collections::{HashMap, HashSet};
+
+/// Compute the adjusted Rand index between two label assignments of the same points.
+///
+/// The index is 1 for identical partitions regardless of the label values, and around 0 for
+/// partitions agreeing no more than random ones. If both partitions are trivial, e.g. a single
+/// cluster, the index is 1.
+///
+/// [Rand index - Wikipedia](https://en.wikipedia.org/wiki/Rand_index#Adjusted_Rand_index)
+///
+/// # Panics
+/// Panics if the lengths of the label assignments differ.
+#[must_use]
+pub fn adjusted_rand_index<F: Float>(labels1: &[usize], labels2: &[usize]) -> F {
+    assert_eq!(
+        labels1.len(),
+        labels2.len(),
+        "The label assignments must have the same length"
+    );
+    let pairs = |count: usize| {
+        F::from_usize(count) * F::from_usize(count.saturating_sub(1)) / F::from_f64(2.0)
+    };
+
+    let mut contingency: HashMap<(usize, usize), usize> = HashMap::new();
+    let mut rows: HashMap<usize, usize> = HashMap::new();
+    let mut cols: HashMap<usize, usize> = HashMap::new();
+    for (&label1, &label2) in labels1.iter().zip(labels2) {
+        *contingency.entry((label1, label2)).or_default() += 1;
+        *rows.entry(label1).or_default() += 1;
+        *cols.entry(label2).or_default() += 1;
+    }
+
+    let index = contingency
+        .values()
+        .fold(F::zero(), |total, &count| total + pairs(count));
+    let sum_rows = rows
+        .values()
+        .fold(F::zero(), |total, &count| total + pairs(count));
+    let sum_cols = cols
+        .values()
+        .fold(F::zero(), |total, &count| total + pairs(count));
+    let total_pairs = pairs(labels1.len());
+    if total_pairs.is_zero() {
+        return F::one();
+    }
+
+    let expected = sum_rows * sum_cols / total_pairs;
+    let max = (sum_rows + sum_cols) / F::from_f64(2.0);
+    if max == expected {
+        return F::one();
+    }
+    (index - expected) / (max - expected)
+}
+
+/// Compute the mean silhouette coefficient of the given label assignment of the points.
+///
+/// The coefficient of each point compares the mean distance to the other points of its cluster
+/// with the mean distance to the points of the nearest other cluster, ranging from -1 to 1 where
+/// higher is better. Points alone in their cluster have a coefficient of 0, and the score is 0 if
+/// there are fewer than 2 clusters.
+///
+/// The silhouette is defined on distances, and the coefficients change if the distances are
+/// squared, so [`DistanceMetric::SquaredEuclidean`] is evaluated as [`DistanceMetric::Euclidean`].
+/// Both metrics therefore give the same score and rank label assignments identically.
+///
+/// [Silhouette (clustering) - Wikipedia](https://en.wikipedia.org/wiki/Silhouette_(clustering))
+///
+/// # Panics
+/// Panics if the number of labels differs from the number of points.
+#[must_use]
+pub fn silhouette_score<F, P>(dataset: &[P], labels: &[usize], metric: &DistanceMetric) -> F
+where
+    F: Float,
+    P: Point<F>,
+{
+    assert_eq!(
+        dataset.len(),
+        labels.len(),
+        "Labels should be given for each point"
+    );
+    if labels.iter().collect::<HashSet<_>>().len() < 2 {
+        return F::zero();
+    }
+    let metric = match *metric {
+        DistanceMetric::SquaredEuclidean => DistanceMetric::Euclidean,
+        metric => metric,
+    };
+
+    let mut total = F::zero();
+    for (i, (point, &label)) in dataset.iter().zip(labels).enumerate() {
+        let mut sums: HashMap<usize, (F, usize)> = HashMap::new();
+        for (j, (other, &other_label)) in dataset.iter().zip(labels).enumerate() {
+            if i != j {
+                let (sum, count) = sums.entry(other_label).or_insert((F::zero(), 0));
+                *sum += metric.measure(point, other);
+                *count += 1;
+            }
+        }
+        let Some(&(sum, count)) = sums.get(&label) else {
+            continue;
+        };
+        let mean = |(sum, count): (F, usize)| sum / F::from_usize(count);
+        let intra = mean((sum, count));
+        let Some(inter) = sums
+            .iter()
+            .filter(|(&other_label, _)| other_label != label)
+            .map(|(_, &entry)| mean(entry))
+            .min_by(|distance1, distance2| distance1.total_order(distance2))
+        else {
+            continue;
+        };
+        let max = intra.max(inter);
+        if max > F::zero() {
+            total += (inter - intra) / max;
+        }
+    }
+
+    total / F::from_usize(dataset.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::point::Point2;
+
+    #[test]
+    fn adjusted_rand_index_should_be_one_for_identical_partitions() {
+        let labels1 = [0, 0, 1, 1, 2, 2];
+        let labels2 = [5, 5, 3, 3, 4, 4];
+        let index: f64 = adjusted_rand_index(&labels1, &labels2);
+        assert!((index - 1.0).abs() < 1e-12);
+
+        let index: f64 = adjusted_rand_index(&[0, 0, 0], &[1, 1, 1]);
+        assert_eq!(index, 1.0);
+    }
+
+    #[test]
+    fn adjusted_rand_index_should_handle_different_cluster_counts() {
+        let labels1 = [0, 0, 0, 1, 1, 1];
+        let labels2 = [0, 0, 1, 2, 3, 3];
+        let index: f64 = adjusted_rand_index(&labels1, &labels2);
+        assert!(index > 0.0 && index < 1.0);
+        assert_eq!(index, adjusted_rand_index::<f64>(&labels2, &labels1));
+    }
+
+    #[test]
+    fn adjusted_rand_index_should_be_near_zero_for_unrelated_partitions() {
+        // Every pair of labels occurs equally often, so the partitions are independent.
+        let labels1: Vec<usize> = (0..1000).map(|i| i % 4).collect();
+        let labels2: Vec<usize> = (0..1000).map(|i| (i / 4) % 5).collect();
+        let index: f64 = adjusted_rand_index(&labels1, &labels2);
+        assert!(index.abs() < 0.01);
+    }
+
+    #[test]
+    fn adjusted_rand_index_should_match_known_value() {
+        // The example from the documentation of scikit-learn.
+        let index: f64 = adjusted_rand_index(&[0, 0, 1, 2], &[0, 0, 1, 1]);
+        assert!((index - 0.571_428_571_428_571_4).abs() < 1e-12);
+
+        let index: f64 = adjusted_rand_index(&[0, 0, 1, 1], &[0, 1, 0, 1]);
+        assert!(index < 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn adjusted_rand_index_should_panic_if_lengths_differ() {
+        let _: f64 = adjusted_rand_index(&[0, 1], &[0]);
+    }
+
+    const POINTS: [Point2<f64>; 6] = [
+        Point2(0.0, 0.0),
+        Point2(0.0, 1.0),
+        Point2(1.0, 0.0),
+        Point2(5.0, 5.0),
+        Point2(5.0, 6.0),
+        Point2(6.0, 5.0),
+    ];
+
+    #[test]
+    fn silhouette_score_should_match_known_value() {
+        let labels = [0, 0, 0, 1, 1, 1];
+        let score: f64 = silhouette_score(&POINTS, &labels, &DistanceMetric::Euclidean);
+        assert!((score - 0.839_816_331_258_674_2).abs() < 1e-12);
+
+        let score: f64 = silhouette_score(&POINTS, &[0; 6], &DistanceMetric::Euclidean);
+        assert_eq!(score, 0.0);
+    }
+
+    #[test]
+    fn silhouette_score_should_rank_identically_with_squared_euclidean() {
+        let assignments = [
+            [0, 0, 0, 1, 1, 1],
+            [0, 0, 1, 1, 1, 1],
+            [0, 1, 0, 1, 0, 1],
+            [0, 1, 2, 3, 3, 3],
+        ];
+        let rank = |metric: DistanceMetric| -> Vec<usize> {
+            let scores: Vec<f64> = assignments
+                .iter()
+                .map(|labels| silhouette_score(&POINTS, labels, &metric))
+                .collect();
+            let mut order: Vec<usize> = (0..assignments.len()).collect();
+            order.sort_by(|&i, &j| scores[j].total_cmp(&scores[i]));
+            order
+        };
+        assert_eq!(
+            rank(DistanceMetric::Euclidean),
+            rank(DistanceMetric::SquaredEuclidean)
+        );
+        assert_eq!(rank(DistanceMetric::Euclidean)[0], 0);
+        for labels in &assignments {
+            let euclidean: f64 = silhouette_score(&POINTS, labels, &DistanceMetric::Euclidean);
+            let squared: f64 = silhouette_score(&POINTS, labels, &DistanceMetric::SquaredEuclidean);
+            assert_eq!(euclidean, squared);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Labels should be given for each point")]
+    fn silhouette_score_should_panic_if_lengths_differ() {
+        let _: f64 = silhouette_score(&POINTS, &[0, 1], &DistanceMetric::Euclidean);
+    }
+}
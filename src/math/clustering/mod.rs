@@ -1,5 +1,9 @@
 pub(crate) mod dbscan;
+// HDBSCAN is not finished yet, so nothing uses it.
+#[allow(dead_code)]
 pub(crate) mod hdbscan;
 pub(crate) mod hierarchical;
 pub(crate) mod kmeans;
+pub(crate) mod metrics;
+pub(crate) mod spectral;
 pub(crate) mod traits;
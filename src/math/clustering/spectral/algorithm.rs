@@ -0,0 +1,224 @@
+use crate::math::clustering::kmeans::algorithm::Kmeans;
+use crate::math::clustering::kmeans::params::KmeansParams;
+use crate::math::clustering::spectral::params::SpectralParams;
+use crate::math::clustering::traits::Fit;
+use crate::math::distance::metric::DistanceMetric;
+use crate::math::matrix::Matrix;
+use crate::math::neighbors::kdtree::KDTree;
+use crate::math::neighbors::nns::NeighborSearch;
+use crate::math::number::Float;
+use crate::math::point::{Point, Point5};
+use rand::Rng;
+use std::marker::PhantomData;
+
+/// Spectral clustering algorithm.
+///
+/// The dataset is embedded using the eigenvectors of the normalized Laplacian of a k-nearest
+/// neighbor graph, and then the embedding is clustered with k-means. This is able to find
+/// non-convex clusters, but the eigen decomposition is O(n^3), so it is intended for small datasets.
+/// The embedding has at most 5 dimensions, so no point is labeled if more than 5 clusters are
+/// requested.
+///
+/// [Spectral clustering - Wikipedia](https://en.wikipedia.org/wiki/Spectral_clustering)
+#[derive(Debug, Clone)]
+pub struct SpectralClustering<F, P>
+where
+    F: Float,
+    P: Point<F>,
+{
+    _t: PhantomData<(F, P)>,
+    labels: Vec<usize>,
+}
+
+impl<F, P> SpectralClustering<F, P>
+where
+    F: Float,
+    P: Point<F>,
+{
+    /// The maximum number of clusters supported by the spectral embedding.
+    const MAX_K: usize = 5;
+
+    /// Return the cluster index assigned to each point of the dataset.
+    #[must_use]
+    pub fn labels(&self) -> &[usize] {
+        &self.labels
+    }
+
+    fn affinity_matrix(dataset: &[P], n_neighbors: usize, metric: &DistanceMetric) -> Matrix<F> {
+        let n = dataset.len();
+        let nns = KDTree::new(dataset, metric);
+        let half = F::from_f64(0.5);
+        let mut affinity = Matrix::zeros(n, n);
+        for (index, point) in dataset.iter().enumerate() {
+            // The query point itself is included in the result.
+            for neighbor in nns.search(point, n_neighbors + 1) {
+                if neighbor.index == index {
+                    continue;
+                }
+                affinity[(index, neighbor.index)] += half;
+                affinity[(neighbor.index, index)] += half;
+            }
+        }
+        affinity
+    }
+
+    fn normalized_laplacian(affinity: &Matrix<F>) -> Matrix<F> {
+        let n = affinity.rows();
+        let inv_sqrt_degrees: Vec<F> = (0..n)
+            .map(|row| {
+                let degree = (0..n).fold(F::zero(), |total, col| total + affinity[(row, col)]);
+                if degree.is_zero() {
+                    F::zero()
+                } else {
+                    F::one() / degree.sqrt()
+                }
+            })
+            .collect();
+
+        let mut laplacian = Matrix::identity(n);
+        for row in 0..n {
+            for col in 0..n {
+                laplacian[(row, col)] -=
+                    inv_sqrt_degrees[row] * affinity[(row, col)] * inv_sqrt_degrees[col];
+            }
+        }
+        laplacian
+    }
+
+    fn embed(laplacian: &Matrix<F>, k: usize) -> Vec<Point5<F>> {
+        let n = laplacian.rows();
+        let (_, eigenvectors) = laplacian.symmetric_eigen();
+        (0..n)
+            .map(|row| {
+                let mut values = [F::zero(); 5];
+                for (col, value) in values.iter_mut().enumerate().take(k) {
+                    *value = eigenvectors[(row, col)];
+                }
+                let norm = values
+                    .iter()
+                    .fold(F::zero(), |total, value| total + value.powi(2))
+                    .sqrt();
+                let point = Point5::new(values[0], values[1], values[2], values[3], values[4]);
+                if norm.is_zero() {
+                    point
+                } else {
+                    point / norm
+                }
+            })
+            .collect()
+    }
+}
+
+impl<F, P, R> Fit<F, P, SpectralParams<R>> for SpectralClustering<F, P>
+where
+    F: Float,
+    P: Point<F>,
+    R: Rng + Clone,
+{
+    fn fit(dataset: &[P], params: &SpectralParams<R>) -> Self {
+        let k = params.k();
+        if k == 0 || k > Self::MAX_K || dataset.is_empty() {
+            return Self {
+                _t: PhantomData,
+                labels: Vec::new(),
+            };
+        }
+
+        if k >= dataset.len() {
+            return Self {
+                _t: PhantomData,
+                labels: (0..dataset.len()).collect(),
+            };
+        }
+
+        let affinity = Self::affinity_matrix(dataset, params.n_neighbors(), params.metric());
+        let laplacian = Self::normalized_laplacian(&affinity);
+        let embedding = Self::embed(&laplacian, k);
+
+        let kmeans_params = KmeansParams::new(
+            k,
+            DistanceMetric::SquaredEuclidean,
+            params.initializer().clone(),
+        );
+        let kmeans = Kmeans::fit(&embedding, &kmeans_params);
+        Self {
+            _t: PhantomData,
+            labels: kmeans.labels(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::clustering::kmeans::init::Initializer;
+    use crate::math::point::Point2;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use std::f64::consts::PI;
+
+    fn half_moons(n: usize) -> (Vec<Point2<f64>>, Vec<usize>) {
+        let mut dataset = Vec::with_capacity(n * 2);
+        let mut expected = Vec::with_capacity(n * 2);
+        for i in 0..n {
+            let t = PI * i as f64 / (n - 1) as f64;
+            dataset.push(Point2(t.cos(), t.sin()));
+            expected.push(0);
+            dataset.push(Point2(1.0 - t.cos(), 0.5 - t.sin()));
+            expected.push(1);
+        }
+        (dataset, expected)
+    }
+
+    fn is_same_partition(labels: &[usize], expected: &[usize]) -> bool {
+        labels.iter().zip(expected).all(|(label, expected_label)| {
+            let first = labels
+                .iter()
+                .zip(expected)
+                .find(|(_, e)| *e == expected_label)
+                .map(|(l, _)| l);
+            first == Some(label)
+        }) && labels[0] != labels[1]
+    }
+
+    #[test]
+    fn fit_should_separate_half_moons() {
+        let (dataset, expected) = half_moons(40);
+        let initializer = Initializer::KmeansPlusPlus(StdRng::seed_from_u64(7));
+
+        let params = SpectralParams::new(2, 6, DistanceMetric::Euclidean, initializer.clone());
+        let spectral = SpectralClustering::fit(&dataset, &params);
+        assert_eq!(spectral.labels().len(), 80);
+        assert!(is_same_partition(spectral.labels(), &expected));
+
+        let kmeans_params = KmeansParams::new(2, DistanceMetric::SquaredEuclidean, initializer);
+        let kmeans = Kmeans::fit(&dataset, &kmeans_params);
+        assert!(!is_same_partition(&kmeans.labels(), &expected));
+    }
+
+    #[test]
+    fn fit_should_return_empty_labels_if_dataset_is_empty() {
+        let dataset: Vec<Point2<f64>> = Vec::new();
+        let params = SpectralParams::new(
+            2,
+            6,
+            DistanceMetric::Euclidean,
+            Initializer::Random(StdRng::seed_from_u64(0)),
+        );
+        let spectral = SpectralClustering::fit(&dataset, &params);
+        assert!(spectral.labels().is_empty());
+    }
+
+    #[test]
+    fn fit_should_return_empty_labels_if_k_exceeds_embedding() {
+        let (dataset, _) = half_moons(10);
+        let params = SpectralParams::new(
+            6,
+            4,
+            DistanceMetric::Euclidean,
+            Initializer::Random(StdRng::seed_from_u64(0)),
+        );
+        let spectral = SpectralClustering::fit(&dataset, &params);
+        assert!(spectral.labels().is_empty());
+    }
+}
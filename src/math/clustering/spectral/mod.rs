@@ -0,0 +1,2 @@
+pub(crate) mod algorithm;
+pub(crate) mod params;
@@ -0,0 +1,76 @@
+use crate::math::clustering::kmeans::init::Initializer;
+use crate::math::distance::metric::DistanceMetric;
+use rand::Rng;
+
+/// Parameters of spectral clustering algorithm.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpectralParams<R>
+where
+    R: Rng + Clone,
+{
+    k: usize,
+    n_neighbors: usize,
+    metric: DistanceMetric,
+    initializer: Initializer<R>,
+}
+
+impl<R> SpectralParams<R>
+where
+    R: Rng + Clone,
+{
+    /// Create a new SpectralParams.
+    ///
+    /// # Arguments
+    /// * k - The number of clusters, up to 5.
+    /// * n_neighbors - The number of nearest neighbors used to build the affinity graph.
+    /// * metric - The distance metric used to find nearest neighbors.
+    /// * initializer - The initializer of k-means applied to the spectral embedding.
+    pub fn new(
+        k: usize,
+        n_neighbors: usize,
+        metric: DistanceMetric,
+        initializer: Initializer<R>,
+    ) -> Self {
+        Self {
+            k,
+            n_neighbors,
+            metric,
+            initializer,
+        }
+    }
+
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    pub fn n_neighbors(&self) -> usize {
+        self.n_neighbors
+    }
+
+    pub fn metric(&self) -> &DistanceMetric {
+        &self.metric
+    }
+
+    pub fn initializer(&self) -> &Initializer<R> {
+        &self.initializer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    #[test]
+    fn new_should_create_params() {
+        let params = SpectralParams::new(
+            3,
+            8,
+            DistanceMetric::Euclidean,
+            Initializer::KmeansPlusPlus(thread_rng()),
+        );
+        assert_eq!(params.k(), 3);
+        assert_eq!(params.n_neighbors(), 8);
+        assert_eq!(params.metric(), &DistanceMetric::Euclidean);
+    }
+}
@@ -1,11 +1,13 @@
 use crate::math::number::Float;
 use crate::math::point::Point;
 
-pub(crate) trait Fit<F, P, T>
+/// Trait for clustering algorithms fitted to a dataset with parameters.
+pub trait Fit<F, P, T>
 where
     F: Float,
     P: Point<F>,
 {
+    /// Fit the algorithm to the given dataset.
     #[must_use]
     fn fit(dataset: &[P], params: &T) -> Self;
 }
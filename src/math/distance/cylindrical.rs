@@ -0,0 +1,181 @@
+use crate::math::distance::measure::DistanceMeasure;
+use crate::math::number::Float;
+use crate::math::point::Point;
+
+/// Euclidean distance measure over cylindrical coordinates such as CIE LCh.
+///
+/// The hue axis holds an angle in degrees and is measured along the shortest arc, so hues of 359°
+/// and 1° are 2° apart. The other axes, such as the lightness and the chroma, are measured linearly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CylindricalDistance {
+    hue_axis: usize,
+}
+
+impl CylindricalDistance {
+    /// The period of the hue angle in degrees.
+    const FULL_TURN: f64 = 360.0;
+
+    /// Create a distance measure for points of lightness, chroma and hue in this order.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { hue_axis: 2 }
+    }
+
+    /// Set the axis holding the hue angle.
+    #[must_use]
+    pub fn with_hue_axis(mut self, hue_axis: usize) -> Self {
+        self.hue_axis = hue_axis;
+        self
+    }
+
+    /// Return the axis holding the hue angle.
+    #[must_use]
+    pub fn hue_axis(&self) -> usize {
+        self.hue_axis
+    }
+
+    /// Return the difference of the given hue angles along the shortest arc, from 0° to 180°.
+    #[inline]
+    #[must_use]
+    fn hue_delta<F: Float>(hue1: F, hue2: F) -> F {
+        let full_turn = F::from_f64(Self::FULL_TURN);
+        let delta = (hue1 - hue2).abs() % full_turn;
+        delta.min(full_turn - delta)
+    }
+
+    /// Return the difference of the given hue angle to the nearest hue angle in `[lower, upper]`.
+    ///
+    /// The range is wrapped around the hue circle, so a hue of 370° lies within `[5, 15]`.
+    #[inline]
+    #[must_use]
+    fn hue_bound<F: Float>(hue: F, lower: F, upper: F) -> F {
+        let full_turn = F::from_f64(Self::FULL_TURN);
+        if upper - lower >= full_turn {
+            return F::zero();
+        }
+        let offset = ((hue - lower) % full_turn + full_turn) % full_turn;
+        if offset <= upper - lower {
+            return F::zero();
+        }
+        Self::hue_delta(hue, lower).min(Self::hue_delta(hue, upper))
+    }
+
+    /// Compute the minimum distance from the query point to the given axis-aligned box.
+    ///
+    /// This is a lower bound of the distance to any point within the box, and is used to prune
+    /// subtrees during neighbor search.
+    #[must_use]
+    pub(crate) fn measure_bounds<F: Float, P: Point<F>>(
+        &self,
+        query: &P,
+        lower: &[F],
+        upper: &[F],
+    ) -> F {
+        (0..query.dim())
+            .fold(F::zero(), |total, axis| {
+                let delta = if axis == self.hue_axis {
+                    Self::hue_bound(query[axis], lower[axis], upper[axis])
+                } else {
+                    (lower[axis] - query[axis])
+                        .max(query[axis] - upper[axis])
+                        .max(F::zero())
+                };
+                total + delta.powi(2)
+            })
+            .sqrt()
+    }
+}
+
+impl Default for CylindricalDistance {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F, P> DistanceMeasure<F, P> for CylindricalDistance
+where
+    F: Float,
+    P: Point<F>,
+{
+    fn measure(&self, lhs: &P, rhs: &P) -> F {
+        (0..lhs.dim())
+            .fold(F::zero(), |total, axis| {
+                let delta = if axis == self.hue_axis {
+                    Self::hue_delta(lhs[axis], rhs[axis])
+                } else {
+                    lhs[axis] - rhs[axis]
+                };
+                total + delta.powi(2)
+            })
+            .sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::point::Point3;
+
+    #[test]
+    fn measure_should_wrap_hue_around() {
+        let distance = CylindricalDistance::new();
+        let actual: f64 = distance.measure(
+            &Point3::new(50.0, 30.0, 359.0),
+            &Point3::new(50.0, 30.0, 1.0),
+        );
+        assert!((actual - 2.0).abs() < 1e-12);
+
+        let actual: f64 = distance.measure(
+            &Point3::new(50.0, 30.0, 90.0),
+            &Point3::new(50.0, 30.0, 270.0),
+        );
+        assert!((actual - 180.0).abs() < 1e-12);
+
+        let actual: f64 = distance.measure(
+            &Point3::new(50.0, 30.0, 10.0),
+            &Point3::new(50.0, 30.0, 730.0),
+        );
+        assert!(actual.abs() < 1e-12);
+    }
+
+    #[test]
+    fn measure_should_treat_other_axes_linearly() {
+        let distance = CylindricalDistance::new();
+        let actual: f64 = distance.measure(
+            &Point3::new(50.0, 30.0, 358.0),
+            &Point3::new(53.0, 34.0, 0.0),
+        );
+        assert!((actual - 29.0_f64.sqrt()).abs() < 1e-12);
+
+        let distance = CylindricalDistance::new().with_hue_axis(0);
+        assert_eq!(distance.hue_axis(), 0);
+        let actual: f64 = distance.measure(
+            &Point3::new(350.0, 50.0, 30.0),
+            &Point3::new(10.0, 50.0, 30.0),
+        );
+        assert!((actual - 20.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn measure_bounds_should_wrap_hue_range_around() {
+        let distance = CylindricalDistance::new();
+        let lower = [40.0, 20.0, 5.0];
+        let upper = [60.0, 40.0, 15.0];
+
+        let actual: f64 = distance.measure_bounds(&Point3::new(50.0, 30.0, 355.0), &lower, &upper);
+        assert!((actual - 10.0).abs() < 1e-12);
+
+        let actual: f64 = distance.measure_bounds(&Point3::new(50.0, 30.0, 370.0), &lower, &upper);
+        assert!(actual.abs() < 1e-12);
+
+        let actual: f64 = distance.measure_bounds(&Point3::new(63.0, 44.0, 180.0), &lower, &upper);
+        assert!((actual - (9.0_f64 + 16.0 + 165.0 * 165.0).sqrt()).abs() < 1e-12);
+
+        let actual: f64 = distance.measure_bounds(
+            &Point3::new(50.0, 30.0, 180.0),
+            &lower,
+            &[60.0, 40.0, 400.0],
+        );
+        assert!(actual.abs() < 1e-12);
+    }
+}
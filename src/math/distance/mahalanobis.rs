@@ -0,0 +1,153 @@
+use crate::math::distance::measure::DistanceMeasure;
+use crate::math::matrix::Matrix;
+use crate::math::number::Float;
+use crate::math::point::Point;
+
+/// Mahalanobis distance, which accounts for the correlation between the axes of a dataset.
+///
+/// [Mahalanobis distance - Wikipedia](https://en.wikipedia.org/wiki/Mahalanobis_distance)
+#[derive(Debug, Clone, PartialEq)]
+pub struct MahalanobisDistance<F: Float> {
+    inverse: Matrix<F>,
+}
+
+impl<F> MahalanobisDistance<F>
+where
+    F: Float,
+{
+    /// Create a Mahalanobis distance from the given inverse covariance matrix.
+    ///
+    /// # Panics
+    /// Panics if the given matrix is not square.
+    #[must_use]
+    pub(crate) fn new(inverse: Matrix<F>) -> Self {
+        assert_eq!(inverse.rows(), inverse.cols(), "Matrix must be square");
+        Self { inverse }
+    }
+
+    /// Create a Mahalanobis distance from the covariance matrix estimated from the given dataset.
+    ///
+    /// The covariance matrix is inverted by eigendecomposition, and the directions without any
+    /// variance are ignored, so that a degenerate dataset yields the pseudo-inverse.
+    ///
+    /// # Panics
+    /// Panics if the given dataset is empty.
+    #[must_use]
+    pub fn from_dataset<P: Point<F>>(dataset: &[P]) -> Self {
+        let mean = P::centroid(dataset).expect("Dataset must not be empty");
+        let dim = mean.dim();
+        let count = F::from_usize(dataset.len());
+
+        let mut covariance = Matrix::zeros(dim, dim);
+        for point in dataset {
+            for row in 0..dim {
+                for col in 0..dim {
+                    covariance[(row, col)] += (point[row] - mean[row]) * (point[col] - mean[col]);
+                }
+            }
+        }
+        for row in 0..dim {
+            for col in 0..dim {
+                covariance[(row, col)] /= count;
+            }
+        }
+
+        let (eigenvalues, eigenvectors) = covariance.symmetric_eigen();
+        let threshold = eigenvalues
+            .iter()
+            .fold(F::zero(), |max, &value| max.max(value))
+            * F::epsilon()
+            * F::from_usize(dim);
+        let mut inverse = Matrix::zeros(dim, dim);
+        for (k, &lambda) in eigenvalues.iter().enumerate() {
+            if lambda <= threshold {
+                continue;
+            }
+            for row in 0..dim {
+                for col in 0..dim {
+                    inverse[(row, col)] += eigenvectors[(row, k)] * eigenvectors[(col, k)] / lambda;
+                }
+            }
+        }
+        Self::new(inverse)
+    }
+}
+
+impl<F, P> DistanceMeasure<F, P> for MahalanobisDistance<F>
+where
+    F: Float,
+    P: Point<F>,
+{
+    /// Compute `sqrt((lhs - rhs)^T * inverse * (lhs - rhs))`.
+    ///
+    /// # Panics
+    /// Panics if the dimension of the points differs from the size of the matrix.
+    fn measure(&self, lhs: &P, rhs: &P) -> F {
+        let dim = self.inverse.rows();
+        assert_eq!(lhs.dim(), dim, "Point dimension must match the matrix");
+        let diff = *lhs - *rhs;
+        let mut squared = F::zero();
+        for row in 0..dim {
+            for col in 0..dim {
+                squared += diff[row] * self.inverse[(row, col)] * diff[col];
+            }
+        }
+        squared.max(F::zero()).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::distance::metric::DistanceMetric;
+    use crate::math::point::Point2;
+
+    #[test]
+    fn new_should_measure_with_identity_as_euclidean() {
+        let distance = MahalanobisDistance::<f64>::new(Matrix::identity(2));
+        let actual: f64 = distance.measure(&Point2(0.0, 0.0), &Point2(3.0, 4.0));
+        assert!((actual - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn from_dataset_should_account_for_correlation() {
+        // Points spread along the diagonal y = x with a small orthogonal noise.
+        let dataset: Vec<Point2<f64>> = (0..100)
+            .map(|i| {
+                let t = f64::from(i) / 10.0 - 5.0;
+                let noise = if i % 2 == 0 { 0.1 } else { -0.1 };
+                Point2(t + noise, t - noise)
+            })
+            .collect();
+        let distance = MahalanobisDistance::from_dataset(&dataset);
+
+        let origin = Point2(0.0, 0.0);
+        let along = Point2(1.0, 1.0);
+        let across = Point2(1.0, -1.0);
+        let euclidean_along: f64 = DistanceMetric::Euclidean.measure(&origin, &along);
+        let euclidean_across: f64 = DistanceMetric::Euclidean.measure(&origin, &across);
+        assert!((euclidean_along - euclidean_across).abs() < 1e-12);
+
+        // Moving along the correlated axis is cheap, and moving across it is expensive.
+        let mahalanobis_along: f64 = distance.measure(&origin, &along);
+        let mahalanobis_across: f64 = distance.measure(&origin, &across);
+        assert!(mahalanobis_along < euclidean_along);
+        assert!(mahalanobis_across > 10.0 * mahalanobis_along);
+    }
+
+    #[test]
+    fn from_dataset_should_ignore_degenerate_axes() {
+        let dataset = [Point2(0.0, 1.0), Point2(1.0, 1.0), Point2(2.0, 1.0)];
+        let distance = MahalanobisDistance::<f64>::from_dataset(&dataset);
+        let actual: f64 = distance.measure(&Point2(0.0, 1.0), &Point2(0.0, 5.0));
+        assert!(actual.abs() < 1e-9);
+        let actual: f64 = distance.measure(&Point2(0.0, 1.0), &Point2(1.0, 1.0));
+        assert!(actual > 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_dataset_should_panic_if_empty() {
+        let _ = MahalanobisDistance::<f64>::from_dataset::<Point2<f64>>(&[]);
+    }
+}
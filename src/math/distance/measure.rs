@@ -0,0 +1,110 @@
+use crate::math::distance::metric::DistanceMetric;
+use crate::math::number::Float;
+use crate::math::point::Point;
+
+/// Trait for measuring the distance between points.
+pub trait DistanceMeasure<F: Float, P: Point<F>> {
+    /// Compute the distance between two points.
+    #[must_use]
+    fn measure(&self, lhs: &P, rhs: &P) -> F;
+
+    /// Compute the distances from the query point to each of the given points.
+    ///
+    /// # Panics
+    /// Panics if the length of `out` differs from the number of points.
+    fn measure_batch(&self, query: &P, points: &[P], out: &mut [F]) {
+        assert_eq!(
+            points.len(),
+            out.len(),
+            "The output length must match the number of points"
+        );
+        for (point, distance) in points.iter().zip(out.iter_mut()) {
+            *distance = self.measure(query, point);
+        }
+    }
+}
+
+impl<F, P> DistanceMeasure<F, P> for DistanceMetric
+where
+    F: Float,
+    P: Point<F>,
+{
+    #[inline]
+    fn measure(&self, lhs: &P, rhs: &P) -> F {
+        DistanceMetric::measure(self, lhs, rhs)
+    }
+
+    fn measure_batch(&self, query: &P, points: &[P], out: &mut [F]) {
+        assert_eq!(
+            points.len(),
+            out.len(),
+            "The output length must match the number of points"
+        );
+        let is_squared = match *self {
+            DistanceMetric::Euclidean => false,
+            DistanceMetric::SquaredEuclidean => true,
+            DistanceMetric::Manhattan
+            | DistanceMetric::Chebyshev
+            | DistanceMetric::CIEDE2000
+            | DistanceMetric::Cylindrical => {
+                for (point, distance) in points.iter().zip(out.iter_mut()) {
+                    *distance = DistanceMetric::measure(self, query, point);
+                }
+                return;
+            }
+        };
+
+        // Index the coordinates directly to avoid allocating a vector for each point.
+        let dim = query.dim();
+        for (point, distance) in points.iter().zip(out.iter_mut()) {
+            let squared = (0..dim).fold(F::zero(), |total, axis| {
+                total + (query[axis] - point[axis]).powi(2)
+            });
+            *distance = if is_squared { squared } else { squared.sqrt() };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::point::Point3;
+
+    #[test]
+    fn measure_batch_should_match_measure() {
+        let query = Point3(1.0, 2.0, 3.0);
+        let points = [
+            Point3(0.0, 0.0, 0.0),
+            Point3(1.0, 2.0, 3.0),
+            Point3(-4.0, 5.5, 0.25),
+            Point3(10.0, -2.0, 8.0),
+        ];
+        for metric in [
+            DistanceMetric::Euclidean,
+            DistanceMetric::SquaredEuclidean,
+            DistanceMetric::Manhattan,
+            DistanceMetric::Chebyshev,
+            DistanceMetric::CIEDE2000,
+            DistanceMetric::Cylindrical,
+        ] {
+            let mut out = [0.0_f64; 4];
+            DistanceMeasure::measure_batch(&metric, &query, &points, &mut out);
+            for (point, actual) in points.iter().zip(out) {
+                let expected: f64 = DistanceMeasure::measure(&metric, &query, point);
+                assert!((actual - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn measure_batch_should_panic_if_output_length_differs() {
+        let mut out = [0.0_f64; 1];
+        DistanceMeasure::measure_batch(
+            &DistanceMetric::Euclidean,
+            &Point3(0.0, 0.0, 0.0),
+            &[Point3(1.0, 0.0, 0.0), Point3(0.0, 1.0, 0.0)],
+            &mut out,
+        );
+    }
+}
@@ -1,33 +1,259 @@
+use crate::color::difference::ciede2000;
+use crate::color::lab::Lab;
+use crate::math::distance::cylindrical::CylindricalDistance;
+use crate::math::distance::measure::DistanceMeasure;
 use crate::math::number::Float;
 use crate::math::point::Point;
+use alloc::string::{String, ToString};
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
 
 /// Distance metric enumerated type.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum DistanceMetric {
     /// Euclidean distance measure.
+    #[default]
     Euclidean,
     /// Squared euclidean distance measure.
     SquaredEuclidean,
+    /// Manhattan distance measure.
+    Manhattan,
+    /// Chebyshev distance measure.
+    Chebyshev,
+    /// CIEDE2000 color difference measure.
+    ///
+    /// This is only meaningful for 3-dimensional points holding the L*, a* and b* values of colors
+    /// in CIE L*a*b* color space without normalization.
+    #[allow(clippy::upper_case_acronyms)]
+    CIEDE2000,
+    /// Euclidean distance measure over cylindrical coordinates.
+    ///
+    /// This is only meaningful for 3-dimensional points holding the lightness, chroma and hue of
+    /// colors in CIE LCh color space, as the hue is an angle in degrees measured along the shortest
+    /// arc by [`CylindricalDistance`].
+    Cylindrical,
 }
 
 impl DistanceMetric {
     /// Compute the distance between two points.
+    ///
+    /// # Panics
+    /// Panics if the metric is [`DistanceMetric::CIEDE2000`] and the points are not 3-dimensional.
     pub fn measure<F: Float, P: Point<F>>(&self, lhs: &P, rhs: &P) -> F {
         match *self {
+            DistanceMetric::CIEDE2000 => {
+                assert_eq!(lhs.dim(), 3, "CIEDE2000 requires 3-dimensional Lab points");
+                let lab1: Lab<F> = Lab::new(lhs[0], lhs[1], lhs[2]);
+                let lab2: Lab<F> = Lab::new(rhs[0], rhs[1], rhs[2]);
+                ciede2000(&lab1, &lab2)
+            }
+            DistanceMetric::Cylindrical => CylindricalDistance::new().measure(lhs, rhs),
             DistanceMetric::Euclidean => DistanceMetric::SquaredEuclidean.measure(lhs, rhs).sqrt(),
-            DistanceMetric::SquaredEuclidean => lhs
-                .sub(*rhs)
-                .to_vec()
-                .iter()
-                .fold(F::zero(), |total, delta| total + delta.powi(2)),
+            DistanceMetric::SquaredEuclidean => {
+                Self::fold_deltas(lhs, rhs, |total, delta| total + delta.powi(2))
+            }
+            DistanceMetric::Manhattan => {
+                Self::fold_deltas(lhs, rhs, |total, delta| total + delta.abs())
+            }
+            DistanceMetric::Chebyshev => {
+                Self::fold_deltas(lhs, rhs, |max, delta| max.max(delta.abs()))
+            }
         }
     }
+
+    /// Return the metric ordering distances identically to this metric at a lower cost.
+    ///
+    /// Euclidean distances are compared by their squares to skip the square root, so neighbor
+    /// search only takes the root of the distances it returns.
+    #[must_use]
+    pub fn comparison_metric(&self) -> DistanceMetric {
+        match *self {
+            DistanceMetric::Euclidean => DistanceMetric::SquaredEuclidean,
+            metric => metric,
+        }
+    }
+
+    /// Convert a distance of the comparison metric into a distance of this metric.
+    #[inline]
+    #[must_use]
+    pub fn from_comparison<F: Float>(&self, distance: F) -> F {
+        match *self {
+            DistanceMetric::Euclidean => distance.sqrt(),
+            _ => distance,
+        }
+    }
+
+    /// Return an upper bound of the comparison distance of any point within the given radius.
+    ///
+    /// The squared radius is widened by a few units in the last place to absorb rounding, so a
+    /// point passing this bound must still be checked against the radius itself.
+    #[cfg(feature = "std")]
+    #[inline]
+    #[must_use]
+    pub(crate) fn comparison_bound<F: Float>(&self, radius: F) -> F {
+        match *self {
+            DistanceMetric::Euclidean => {
+                radius.powi(2) * (F::one() + F::from_f64(8.0) * F::epsilon())
+            }
+            _ => radius,
+        }
+    }
+
+    /// Fold the differences of the coordinates of two points without allocation.
+    #[inline]
+    fn fold_deltas<F: Float, P: Point<F>>(lhs: &P, rhs: &P, f: impl Fn(F, F) -> F) -> F {
+        (0..lhs.dim()).fold(F::zero(), |total, axis| f(total, lhs[axis] - rhs[axis]))
+    }
+
+    /// Compute the distance contributed by the given difference along a single axis.
+    ///
+    /// This is a lower bound of the distance between two points whose coordinates differ by `delta`
+    /// on the axis, and is used to prune branches during neighbor search. CIEDE2000 and cylindrical
+    /// distances have no such bound along an unknown axis, so zero is returned and no branch is
+    /// pruned.
+    pub fn measure_axis<F: Float>(&self, delta: F) -> F {
+        match *self {
+            DistanceMetric::CIEDE2000 | DistanceMetric::Cylindrical => F::zero(),
+            DistanceMetric::Euclidean | DistanceMetric::Manhattan | DistanceMetric::Chebyshev => {
+                delta.abs()
+            }
+            DistanceMetric::SquaredEuclidean => delta.powi(2),
+        }
+    }
+
+    /// Compute the minimum distance from the query point to the given axis-aligned box.
+    ///
+    /// This is a lower bound of the distance to any point within the box, and is used to prune
+    /// subtrees during neighbor search. CIEDE2000 has no such bound, so zero is returned.
+    pub fn measure_bounds<F: Float, P: Point<F>>(&self, query: &P, lower: &[F], upper: &[F]) -> F {
+        let deltas = (0..query.dim()).map(|axis| {
+            (lower[axis] - query[axis])
+                .max(query[axis] - upper[axis])
+                .max(F::zero())
+        });
+        match *self {
+            DistanceMetric::CIEDE2000 => F::zero(),
+            DistanceMetric::Cylindrical => {
+                CylindricalDistance::new().measure_bounds(query, lower, upper)
+            }
+            DistanceMetric::Euclidean => DistanceMetric::SquaredEuclidean
+                .measure_bounds(query, lower, upper)
+                .sqrt(),
+            DistanceMetric::SquaredEuclidean => {
+                deltas.fold(F::zero(), |total, delta| total + delta.powi(2))
+            }
+            DistanceMetric::Manhattan => deltas.fold(F::zero(), |total, delta| total + delta),
+            DistanceMetric::Chebyshev => deltas.fold(F::zero(), |max, delta| max.max(delta)),
+        }
+    }
+}
+
+/// Error returned when parsing an unknown distance metric name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDistanceMetricError {
+    name: String,
+}
+
+impl Display for ParseDistanceMetricError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Unknown distance metric '{}', expected one of 'euclidean', 'squared_euclidean', \
+             'manhattan', 'chebyshev', 'ciede2000' or 'cylindrical'",
+            self.name
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseDistanceMetricError {}
+
+impl TryFrom<&str> for DistanceMetric {
+    type Error = ParseDistanceMetricError;
+
+    /// Parse a distance metric from its snake case name ignoring ASCII case.
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        [
+            ("euclidean", DistanceMetric::Euclidean),
+            ("squared_euclidean", DistanceMetric::SquaredEuclidean),
+            ("manhattan", DistanceMetric::Manhattan),
+            ("chebyshev", DistanceMetric::Chebyshev),
+            ("ciede2000", DistanceMetric::CIEDE2000),
+            ("cylindrical", DistanceMetric::Cylindrical),
+        ]
+        .into_iter()
+        .find(|(candidate, _)| candidate.eq_ignore_ascii_case(name))
+        .map(|(_, metric)| metric)
+        .ok_or_else(|| ParseDistanceMetricError {
+            name: name.to_string(),
+        })
+    }
+}
+
+impl FromStr for DistanceMetric {
+    type Err = ParseDistanceMetricError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        DistanceMetric::try_from(name)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::math::point::{Point2, Point3};
+    use crate::math::point::{Point2, Point3, Point5};
+
+    #[test]
+    fn measure_should_match_allocating_implementation_on_point5() {
+        // The previous implementation which collected the differences into a vector.
+        let reference = |metric: DistanceMetric, lhs: &Point5<f64>, rhs: &Point5<f64>| -> f64 {
+            let deltas = (*lhs - *rhs).to_vec();
+            match metric {
+                DistanceMetric::Euclidean => deltas
+                    .iter()
+                    .fold(0.0, |total, delta| total + delta.powi(2))
+                    .sqrt(),
+                DistanceMetric::SquaredEuclidean => deltas
+                    .iter()
+                    .fold(0.0, |total, delta| total + delta.powi(2)),
+                DistanceMetric::Manhattan => {
+                    deltas.iter().fold(0.0, |total, delta| total + delta.abs())
+                }
+                DistanceMetric::Chebyshev => deltas
+                    .iter()
+                    .fold(0.0, |max: f64, delta| max.max(delta.abs())),
+                DistanceMetric::CIEDE2000 | DistanceMetric::Cylindrical => unreachable!(),
+            }
+        };
+
+        let mut seed: u64 = 7;
+        let mut random = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 11) as f64 / (1_u64 << 53) as f64 * 2.0 - 1.0
+        };
+        let points: Vec<Point5<f64>> = (0..64)
+            .map(|_| Point5::new(random(), random(), random(), random(), random()))
+            .collect();
+        for metric in [
+            DistanceMetric::Euclidean,
+            DistanceMetric::SquaredEuclidean,
+            DistanceMetric::Manhattan,
+            DistanceMetric::Chebyshev,
+        ] {
+            for pair in points.windows(2) {
+                let expected = reference(metric, &pair[0], &pair[1]);
+                let actual: f64 = metric.measure(&pair[0], &pair[1]);
+                assert_eq!(actual.to_bits(), expected.to_bits());
+            }
+        }
+    }
+
+    #[test]
+    fn default_should_return_euclidean() {
+        assert_eq!(DistanceMetric::default(), DistanceMetric::Euclidean);
+    }
 
     #[test]
     fn compute_should_compute_euclidean_distance() {
@@ -51,4 +277,146 @@ mod tests {
             3.0
         );
     }
+
+    #[test]
+    fn compute_should_compute_manhattan_distance() {
+        let metric = DistanceMetric::Manhattan;
+        assert_eq!(metric.measure(&Point2(0.0, 1.0), &Point2(1.0, 0.0)), 2.0);
+        assert_eq!(
+            metric.measure(&Point3(0.0, 1.0, 2.0), &Point3(1.0, 3.0, 5.0)),
+            6.0
+        );
+    }
+
+    #[test]
+    fn compute_should_compute_chebyshev_distance() {
+        let metric = DistanceMetric::Chebyshev;
+        assert_eq!(metric.measure(&Point2(0.0, 1.0), &Point2(1.0, 0.0)), 1.0);
+        assert_eq!(
+            metric.measure(&Point3(0.0, 1.0, 2.0), &Point3(1.0, 3.0, -1.0)),
+            3.0
+        );
+    }
+
+    #[test]
+    fn measure_bounds_should_compute_distance_to_box() {
+        let lower = [1.0, 1.0];
+        let upper = [3.0, 2.0];
+        let query = Point2(6.0, 6.0);
+        assert_eq!(
+            DistanceMetric::Euclidean.measure_bounds(&query, &lower, &upper),
+            5.0
+        );
+        assert_eq!(
+            DistanceMetric::SquaredEuclidean.measure_bounds(&query, &lower, &upper),
+            25.0
+        );
+        assert_eq!(
+            DistanceMetric::Manhattan.measure_bounds(&query, &lower, &upper),
+            7.0
+        );
+        assert_eq!(
+            DistanceMetric::Chebyshev.measure_bounds(&query, &lower, &upper),
+            4.0
+        );
+        assert_eq!(
+            DistanceMetric::Euclidean.measure_bounds(&Point2(2.0, 1.5), &lower, &upper),
+            0.0
+        );
+    }
+
+    #[test]
+    fn comparison_metric_should_order_distances_identically() {
+        let metric = DistanceMetric::Euclidean;
+        assert_eq!(metric.comparison_metric(), DistanceMetric::SquaredEuclidean);
+        let squared: f64 = metric
+            .comparison_metric()
+            .measure(&Point2(0.0, 0.0), &Point2(3.0, 4.0));
+        assert_eq!(squared, 25.0);
+        assert_eq!(metric.from_comparison(squared), 5.0);
+        assert!(metric.comparison_bound(5.0) >= 25.0);
+
+        for metric in [
+            DistanceMetric::SquaredEuclidean,
+            DistanceMetric::Manhattan,
+            DistanceMetric::Chebyshev,
+            DistanceMetric::CIEDE2000,
+            DistanceMetric::Cylindrical,
+        ] {
+            assert_eq!(metric.comparison_metric(), metric);
+            assert_eq!(metric.from_comparison(2.0), 2.0);
+            assert_eq!(metric.comparison_bound(2.0), 2.0);
+        }
+    }
+
+    #[test]
+    fn try_from_should_parse_metric_name() {
+        assert_eq!(
+            DistanceMetric::try_from("euclidean"),
+            Ok(DistanceMetric::Euclidean)
+        );
+        assert_eq!(
+            DistanceMetric::try_from("Squared_Euclidean"),
+            Ok(DistanceMetric::SquaredEuclidean)
+        );
+        assert_eq!(
+            DistanceMetric::try_from("MANHATTAN"),
+            Ok(DistanceMetric::Manhattan)
+        );
+        assert_eq!(
+            DistanceMetric::try_from("chebyshev"),
+            Ok(DistanceMetric::Chebyshev)
+        );
+        assert_eq!(
+            DistanceMetric::try_from("CIEDE2000"),
+            Ok(DistanceMetric::CIEDE2000)
+        );
+        assert_eq!(
+            DistanceMetric::try_from("cylindrical"),
+            Ok(DistanceMetric::Cylindrical)
+        );
+        assert_eq!("euclidean".parse(), Ok(DistanceMetric::Euclidean));
+    }
+
+    #[test]
+    fn try_from_should_return_error_if_name_is_unknown() {
+        let error = DistanceMetric::try_from("cosine").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown distance metric 'cosine', expected one of 'euclidean', 'squared_euclidean', \
+             'manhattan', 'chebyshev', 'ciede2000' or 'cylindrical'"
+        );
+        assert!("".parse::<DistanceMetric>().is_err());
+    }
+
+    #[test]
+    fn compute_should_compute_ciede2000_distance() {
+        let metric = DistanceMetric::CIEDE2000;
+        let distance: f64 = metric.measure(
+            &Point3(50.0, 2.6772, -79.7751),
+            &Point3(50.0, 0.0, -82.7485),
+        );
+        assert!((distance - 2.0425).abs() < 1e-4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn compute_should_panic_if_ciede2000_point_is_not_3d() {
+        let _: f64 = DistanceMetric::CIEDE2000.measure(&Point2(0.0, 1.0), &Point2(1.0, 0.0));
+    }
+
+    #[test]
+    fn measure_axis_should_compute_distance_along_axis() {
+        assert_eq!(DistanceMetric::Euclidean.measure_axis(-2.0), 2.0);
+        assert_eq!(DistanceMetric::SquaredEuclidean.measure_axis(-2.0), 4.0);
+        assert_eq!(DistanceMetric::CIEDE2000.measure_axis(-2.0), 0.0);
+        assert_eq!(DistanceMetric::Cylindrical.measure_axis(-2.0), 0.0);
+    }
+
+    #[test]
+    fn compute_should_compute_cylindrical_distance() {
+        let metric = DistanceMetric::Cylindrical;
+        let distance: f64 = metric.measure(&Point3(50.0, 30.0, 358.0), &Point3(53.0, 34.0, 0.0));
+        assert!((distance - 29.0_f64.sqrt()).abs() < 1e-12);
+    }
 }
@@ -1 +1,5 @@
+pub(crate) mod cylindrical;
+pub(crate) mod mahalanobis;
+pub(crate) mod measure;
 pub(crate) mod metric;
+pub(crate) mod projected;
@@ -0,0 +1,90 @@
+use crate::math::distance::measure::DistanceMeasure;
+use crate::math::number::Float;
+use crate::math::point::Point;
+use alloc::vec::Vec;
+use core::ops::IndexMut;
+
+/// Distance measure over a subset of the axes of the points.
+///
+/// The inner measure is applied to copies of the points whose other coordinates are zeroed, which
+/// equals the distance over the selected axes for coordinate-wise metrics such as Euclidean. This
+/// allows, for example, clustering pixels of color and position by their color only.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectedDistance<D> {
+    axes: Vec<usize>,
+    inner: D,
+}
+
+impl<D> ProjectedDistance<D> {
+    /// Create a distance measure over the given axes with the inner measure.
+    #[must_use]
+    pub fn new(axes: Vec<usize>, inner: D) -> Self {
+        Self { axes, inner }
+    }
+
+    /// Return the axes measured by this measure.
+    #[must_use]
+    pub fn axes(&self) -> &[usize] {
+        &self.axes
+    }
+
+    /// Return the inner distance measure.
+    #[must_use]
+    pub fn inner(&self) -> &D {
+        &self.inner
+    }
+}
+
+impl<F, P, D> DistanceMeasure<F, P> for ProjectedDistance<D>
+where
+    F: Float,
+    P: Point<F> + IndexMut<usize, Output = F>,
+    D: DistanceMeasure<F, P>,
+{
+    #[inline]
+    fn measure(&self, lhs: &P, rhs: &P) -> F {
+        self.inner
+            .measure(&lhs.project(&self.axes), &rhs.project(&self.axes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::distance::metric::DistanceMetric;
+    use crate::math::point::{Point3, Point5};
+
+    #[test]
+    fn measure_should_match_distance_over_selected_axes() {
+        let lhs = Point5::new(0.1, 0.2, 0.3, 0.0, 0.0);
+        let rhs = Point5::new(0.4, -0.2, 0.5, 0.9, 0.1);
+        for metric in [
+            DistanceMetric::Euclidean,
+            DistanceMetric::SquaredEuclidean,
+            DistanceMetric::Manhattan,
+            DistanceMetric::Chebyshev,
+        ] {
+            let projected = ProjectedDistance::new(vec![0, 1, 2], metric);
+            let actual: f64 = projected.measure(&lhs, &rhs);
+            let expected: f64 = DistanceMeasure::measure(
+                &metric,
+                &Point3::new(0.1, 0.2, 0.3),
+                &Point3::new(0.4, -0.2, 0.5),
+            );
+            assert!((actual - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn measure_should_ignore_unselected_axes() {
+        let projected = ProjectedDistance::new(vec![3, 4], DistanceMetric::Euclidean);
+        assert_eq!(projected.axes(), &[3, 4]);
+        assert_eq!(projected.inner(), &DistanceMetric::Euclidean);
+
+        let distance: f64 = projected.measure(
+            &Point5::new(0.0, 0.0, 0.0, 0.0, 0.0),
+            &Point5::new(9.0, 9.0, 9.0, 3.0, 4.0),
+        );
+        assert!((distance - 5.0).abs() < 1e-12);
+    }
+}
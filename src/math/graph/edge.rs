@@ -51,7 +51,7 @@ where
     F: Float,
 {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap_or(Ordering::Greater)
+        self.weight.total_order(&other.weight).reverse()
     }
 }
 
@@ -60,9 +60,7 @@ where
     F: Float,
 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.weight
-            .partial_cmp(&other.weight)
-            .map(|ordering| ordering.reverse())
+        Some(self.cmp(other))
     }
 }
 
@@ -97,11 +95,11 @@ mod tests {
     fn eq_should_return_true_if_each_edge_is_equal() {
         let edge1 = WeightedEdge::new(0, 1, 5.0);
         let edge2 = WeightedEdge::new(0, 1, 5.0);
-        assert_eq!(edge1.eq(&edge2), true);
+        assert!(edge1.eq(&edge2));
 
         let edge1 = WeightedEdge::new(0, 1, 5.0);
         let edge2 = WeightedEdge::new(0, 2, 5.0);
-        assert_eq!(edge1.eq(&edge2), false);
+        assert!(!edge1.eq(&edge2));
     }
 
     #[test]
@@ -118,8 +116,9 @@ mod tests {
         let edge2 = WeightedEdge::new(1, 2, 2.5);
         assert_eq!(edge1.cmp(&edge2), Ordering::Greater);
 
+        // NaN weight is treated as the largest one.
         let edge1 = WeightedEdge::new(0, 1, f64::NAN);
         let edge2 = WeightedEdge::new(1, 2, 2.5);
-        assert_eq!(edge1.cmp(&edge2), Ordering::Greater);
+        assert_eq!(edge1.cmp(&edge2), Ordering::Less);
     }
 }
@@ -5,6 +5,7 @@ use std::collections::{BinaryHeap, HashSet};
 /// Trait for spanning tree.
 pub trait SpanningTree<F: Float, E: Edge> {
     /// Return total weight of this spanning tree.
+    #[cfg(test)]
     fn weight(&self) -> F;
 
     /// Return all edges of this graph.
@@ -14,7 +15,6 @@ pub trait SpanningTree<F: Float, E: Edge> {
 /// Minimum spanning tree struct.
 #[derive(Debug, Clone)]
 pub struct MinimumSpanningTree<F: Float> {
-    weight: F,
     edges: Vec<WeightedEdge<F>>,
 }
 
@@ -28,17 +28,13 @@ where
         WF: Fn(usize, usize) -> F,
     {
         if vertices.is_empty() {
-            return Self {
-                weight: F::zero(),
-                edges: Vec::new(),
-            };
+            return Self { edges: Vec::new() };
         }
 
         let n_vertices = vertices.len();
         let mut edges = Vec::new();
         let mut attached = HashSet::with_capacity(n_vertices);
         let mut candidates = BinaryHeap::new();
-        let mut total_weight = F::zero();
         let mut current_index = n_vertices - 1;
         attached.insert(current_index);
         while attached.len() < n_vertices {
@@ -54,17 +50,13 @@ where
             while let Some(edge) = candidates.pop() {
                 if !attached.contains(&edge.v()) {
                     current_index = edge.v();
-                    total_weight += edge.weight();
                     edges.push(edge);
                     attached.insert(current_index);
                     break;
                 }
             }
         }
-        Self {
-            edges,
-            weight: total_weight,
-        }
+        Self { edges }
     }
 }
 
@@ -72,8 +64,11 @@ impl<F> SpanningTree<F, WeightedEdge<F>> for MinimumSpanningTree<F>
 where
     F: Float,
 {
+    #[cfg(test)]
     fn weight(&self) -> F {
-        self.weight
+        self.edges
+            .iter()
+            .fold(F::zero(), |total, edge| total + edge.weight())
     }
 
     fn edges(&self) -> &[WeightedEdge<F>] {
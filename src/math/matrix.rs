@@ -0,0 +1,187 @@
+use crate::math::number::Float;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{Index, IndexMut};
+
+/// Dense matrix stored in row-major order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix<F: Float> {
+    rows: usize,
+    cols: usize,
+    data: Vec<F>,
+}
+
+impl<F> Matrix<F>
+where
+    F: Float,
+{
+    const MAX_SWEEPS: usize = 64;
+
+    /// Create a matrix filled with zeros.
+    #[must_use]
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![F::zero(); rows * cols],
+        }
+    }
+
+    /// Create an identity matrix of the given size.
+    #[must_use]
+    pub fn identity(n: usize) -> Self {
+        let mut matrix = Self::zeros(n, n);
+        for i in 0..n {
+            matrix[(i, i)] = F::one();
+        }
+        matrix
+    }
+
+    /// Return the number of rows.
+    #[must_use]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Return the number of columns.
+    #[must_use]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Compute eigenvalues and eigenvectors of this symmetric matrix.
+    ///
+    /// Eigenvalues are returned in ascending order, and the eigenvector corresponding to each
+    /// eigenvalue is stored in the column of the same index.
+    ///
+    /// [Jacobi eigenvalue algorithm - Wikipedia](https://en.wikipedia.org/wiki/Jacobi_eigenvalue_algorithm)
+    #[must_use]
+    pub fn symmetric_eigen(&self) -> (Vec<F>, Matrix<F>) {
+        assert_eq!(self.rows, self.cols, "Matrix must be square");
+        let n = self.rows;
+        let mut a = self.clone();
+        let mut v = Self::identity(n);
+        for _ in 0..Self::MAX_SWEEPS {
+            let mut off_diagonal = F::zero();
+            for p in 0..n {
+                for q in (p + 1)..n {
+                    off_diagonal += a[(p, q)].powi(2);
+                }
+            }
+            if off_diagonal <= F::epsilon() {
+                break;
+            }
+
+            for p in 0..n {
+                for q in (p + 1)..n {
+                    let apq = a[(p, q)];
+                    if apq.is_zero() {
+                        continue;
+                    }
+
+                    let theta = (a[(q, q)] - a[(p, p)]) / (F::from_f64(2.0) * apq);
+                    let t = theta.signum() / (theta.abs() + (theta.powi(2) + F::one()).sqrt());
+                    let c = F::one() / (t.powi(2) + F::one()).sqrt();
+                    let s = t * c;
+                    for k in 0..n {
+                        let akp = a[(k, p)];
+                        let akq = a[(k, q)];
+                        a[(k, p)] = c * akp - s * akq;
+                        a[(k, q)] = s * akp + c * akq;
+                    }
+                    for k in 0..n {
+                        let apk = a[(p, k)];
+                        let aqk = a[(q, k)];
+                        a[(p, k)] = c * apk - s * aqk;
+                        a[(q, k)] = s * apk + c * aqk;
+                    }
+                    for k in 0..n {
+                        let vkp = v[(k, p)];
+                        let vkq = v[(k, q)];
+                        v[(k, p)] = c * vkp - s * vkq;
+                        v[(k, q)] = s * vkp + c * vkq;
+                    }
+                }
+            }
+        }
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| a[(i, i)].total_order(&a[(j, j)]));
+
+        let values = order.iter().map(|&i| a[(i, i)]).collect();
+        let mut vectors = Self::zeros(n, n);
+        for (col, &i) in order.iter().enumerate() {
+            for row in 0..n {
+                vectors[(row, col)] = v[(row, i)];
+            }
+        }
+        (values, vectors)
+    }
+}
+
+impl<F> Index<(usize, usize)> for Matrix<F>
+where
+    F: Float,
+{
+    type Output = F;
+
+    #[inline]
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        assert!(row < self.rows && col < self.cols, "Index out of bounds");
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl<F> IndexMut<(usize, usize)> for Matrix<F>
+where
+    F: Float,
+{
+    #[inline]
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        assert!(row < self.rows && col < self.cols, "Index out of bounds");
+        &mut self.data[row * self.cols + col]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_should_create_identity_matrix() {
+        let matrix: Matrix<f64> = Matrix::identity(2);
+        assert_eq!(matrix.rows(), 2);
+        assert_eq!(matrix.cols(), 2);
+        assert_eq!(matrix[(0, 0)], 1.0);
+        assert_eq!(matrix[(0, 1)], 0.0);
+        assert_eq!(matrix[(1, 0)], 0.0);
+        assert_eq!(matrix[(1, 1)], 1.0);
+    }
+
+    #[test]
+    fn symmetric_eigen_should_decompose_matrix() {
+        let mut matrix = Matrix::zeros(3, 3);
+        let values = [[2.0, -1.0, 0.0], [-1.0, 2.0, -1.0], [0.0, -1.0, 2.0]];
+        for (row, row_values) in values.iter().enumerate() {
+            for (col, value) in row_values.iter().enumerate() {
+                matrix[(row, col)] = *value;
+            }
+        }
+
+        let (eigenvalues, eigenvectors) = matrix.symmetric_eigen();
+        let expected = [2.0 - 2.0_f64.sqrt(), 2.0, 2.0 + 2.0_f64.sqrt()];
+        for (actual, expected) in eigenvalues.iter().zip(expected) {
+            assert!((actual - expected).abs() < 1e-9);
+        }
+
+        // A * v = lambda * v for each eigenpair.
+        for (col, lambda) in eigenvalues.iter().enumerate() {
+            for row in 0..3 {
+                let av: f64 = (0..3)
+                    .map(|k| matrix[(row, k)] * eigenvectors[(k, col)])
+                    .sum();
+                assert!((av - lambda * eigenvectors[(row, col)]).abs() < 1e-9);
+            }
+        }
+    }
+}
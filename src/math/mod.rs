@@ -1,6 +1,11 @@
+#[cfg(feature = "std")]
 pub mod clustering;
 pub mod distance;
+#[cfg(feature = "std")]
 pub mod graph;
+pub mod matrix;
+#[cfg(feature = "std")]
 pub mod neighbors;
 pub mod number;
 pub mod point;
+pub mod preprocessing;
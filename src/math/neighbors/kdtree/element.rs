@@ -1,8 +1,10 @@
 use crate::math::number::Float;
 use std::cmp::Ordering;
-use std::cmp::Ordering::Greater;
 
 /// An index of the node and the distance from a query point.
+///
+/// Elements are ordered by the distance and then by the index, so that equidistant elements have a
+/// stable order regardless of the traversal.
 pub(crate) struct Element<F: Float> {
     index: usize,
     distance: F,
@@ -35,7 +37,7 @@ where
     F: Float,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.distance == other.distance
+        self.cmp(other) == Ordering::Equal
     }
 }
 
@@ -44,7 +46,11 @@ where
     F: Float,
 {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap_or(Greater)
+        // Return reversed ordering to increase the priority in the BinaryHeap.
+        self.distance
+            .total_order(&other.distance)
+            .then_with(|| self.index.cmp(&other.index))
+            .reverse()
     }
 }
 
@@ -53,9 +59,28 @@ where
     F: Float,
 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        // Return reversed ordering to increase the priority in the BinaryHeap.
-        self.distance
-            .partial_cmp(&other.distance)
-            .map(|ordering| ordering.reverse())
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BinaryHeap;
+
+    #[test]
+    fn cmp_should_break_ties_by_index() {
+        let mut heap = BinaryHeap::from(vec![
+            Element::new(7, 5.0),
+            Element::new(2, 5.0),
+            Element::new(4, 2.0),
+            Element::new(0, 5.0),
+        ]);
+        let mut indices = Vec::new();
+        while let Some(element) = heap.pop() {
+            indices.push(element.index());
+        }
+        assert_eq!(indices, vec![4, 0, 2, 7]);
+        assert!(Element::new(1, 5.0) != Element::new(2, 5.0));
     }
 }
@@ -2,9 +2,11 @@ use crate::math::distance::metric::DistanceMetric;
 use crate::math::neighbors::nns::{Neighbor, NeighborSearch};
 use crate::math::number::Float;
 use crate::math::point::Point;
+#[cfg(test)]
+use core::sync::atomic::{AtomicUsize, Ordering};
 use element::Element;
 use node::Node;
-use std::cmp::Ordering::Greater;
+use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 use std::marker::PhantomData;
 use std::ops::Div;
@@ -20,9 +22,12 @@ where
     P: Point<F>,
 {
     _t: PhantomData<F>,
-    root: Option<Box<Node>>,
-    dataset: &'a Vec<P>,
-    metric: &'a DistanceMetric,
+    root: Option<Box<Node<F>>>,
+    dataset: &'a [P],
+    metric: DistanceMetric,
+    comparison: DistanceMetric,
+    #[cfg(test)]
+    visited: AtomicUsize,
 }
 
 impl<'a, F, P> KDTree<'a, F, P>
@@ -30,97 +35,360 @@ where
     F: Float,
     P: Point<F>,
 {
+    /// The default maximum depth of the tree, which is only reached by more than 2^32 points.
+    const DEFAULT_MAX_DEPTH: usize = 32;
+
     /// Create a new KDTree.
-    pub fn new(dataset: &'a Vec<P>, metric: &'a DistanceMetric) -> Self {
+    pub fn new(dataset: &'a [P], metric: &DistanceMetric) -> Self {
+        Self::with_metric(dataset, *metric)
+    }
+
+    /// Create a new KDTree owning the given distance metric.
+    ///
+    /// Unlike [`KDTree::new`], the metric need not outlive the tree, so a metric computed on the
+    /// fly can be passed directly.
+    pub fn with_metric(dataset: &'a [P], metric: DistanceMetric) -> Self {
+        Self::with_max_depth(dataset, &metric, Self::DEFAULT_MAX_DEPTH)
+    }
+
+    /// Create a new KDTree whose nodes at the given depth become leaves holding all the remaining
+    /// points in a bucket scanned linearly.
+    ///
+    /// This bounds the recursion of building and searching the tree regardless of the dataset.
+    pub fn with_max_depth(dataset: &'a [P], metric: &DistanceMetric, max_depth: usize) -> Self {
         let mut indices: Vec<usize> = (0..dataset.len()).collect();
-        let root = Self::build_node(dataset, &mut indices, 0);
+        let root = Self::build_node(dataset, &mut indices, 0, max_depth);
         KDTree {
-            _t: PhantomData::default(),
+            _t: PhantomData,
             root: root.map(Box::new),
             dataset,
-            metric,
+            metric: *metric,
+            comparison: metric.comparison_metric(),
+            #[cfg(test)]
+            visited: AtomicUsize::new(0),
         }
     }
 
-    fn search_recursively(
+    /// Return the comparison distance from the query point to the point at the given index.
+    #[inline]
+    fn measure(&self, index: usize, query: &P) -> F {
+        self.comparison.measure(&self.dataset[index], query)
+    }
+
+    /// Return the minimum comparison distance from the query point to the bounding box of the
+    /// given node.
+    #[inline]
+    fn measure_node(&self, node: Option<&Node<F>>, query: &P) -> F {
+        node.map_or(F::zero(), |node| {
+            self.comparison
+                .measure_bounds(query, node.lower(), node.upper())
+        })
+    }
+
+    /// Record a visit of a node to count the visited nodes in tests.
+    #[inline]
+    fn record_visit(&self) {
+        #[cfg(test)]
+        self.visited.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Search the nearest neighbor of the query point only if it is within the given radius.
+    ///
+    /// This traverses the tree once while pruning branches beyond the radius or the nearest
+    /// neighbor found so far.
+    #[must_use]
+    pub fn search_nearest_within(&self, query: &P, radius: F) -> Option<Neighbor<F>> {
+        if radius < F::zero() {
+            return None;
+        }
+
+        let mut nearest: Option<Element<F>> = None;
+        self.search_nearest_within_recursively(
+            self.root.as_deref(),
+            query,
+            (radius, self.metric.comparison_bound(radius)),
+            &mut nearest,
+        );
+        nearest.map(|element| {
+            Neighbor::new(
+                element.index(),
+                self.metric.from_comparison(element.distance()),
+            )
+        })
+    }
+
+    fn search_nearest_within_recursively(
         &self,
-        root: Option<&Box<Node>>,
+        root: Option<&Node<F>>,
         query: &P,
-        k: usize,
-        heap: &mut BinaryHeap<Element<F>>,
+        (radius, bound): (F, F),
+        nearest: &mut Option<Element<F>>,
     ) {
         let Some(node) = root else {
             return;
         };
+        self.record_visit();
+
+        for index in node.indices() {
+            let distance = self.measure(index, query);
+            let is_nearer = match nearest {
+                Some(element) => {
+                    distance < element.distance()
+                        || (distance == element.distance() && index < element.index())
+                }
+                None => distance <= bound && self.metric.from_comparison(distance) <= radius,
+            };
+            if is_nearer {
+                *nearest = Some(Element::new(index, distance));
+            }
+        }
+        if node.is_leaf() {
+            return;
+        }
+
+        let point = self.dataset[node.index()];
+        let delta = {
+            let axis = node.axis();
+            query[axis] - point[axis]
+        };
+        let (near, far) = if delta < F::zero() {
+            (node.left(), node.right())
+        } else {
+            (node.right(), node.left())
+        };
+        self.search_nearest_within_recursively(near, query, (radius, bound), nearest);
+
+        let furthest = nearest.as_ref().map_or(bound, |element| element.distance());
+        if self.measure_node(far, query) <= furthest {
+            self.search_nearest_within_recursively(far, query, (radius, bound), nearest);
+        }
+    }
 
-        let index = node.index();
-        let point = self.dataset[index];
-        let element = {
-            let distance = self.metric.measure(&point, query);
-            Element::new(index, distance)
+    /// Search up to k nearest neighbors of the query point within the given radius.
+    ///
+    /// This traverses the tree once while pruning branches beyond the radius or the k-th nearest
+    /// neighbor found so far, which is cheaper than filtering a full radius search. The neighbors
+    /// are sorted from nearest to furthest.
+    #[must_use]
+    pub fn search_knn_within_radius(&self, query: &P, k: usize, radius: F) -> Vec<Neighbor<F>> {
+        if k < 1 || radius < F::zero() {
+            return Vec::new();
+        }
+
+        let mut heap: BinaryHeap<Reverse<Element<F>>> = BinaryHeap::with_capacity(k + 1);
+        self.search_knn_within_radius_recursively(
+            self.root.as_deref(),
+            query,
+            (k, radius, self.metric.comparison_bound(radius)),
+            &mut heap,
+        );
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(element)| {
+                Neighbor::new(
+                    element.index(),
+                    self.metric.from_comparison(element.distance()),
+                )
+            })
+            .collect()
+    }
+
+    fn search_knn_within_radius_recursively(
+        &self,
+        root: Option<&Node<F>>,
+        query: &P,
+        (k, radius, bound): (usize, F, F),
+        heap: &mut BinaryHeap<Reverse<Element<F>>>,
+    ) {
+        let Some(node) = root else {
+            return;
         };
-        heap.push(element);
+        self.record_visit();
+
+        for index in node.indices() {
+            let distance = self.measure(index, query);
+            if distance > bound || self.metric.from_comparison(distance) > radius {
+                continue;
+            }
+            heap.push(Reverse(Element::new(index, distance)));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
         if node.is_leaf() {
             return;
         }
 
+        let point = self.dataset[node.index()];
         let delta = {
             let axis = node.axis();
             query[axis] - point[axis]
         };
-        let distance = heap.peek().map(|e| e.distance()).unwrap_or(F::min_value());
-        if heap.len() < k || delta.abs() <= distance {
-            self.search_recursively(node.left(), query, k, heap);
-            self.search_recursively(node.right(), query, k, heap);
-        } else if delta < F::zero() {
-            self.search_recursively(node.left(), query, k, heap);
+        let (near, far) = if delta < F::zero() {
+            (node.left(), node.right())
         } else {
-            self.search_recursively(node.right(), query, k, heap);
+            (node.right(), node.left())
+        };
+        self.search_knn_within_radius_recursively(near, query, (k, radius, bound), heap);
+
+        let furthest = match heap.peek() {
+            Some(Reverse(element)) if heap.len() >= k => element.distance(),
+            _ => bound,
+        };
+        if far.is_some() && self.measure_node(far, query) <= furthest {
+            self.search_knn_within_radius_recursively(far, query, (k, radius, bound), heap);
         }
     }
 
-    fn search_radius_recursively(
+    fn search_recursively(
         &self,
-        root: Option<&Box<Node>>,
+        root: Option<&Node<F>>,
         query: &P,
-        radius: F,
-        results: &mut BinaryHeap<Element<F>>,
+        k: usize,
+        heap: &mut BinaryHeap<Reverse<Element<F>>>,
     ) {
         let Some(node) = root else {
             return;
         };
+        self.record_visit();
 
-        let index = node.index();
-        let point = self.dataset[index];
-        let distance = self.metric.measure(&point, query);
-        if distance <= radius {
-            results.push(Element::new(index, distance));
+        for index in node.indices() {
+            let distance = self.measure(index, query);
+            // The heap keeps the k nearest elements and its top is the furthest one of them.
+            heap.push(Reverse(Element::new(index, distance)));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+        if node.is_leaf() {
+            return;
         }
 
+        let point = self.dataset[node.index()];
         let delta = {
             let axis = node.axis();
             query[axis] - point[axis]
         };
-        if delta.abs() <= radius {
-            self.search_radius_recursively(node.left(), query, radius, results);
-            self.search_radius_recursively(node.right(), query, radius, results);
-        } else if delta < F::zero() {
-            self.search_radius_recursively(node.left(), query, radius, results);
+        let (near, far) = if delta < F::zero() {
+            (node.left(), node.right())
         } else {
-            self.search_radius_recursively(node.right(), query, radius, results);
+            (node.right(), node.left())
+        };
+        self.search_recursively(near, query, k, heap);
+
+        let furthest = heap
+            .peek()
+            .map_or(F::max_value(), |Reverse(element)| element.distance());
+        if heap.len() < k || furthest.is_nan() || self.measure_node(far, query) <= furthest {
+            self.search_recursively(far, query, k, heap);
+        }
+    }
+
+    fn search_radius_recursively(
+        &self,
+        root: Option<&Node<F>>,
+        query: &P,
+        (radius, bound): (F, F),
+        results: &mut BinaryHeap<Element<F>>,
+    ) {
+        let Some(node) = root else {
+            return;
+        };
+        self.record_visit();
+
+        for index in node.indices() {
+            let distance = self.measure(index, query);
+            if distance > bound {
+                continue;
+            }
+            // The root is taken only for the points near the radius.
+            let distance = self.metric.from_comparison(distance);
+            if distance <= radius {
+                results.push(Element::new(index, distance));
+            }
+        }
+
+        for child in [node.left(), node.right()] {
+            if child.is_some() && self.measure_node(child, query) <= bound {
+                self.search_radius_recursively(child, query, (radius, bound), results);
+            }
         }
     }
 
-    fn build_node(dataset: &'a [P], indices: &mut [usize], depth: usize) -> Option<Node> {
+    /// Count the points within the radius in the given subtree, stopping at the given limit.
+    fn count_within_radius_recursively(
+        &self,
+        root: Option<&Node<F>>,
+        query: &P,
+        (radius, bound): (F, F),
+        limit: usize,
+        count: &mut usize,
+    ) {
+        let Some(node) = root else {
+            return;
+        };
+        self.record_visit();
+
+        for index in node.indices() {
+            if *count >= limit {
+                return;
+            }
+            let distance = self.measure(index, query);
+            if distance <= bound && self.metric.from_comparison(distance) <= radius {
+                *count += 1;
+            }
+        }
+
+        for child in [node.left(), node.right()] {
+            if *count < limit && child.is_some() && self.measure_node(child, query) <= bound {
+                self.count_within_radius_recursively(child, query, (radius, bound), limit, count);
+            }
+        }
+    }
+
+    /// Count the points within the radius up to the given limit without materializing them.
+    fn count_up_to(&self, query: &P, radius: F, limit: usize) -> usize {
+        if radius < F::zero() {
+            return 0;
+        }
+
+        let mut count = 0;
+        self.count_within_radius_recursively(
+            self.root.as_deref(),
+            query,
+            (radius, self.metric.comparison_bound(radius)),
+            limit,
+            &mut count,
+        );
+        count
+    }
+
+    fn build_node(
+        dataset: &'a [P],
+        indices: &mut [usize],
+        depth: usize,
+        max_depth: usize,
+    ) -> Option<Node<F>> {
         if dataset.is_empty() || indices.is_empty() {
             return None;
         }
 
         let axis = depth % dataset[0].dim();
+        if depth >= max_depth {
+            let (&index, bucket) = indices.split_first()?;
+            let points: Vec<Vec<F>> = bucket
+                .iter()
+                .map(|&index| dataset[index].to_vec())
+                .collect();
+            let node = Node::new(index, axis, dataset[index].to_vec(), None, None)
+                .with_bucket(bucket.to_vec(), &points);
+            return Some(node);
+        }
+
         indices.sort_unstable_by(|index1, index2| {
             let lhs = dataset[*index1].index(axis);
             let rhs = dataset[*index2].index(axis);
-            lhs.partial_cmp(rhs).unwrap_or(Greater)
+            lhs.total_order(rhs)
         });
 
         let node = {
@@ -128,8 +396,9 @@ where
             Node::new(
                 indices[median],
                 axis,
-                Self::build_node(dataset, &mut indices[..median], depth + 1),
-                Self::build_node(dataset, &mut indices[median + 1..], depth + 1),
+                dataset[indices[median]].to_vec(),
+                Self::build_node(dataset, &mut indices[..median], depth + 1, max_depth),
+                Self::build_node(dataset, &mut indices[median + 1..], depth + 1, max_depth),
             )
         };
         Some(node)
@@ -146,17 +415,17 @@ where
             return Vec::new();
         }
 
-        let mut heap: BinaryHeap<Element<F>> = BinaryHeap::new();
-        self.search_recursively(self.root.as_ref(), query, k, &mut heap);
-
-        let mut neighbors = Vec::with_capacity(k);
-        while let Some(element) = heap.pop() {
-            neighbors.push(Neighbor::new(element.index(), element.distance()));
-            if neighbors.len() == k {
-                break;
-            }
-        }
-        neighbors
+        let mut heap: BinaryHeap<Reverse<Element<F>>> = BinaryHeap::with_capacity(k + 1);
+        self.search_recursively(self.root.as_deref(), query, k, &mut heap);
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(element)| {
+                Neighbor::new(
+                    element.index(),
+                    self.metric.from_comparison(element.distance()),
+                )
+            })
+            .collect()
     }
 
     fn search_nearest(&self, query: &P) -> Option<Neighbor<F>> {
@@ -169,7 +438,12 @@ where
         }
 
         let mut results: BinaryHeap<Element<F>> = BinaryHeap::new();
-        self.search_radius_recursively(self.root.as_ref(), query, radius, &mut results);
+        self.search_radius_recursively(
+            self.root.as_deref(),
+            query,
+            (radius, self.metric.comparison_bound(radius)),
+            &mut results,
+        );
 
         let mut neighbors = Vec::with_capacity(results.len());
         while let Some(element) = results.pop() {
@@ -177,12 +451,21 @@ where
         }
         neighbors
     }
+
+    fn count_within_radius(&self, query: &P, radius: F) -> usize {
+        self.count_up_to(query, radius, usize::MAX)
+    }
+
+    fn has_at_least(&self, query: &P, radius: F, n: usize) -> bool {
+        self.count_up_to(query, radius, n) >= n
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::math::point::Point2;
+    use crate::math::neighbors::linear::LinearSearch;
+    use crate::math::point::{Point2, Point3, Point5};
 
     const DATASET: [Point2<f32>; 8] = [
         Point2(1.0, 2.0),
@@ -213,16 +496,406 @@ mod tests {
             vec![
                 Neighbor::new(4, 2.0),
                 Neighbor::new(1, 4.0),
-                Neighbor::new(6, 5.0),
+                Neighbor::new(0, 5.0),
                 Neighbor::new(2, 5.0),
+                Neighbor::new(6, 5.0),
                 Neighbor::new(7, 5.0),
-                Neighbor::new(0, 5.0),
                 Neighbor::new(3, 8.0),
                 Neighbor::new(5, 13.0),
             ]
         );
     }
 
+    #[test]
+    fn search_should_order_equidistant_neighbors_by_index() {
+        let dataset = Vec::from(DATASET);
+        let query = Point2(3.0, 3.0);
+        for (metric, radius) in [
+            (DistanceMetric::Euclidean, 2.5),
+            (DistanceMetric::SquaredEuclidean, 6.0),
+        ] {
+            let kdtree = KDTree::new(&dataset, &metric);
+            let linear_search = LinearSearch::new(&dataset, metric);
+            let indices = |neighbors: Vec<Neighbor<f32>>| -> Vec<usize> {
+                neighbors.iter().map(|neighbor| neighbor.index).collect()
+            };
+            for k in 1..=dataset.len() {
+                assert_eq!(
+                    indices(kdtree.search(&query, k)),
+                    indices(linear_search.search(&query, k))
+                );
+            }
+            // The points 0, 2, 6 and 7 are equidistant from the query point.
+            assert_eq!(indices(kdtree.search(&query, 4)), vec![4, 1, 0, 2]);
+            assert_eq!(indices(kdtree.search(&query, 6)), vec![4, 1, 0, 2, 6, 7]);
+            assert_eq!(
+                indices(kdtree.search_radius(&query, radius)),
+                vec![4, 1, 0, 2, 6, 7]
+            );
+        }
+    }
+
+    #[test]
+    fn new_should_accept_array_slice() {
+        let kdtree = KDTree::new(&DATASET, &DistanceMetric::SquaredEuclidean);
+        assert_eq!(
+            kdtree.search(&Point2(3.0, 3.0), 2),
+            vec![Neighbor::new(4, 2.0), Neighbor::new(1, 4.0)]
+        );
+    }
+
+    #[test]
+    fn with_metric_should_search_like_new() {
+        let dataset = Vec::from(DATASET);
+        for metric in [DistanceMetric::Euclidean, DistanceMetric::SquaredEuclidean] {
+            let expected = KDTree::new(&dataset, &metric);
+            let actual = KDTree::with_metric(&dataset, metric);
+            let linear_search = LinearSearch::new(&dataset, metric);
+            for query in [Point2(3.0, 3.0), Point2(0.0, 0.0), Point2(5.0, 4.0)] {
+                assert_eq!(actual.search(&query, 3), expected.search(&query, 3));
+                assert_eq!(actual.search(&query, 3), linear_search.search(&query, 3));
+                assert_eq!(
+                    actual.search_radius(&query, 2.5),
+                    expected.search_radius(&query, 2.5)
+                );
+                assert_eq!(
+                    actual.search_nearest_within(&query, 2.5),
+                    expected.search_nearest_within(&query, 2.5)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn search_should_match_linear_search() {
+        let dataset: Vec<Point2<f64>> = (0..64)
+            .map(|i| {
+                let x = (i * 37 % 64) as f64 / 8.0;
+                let y = (i * 11 % 64) as f64 / 8.0;
+                Point2(x, y)
+            })
+            .collect();
+        for metric in [DistanceMetric::Euclidean, DistanceMetric::SquaredEuclidean] {
+            let kdtree = KDTree::new(&dataset, &metric);
+            let linear_search = LinearSearch::new(&dataset, metric);
+            for query in [Point2(0.5, 0.5), Point2(4.1, 3.3), Point2(7.5, 0.2)] {
+                let distances = |neighbors: Vec<Neighbor<f64>>| -> Vec<f64> {
+                    neighbors.iter().map(|neighbor| neighbor.distance).collect()
+                };
+                assert_eq!(
+                    distances(kdtree.search(&query, 5)),
+                    distances(linear_search.search(&query, 5))
+                );
+                assert_eq!(
+                    kdtree.search_radius(&query, 0.8).len(),
+                    linear_search.search_radius(&query, 0.8).len()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn search_should_match_linear_search_with_cylindrical_metric() {
+        // Hues spread over the whole circle, so that neighbors wrap around 0° and 360°.
+        let dataset: Vec<Point3<f64>> = (0..96)
+            .map(|i| {
+                let lightness = (i * 37 % 96) as f64;
+                let chroma = (i * 11 % 48) as f64;
+                let hue = (i * 53 % 96) as f64 * 3.75;
+                Point3(lightness, chroma, hue)
+            })
+            .collect();
+        let metric = DistanceMetric::Cylindrical;
+        let kdtree = KDTree::new(&dataset, &metric);
+        let linear_search = LinearSearch::new(&dataset, metric);
+        for query in [
+            Point3(50.0, 20.0, 359.0),
+            Point3(10.0, 40.0, 1.0),
+            Point3(80.0, 5.0, 180.0),
+        ] {
+            let indices = |mut neighbors: Vec<Neighbor<f64>>| -> Vec<usize> {
+                neighbors.sort_by_key(|neighbor| neighbor.index);
+                neighbors.iter().map(|neighbor| neighbor.index).collect()
+            };
+            assert_eq!(
+                indices(kdtree.search(&query, 5)),
+                indices(linear_search.search(&query, 5))
+            );
+            assert_eq!(
+                indices(kdtree.search_radius(&query, 30.0)),
+                indices(linear_search.search_radius(&query, 30.0))
+            );
+        }
+    }
+
+    /// Count the nodes visited by a radius search pruning only by the splitting axis.
+    fn count_axis_pruned_nodes<F: Float, P: Point<F>>(
+        kdtree: &KDTree<F, P>,
+        root: Option<&Node<F>>,
+        query: &P,
+        radius: F,
+    ) -> usize {
+        let Some(node) = root else {
+            return 0;
+        };
+        let axis = node.axis();
+        let delta = query[axis] - kdtree.dataset[node.index()][axis];
+        let children = if kdtree.metric.measure_axis(delta) <= radius {
+            count_axis_pruned_nodes(kdtree, node.left(), query, radius)
+                + count_axis_pruned_nodes(kdtree, node.right(), query, radius)
+        } else if delta < F::zero() {
+            count_axis_pruned_nodes(kdtree, node.left(), query, radius)
+        } else {
+            count_axis_pruned_nodes(kdtree, node.right(), query, radius)
+        };
+        children + 1
+    }
+
+    #[test]
+    fn search_radius_should_visit_fewer_nodes_with_bounding_boxes() {
+        let mut seed: u64 = 42;
+        let mut random = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 11) as f64 / (1_u64 << 53) as f64
+        };
+        let dataset: Vec<Point5<f64>> = (0..2048)
+            .map(|_| Point5::new(random(), random(), random(), random(), random()))
+            .collect();
+        let metric = DistanceMetric::Euclidean;
+        let kdtree = KDTree::new(&dataset, &metric);
+        let linear_search = LinearSearch::new(&dataset, metric);
+
+        let mut visited = 0;
+        let mut baseline = 0;
+        for query in dataset.iter().take(32) {
+            kdtree.visited.store(0, Ordering::Relaxed);
+            let mut actual: Vec<usize> = kdtree
+                .search_radius(query, 0.2)
+                .iter()
+                .map(|neighbor| neighbor.index)
+                .collect();
+            let mut expected: Vec<usize> = linear_search
+                .search_radius(query, 0.2)
+                .iter()
+                .map(|neighbor| neighbor.index)
+                .collect();
+            actual.sort_unstable();
+            expected.sort_unstable();
+            assert_eq!(actual, expected);
+
+            visited += kdtree.visited.load(Ordering::Relaxed);
+            baseline += count_axis_pruned_nodes(&kdtree, kdtree.root.as_deref(), query, 0.2);
+        }
+        assert!(visited < baseline);
+    }
+
+    /// Return the depth of the given subtree.
+    fn depth<F: Float>(root: Option<&Node<F>>) -> usize {
+        root.map_or(0, |node| 1 + depth(node.left()).max(depth(node.right())))
+    }
+
+    #[test]
+    fn with_max_depth_should_match_linear_search_on_duplicated_points() {
+        // Mostly identical points as in quantized color data.
+        let mut dataset: Vec<Point5<f64>> = vec![Point5::new(0.5, 0.5, 0.5, 0.25, 0.75); 4096];
+        for i in 0..64 {
+            let value = i as f64 / 64.0;
+            dataset[i * 61] = Point5::new(value, 1.0 - value, 0.5, value, 0.0);
+        }
+        let metric = DistanceMetric::Euclidean;
+        let linear_search = LinearSearch::new(&dataset, metric);
+        let sorted = |neighbors: Vec<Neighbor<f64>>| -> Vec<(usize, f64)> {
+            let mut neighbors: Vec<(usize, f64)> = neighbors
+                .into_iter()
+                .map(|neighbor| (neighbor.index, neighbor.distance))
+                .collect();
+            neighbors.sort_by_key(|&(index, _)| index);
+            neighbors
+        };
+
+        for max_depth in [0, 4, KDTree::<f64, Point5<f64>>::DEFAULT_MAX_DEPTH] {
+            let kdtree = KDTree::with_max_depth(&dataset, &metric, max_depth);
+            assert!(depth(kdtree.root.as_deref()) <= max_depth + 1);
+            for query in [
+                Point5::new(0.5, 0.5, 0.5, 0.25, 0.75),
+                Point5::new(0.1, 0.9, 0.5, 0.1, 0.0),
+                Point5::new(1.0, 1.0, 1.0, 1.0, 1.0),
+            ] {
+                assert_eq!(
+                    sorted(kdtree.search_radius(&query, 0.2)),
+                    sorted(linear_search.search_radius(&query, 0.2))
+                );
+                let distances = |neighbors: Vec<Neighbor<f64>>| -> Vec<f64> {
+                    neighbors.iter().map(|neighbor| neighbor.distance).collect()
+                };
+                assert_eq!(
+                    distances(kdtree.search(&query, 8)),
+                    distances(linear_search.search(&query, 8))
+                );
+                assert_eq!(
+                    kdtree
+                        .search_nearest_within(&query, 2.0)
+                        .map(|n| n.distance),
+                    linear_search.search_nearest(&query).map(|n| n.distance)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn search_should_match_euclidean_distances_exactly_with_squared_comparison() {
+        let mut seed: u64 = 3;
+        let mut random = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 11) as f64 / (1_u64 << 53) as f64
+        };
+        let dataset: Vec<Point5<f64>> = (0..512)
+            .map(|_| Point5::new(random(), random(), random(), random(), random()))
+            .collect();
+        let metric = DistanceMetric::Euclidean;
+        let kdtree = KDTree::new(&dataset, &metric);
+        let reference = |query: &Point5<f64>| -> Vec<(usize, f64)> {
+            let mut neighbors: Vec<(usize, f64)> = dataset
+                .iter()
+                .enumerate()
+                .map(|(index, point)| (index, metric.measure(point, query)))
+                .collect();
+            neighbors.sort_by(|(_, d1), (_, d2)| d1.total_order(d2));
+            neighbors
+        };
+
+        for query in dataset.iter().step_by(37) {
+            let expected = reference(query);
+            let actual: Vec<(usize, f64)> = kdtree
+                .search(query, 10)
+                .into_iter()
+                .map(|neighbor| (neighbor.index, neighbor.distance))
+                .collect();
+            assert_eq!(actual, expected[..10].to_vec());
+
+            // The radius is exactly the distance of the 20th nearest point, which is included.
+            let radius = expected[19].1;
+            let mut actual: Vec<(usize, f64)> = kdtree
+                .search_radius(query, radius)
+                .into_iter()
+                .map(|neighbor| (neighbor.index, neighbor.distance))
+                .collect();
+            actual.sort_by(|(_, d1), (_, d2)| d1.total_order(d2));
+            assert_eq!(actual, expected[..20].to_vec());
+
+            let nearest = kdtree.search_nearest_within(query, radius).unwrap();
+            assert_eq!((nearest.index, nearest.distance), expected[0]);
+        }
+    }
+
+    #[test]
+    fn search_should_not_drop_neighbors_if_distance_is_nan() {
+        let mut dataset = Vec::from(DATASET);
+        dataset.insert(0, Point2(f32::NAN, 0.0));
+        dataset.push(Point2(3.0, f32::NAN));
+        let kdtree = KDTree::new(&dataset, &DistanceMetric::SquaredEuclidean);
+
+        let neighbors = kdtree.search(&Point2(3.0, 3.0), 4);
+        let indices: Vec<usize> = neighbors.iter().map(|neighbor| neighbor.index).collect();
+        assert_eq!(neighbors.len(), 4);
+        assert!(neighbors.iter().all(|neighbor| !neighbor.distance.is_nan()));
+        assert!(indices.contains(&5));
+        assert!(indices.contains(&2));
+
+        // NaN distances are sorted last when all points are requested.
+        let neighbors = kdtree.search(&Point2(3.0, 3.0), dataset.len());
+        assert_eq!(neighbors.len(), dataset.len());
+        assert!(neighbors[8].distance.is_nan());
+        assert!(neighbors[9].distance.is_nan());
+    }
+
+    #[test]
+    fn search_nearest_within_should_return_nearest_neighbor_within_radius() {
+        let dataset = Vec::from(DATASET);
+        let kdtree = KDTree::new(&dataset, &DistanceMetric::SquaredEuclidean);
+
+        let neighbor = kdtree.search_nearest_within(&Point2(3.0, 3.0), 4.0);
+        assert_eq!(neighbor, Some(Neighbor::new(4, 2.0)));
+
+        let neighbor = kdtree.search_nearest_within(&Point2(5.0, 4.0), 1.0);
+        assert_eq!(neighbor, Some(Neighbor::new(3, 1.0)));
+    }
+
+    #[test]
+    fn search_nearest_within_should_return_none_if_outside_radius() {
+        let dataset = Vec::from(DATASET);
+        let kdtree = KDTree::new(&dataset, &DistanceMetric::SquaredEuclidean);
+        assert_eq!(kdtree.search_nearest_within(&Point2(3.0, 3.0), 1.5), None);
+        assert_eq!(kdtree.search_nearest_within(&Point2(3.0, 3.0), -1.0), None);
+
+        let dataset: Vec<Point2<f32>> = Vec::new();
+        let kdtree = KDTree::new(&dataset, &DistanceMetric::SquaredEuclidean);
+        assert_eq!(kdtree.search_nearest_within(&Point2(3.0, 3.0), 10.0), None);
+    }
+
+    #[test]
+    fn search_knn_within_radius_should_match_intersection_of_searches() {
+        let dataset = Vec::from(DATASET);
+        let kdtree = KDTree::new(&dataset, &DistanceMetric::SquaredEuclidean);
+        for query in [Point2(3.0, 3.0), Point2(0.0, 0.0), Point2(5.0, 4.0)] {
+            for k in 0..=dataset.len() {
+                for radius in [-1.0, 0.0, 2.0, 5.0, 10.0, 50.0] {
+                    let within = kdtree.search_radius(&query, radius);
+                    let expected: Vec<Neighbor<f32>> = kdtree
+                        .search(&query, k)
+                        .into_iter()
+                        .filter(|neighbor| within.contains(neighbor))
+                        .collect();
+                    assert_eq!(kdtree.search_knn_within_radius(&query, k, radius), expected);
+                }
+            }
+        }
+
+        assert_eq!(
+            kdtree.search_knn_within_radius(&Point2(3.0, 3.0), 3, 5.0),
+            vec![
+                Neighbor::new(4, 2.0),
+                Neighbor::new(1, 4.0),
+                Neighbor::new(0, 5.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn count_within_radius_should_match_search_radius() {
+        let dataset: Vec<Point2<f64>> = (0..64)
+            .map(|i| Point2((i * 37 % 64) as f64 / 8.0, (i * 11 % 64) as f64 / 8.0))
+            .collect();
+        for metric in [DistanceMetric::Euclidean, DistanceMetric::SquaredEuclidean] {
+            let kdtree = KDTree::new(&dataset, &metric);
+            for query in [Point2(0.5, 0.5), Point2(4.1, 3.3), Point2(7.5, 0.2)] {
+                for radius in [-1.0, 0.0, 0.8, 2.0, 100.0] {
+                    let count = kdtree.search_radius(&query, radius).len();
+                    assert_eq!(kdtree.count_within_radius(&query, radius), count);
+                    assert!(kdtree.has_at_least(&query, radius, count));
+                    assert!(!kdtree.has_at_least(&query, radius, count + 1));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn has_at_least_should_stop_early() {
+        let dataset = vec![Point2(0.0, 0.0); 1024];
+        let kdtree = KDTree::new(&dataset, &DistanceMetric::Euclidean);
+        kdtree.visited.store(0, Ordering::Relaxed);
+        assert!(kdtree.has_at_least(&Point2(0.0, 0.0), 1.0, 4));
+        let visited = kdtree.visited.load(Ordering::Relaxed);
+
+        kdtree.visited.store(0, Ordering::Relaxed);
+        assert_eq!(kdtree.count_within_radius(&Point2(0.0, 0.0), 1.0), 1024);
+        assert!(visited < kdtree.visited.load(Ordering::Relaxed));
+    }
+
     #[test]
     fn search_should_return_neighbors_within_radius() {
         let dataset = Vec::from(DATASET);
@@ -242,10 +915,10 @@ mod tests {
             vec![
                 Neighbor::new(4, 2.0),
                 Neighbor::new(1, 4.0),
+                Neighbor::new(0, 5.0),
+                Neighbor::new(2, 5.0),
                 Neighbor::new(6, 5.0),
                 Neighbor::new(7, 5.0),
-                Neighbor::new(2, 5.0),
-                Neighbor::new(0, 5.0),
             ]
         );
         assert_eq!(
@@ -253,10 +926,10 @@ mod tests {
             vec![
                 Neighbor::new(4, 2.0),
                 Neighbor::new(1, 4.0),
-                Neighbor::new(6, 5.0),
+                Neighbor::new(0, 5.0),
                 Neighbor::new(2, 5.0),
+                Neighbor::new(6, 5.0),
                 Neighbor::new(7, 5.0),
-                Neighbor::new(0, 5.0),
                 Neighbor::new(3, 8.0),
                 Neighbor::new(5, 13.0),
             ]
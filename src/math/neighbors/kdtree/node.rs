@@ -1,41 +1,98 @@
+use crate::math::number::Float;
+
 /// A node of KDTree
 #[derive(Debug)]
-pub(crate) struct Node {
+pub(crate) struct Node<F: Float> {
     index: usize,
+    bucket: Vec<usize>,
     axis: usize,
-    left: Option<Box<Node>>,
-    right: Option<Box<Node>>,
+    lower: Vec<F>,
+    upper: Vec<F>,
+    left: Option<Box<Node<F>>>,
+    right: Option<Box<Node<F>>>,
 }
 
-impl Node {
+impl<F> Node<F>
+where
+    F: Float,
+{
     /// Create a new node.
-    pub(crate) fn new(index: usize, axis: usize, left: Option<Node>, right: Option<Node>) -> Self {
+    ///
+    /// The bounding box of this node is expanded from the given point to cover the children.
+    pub(crate) fn new(
+        index: usize,
+        axis: usize,
+        point: Vec<F>,
+        left: Option<Node<F>>,
+        right: Option<Node<F>>,
+    ) -> Self {
+        let mut lower = point.clone();
+        let mut upper = point;
+        for child in left.iter().chain(right.iter()) {
+            for (axis, (min, max)) in lower.iter_mut().zip(upper.iter_mut()).enumerate() {
+                *min = min.min(child.lower[axis]);
+                *max = max.max(child.upper[axis]);
+            }
+        }
         Self {
             index,
+            bucket: Vec::new(),
             axis,
+            lower,
+            upper,
             left: left.map(Box::new),
             right: right.map(Box::new),
         }
     }
 
+    /// Add the given points to this node as a bucket scanned linearly.
+    ///
+    /// The bounding box of this node is expanded to cover the points of the bucket.
+    #[must_use]
+    pub(crate) fn with_bucket(mut self, indices: Vec<usize>, points: &[Vec<F>]) -> Self {
+        for point in points.iter() {
+            for (axis, (min, max)) in self.lower.iter_mut().zip(self.upper.iter_mut()).enumerate() {
+                *min = min.min(point[axis]);
+                *max = max.max(point[axis]);
+            }
+        }
+        self.bucket = indices;
+        self
+    }
+
     /// Return the index of this node.
     pub(crate) fn index(&self) -> usize {
         self.index
     }
 
+    /// Return the indices of the points held by this node, starting with its own index.
+    pub(crate) fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        core::iter::once(self.index).chain(self.bucket.iter().copied())
+    }
+
     /// Return the axis when this node is split.
     pub(crate) fn axis(&self) -> usize {
         self.axis
     }
 
+    /// Return the lower corner of the bounding box of this subtree.
+    pub(crate) fn lower(&self) -> &[F] {
+        &self.lower
+    }
+
+    /// Return the upper corner of the bounding box of this subtree.
+    pub(crate) fn upper(&self) -> &[F] {
+        &self.upper
+    }
+
     /// Return the left node of this node.
-    pub(crate) fn left(&self) -> Option<&Box<Node>> {
-        self.left.as_ref()
+    pub(crate) fn left(&self) -> Option<&Node<F>> {
+        self.left.as_deref()
     }
 
     /// Return the right node of this node.
-    pub(crate) fn right(&self) -> Option<&Box<Node>> {
-        self.right.as_ref()
+    pub(crate) fn right(&self) -> Option<&Node<F>> {
+        self.right.as_deref()
     }
 
     /// Return whether this node is leaf.
@@ -43,3 +100,29 @@ impl Node {
         self.left.is_none() && self.right.is_none()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_should_cover_children_with_bounding_box() {
+        let left = Node::new(1, 1, vec![0.0, 3.0], None, None);
+        let right = Node::new(2, 1, vec![4.0, -1.0], None, None);
+        let node = Node::new(0, 0, vec![2.0, 2.0], Some(left), Some(right));
+        assert_eq!(node.lower(), &[0.0, -1.0]);
+        assert_eq!(node.upper(), &[4.0, 3.0]);
+        assert!(!node.is_leaf());
+        assert!(node.left().unwrap().is_leaf());
+    }
+
+    #[test]
+    fn with_bucket_should_cover_bucket_points() {
+        let node = Node::new(0, 0, vec![2.0, 2.0], None, None)
+            .with_bucket(vec![3, 5], &[vec![1.0, 4.0], vec![3.0, 0.0]]);
+        assert_eq!(node.lower(), &[1.0, 0.0]);
+        assert_eq!(node.upper(), &[3.0, 4.0]);
+        assert_eq!(node.indices().collect::<Vec<_>>(), vec![0, 3, 5]);
+        assert!(node.is_leaf());
+    }
+}
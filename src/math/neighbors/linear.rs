@@ -13,7 +13,7 @@ where
     P: Point<F>,
 {
     _t: PhantomData<F>,
-    dataset: &'a Vec<P>,
+    dataset: &'a [P],
     metric: DistanceMetric,
 }
 
@@ -23,9 +23,9 @@ where
     P: Point<F>,
 {
     #[allow(unused)]
-    pub fn new(dataset: &'a Vec<P>, metric: DistanceMetric) -> Self {
+    pub fn new(dataset: &'a [P], metric: DistanceMetric) -> Self {
         Self {
-            _t: PhantomData::default(),
+            _t: PhantomData,
             dataset,
             metric,
         }
@@ -42,18 +42,28 @@ where
             return Vec::new();
         }
 
+        let comparison = self.metric.comparison_metric();
         let mut neighbors = Vec::new();
         for (index, point) in self.dataset.iter().enumerate() {
-            let distance = self.metric.measure(point, query);
+            let distance = comparison.measure(point, query);
             neighbors.push(Neighbor::new(index, distance))
         }
 
+        // Break ties by the index so that equidistant neighbors have a stable order.
         neighbors.sort_unstable_by(|neighbor1, neighbor2| -> Ordering {
-            neighbor1.distance.partial_cmp(&neighbor2.distance).unwrap()
+            neighbor1
+                .distance
+                .total_order(&neighbor2.distance)
+                .then_with(|| neighbor1.index.cmp(&neighbor2.index))
         });
 
         let mut results = Vec::with_capacity(k);
-        results.extend(neighbors.into_iter().take(k));
+        results.extend(neighbors.into_iter().take(k).map(|neighbor| {
+            Neighbor::new(
+                neighbor.index,
+                self.metric.from_comparison(neighbor.distance),
+            )
+        }));
         results
     }
 
@@ -66,9 +76,15 @@ where
             return Vec::new();
         }
 
+        let comparison = self.metric.comparison_metric();
+        let bound = self.metric.comparison_bound(radius);
         let mut neighbors = Vec::new();
         for (index, point) in self.dataset.iter().enumerate() {
-            let distance = self.metric.measure(point, query);
+            let distance = comparison.measure(point, query);
+            if distance > bound {
+                continue;
+            }
+            let distance = self.metric.from_comparison(distance);
             if distance <= radius {
                 neighbors.push(Neighbor::new(index, distance));
             }
@@ -1,3 +1,6 @@
 pub(crate) mod kdtree;
 pub(crate) mod linear;
 pub(crate) mod nns;
+
+/// The dataset size below which a linear search is used instead of building a KDTree.
+pub(crate) const LINEAR_SEARCH_THRESHOLD: usize = 32;
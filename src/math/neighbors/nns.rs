@@ -14,6 +14,19 @@ where
 
     /// Search neighbor points within the given radius.
     fn search_radius(&self, query: &T, radius: F) -> Vec<Neighbor<F>>;
+
+    /// Count the neighbor points within the given radius.
+    fn count_within_radius(&self, query: &T, radius: F) -> usize {
+        self.search_radius(query, radius).len()
+    }
+
+    /// Return whether at least `n` neighbor points are within the given radius.
+    ///
+    /// This allows a search to stop as soon as `n` points are found, as in the test for a core
+    /// point of density-based clustering.
+    fn has_at_least(&self, query: &T, radius: F, n: usize) -> bool {
+        self.count_within_radius(query, radius) >= n
+    }
 }
 
 /// A neighbor point.
@@ -1,7 +1,8 @@
+use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::ops::{AddAssign, DivAssign, MulAssign, RemAssign, SubAssign};
 use num_traits::real::Real;
 use num_traits::Num;
-use std::fmt::Debug;
-use std::ops::{AddAssign, DivAssign, MulAssign, RemAssign, SubAssign};
 
 /// Trait for clamp operation.
 pub trait Clamp {
@@ -39,6 +40,33 @@ pub trait Number:
     /// Create value of self type from an usize number.
     #[must_use]
     fn from_usize(n: usize) -> Self;
+
+    /// Create value of self type from an u64 number if it is exactly representable.
+    ///
+    /// The default implementation is meant for binary floating-point types. An odd number is
+    /// representable only if its predecessor converts to one less, and a multiple of it by a power
+    /// of two is representable unless it overflows. Integer types should override it.
+    #[must_use]
+    fn try_from_u64(n: u64) -> Option<Self> {
+        if n == 0 {
+            return Some(Self::zero());
+        }
+        let shift = n.trailing_zeros();
+        let odd = n >> shift;
+        if odd > 1 && Self::from_u64(odd) - Self::from_u64(odd - 1) != Self::one() {
+            return None;
+        }
+        let value = Self::from_u64(n);
+        let two = Self::one() + Self::one();
+        let halved = (0..shift).fold(value, |value, _| value / two);
+        (halved == Self::from_u64(odd)).then_some(value)
+    }
+
+    /// Create value of self type from an usize number if it is exactly representable.
+    #[must_use]
+    fn try_from_usize(n: usize) -> Option<Self> {
+        u64::try_from(n).ok().and_then(Self::try_from_u64)
+    }
 }
 
 /// Trait for float number.
@@ -50,6 +78,24 @@ pub trait Float: Number + Real {
     /// Create value of self type from an f64 number.
     #[must_use]
     fn from_f64(n: f64) -> Self;
+
+    /// Return whether this value is NaN.
+    #[must_use]
+    fn is_nan(self) -> bool;
+
+    /// Compare this value with the other one in a total order.
+    ///
+    /// NaN is treated as equal to itself and greater than any other value, so that it is always
+    /// sorted last instead of corrupting the ordering.
+    #[must_use]
+    fn total_order(&self, other: &Self) -> Ordering {
+        match (self.is_nan(), other.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => self.partial_cmp(other).unwrap_or(Ordering::Equal),
+        }
+    }
 }
 
 macro_rules! impl_clamp {
@@ -71,7 +117,7 @@ macro_rules! impl_clamp {
 }
 
 macro_rules! impl_number {
-    ($number:ty) => {
+    ($number:ty $(, $checked:ident)?) => {
         impl Number for $number {
             #[inline]
             fn from_u8(n: u8) -> Self {
@@ -92,6 +138,33 @@ macro_rules! impl_number {
             fn from_usize(n: usize) -> Self {
                 n as $number
             }
+
+            $($checked!($number);)?
+        }
+    };
+}
+
+macro_rules! impl_checked_integer {
+    ($number:ty) => {
+        #[inline]
+        fn try_from_u64(n: u64) -> Option<Self> {
+            let value = n as $number;
+            // Compare in u128 because casting back to u64 saturates.
+            if value as u128 == n as u128 {
+                Some(value)
+            } else {
+                None
+            }
+        }
+
+        #[inline]
+        fn try_from_usize(n: usize) -> Option<Self> {
+            let value = n as $number;
+            if value as u128 == n as u128 {
+                Some(value)
+            } else {
+                None
+            }
         }
     };
 }
@@ -108,6 +181,11 @@ macro_rules! impl_float {
             fn from_f64(n: f64) -> Self {
                 n as $number
             }
+
+            #[inline]
+            fn is_nan(self) -> bool {
+                <$number>::is_nan(self)
+            }
         }
     };
 }
@@ -120,13 +198,59 @@ impl_clamp!(u128);
 impl_clamp!(f32);
 impl_clamp!(f64);
 
-impl_number!(u8);
-impl_number!(u16);
-impl_number!(u32);
-impl_number!(u64);
-impl_number!(u128);
+impl_number!(u8, impl_checked_integer);
+impl_number!(u16, impl_checked_integer);
+impl_number!(u32, impl_checked_integer);
+impl_number!(u64, impl_checked_integer);
+impl_number!(u128, impl_checked_integer);
 impl_number!(f32);
 impl_number!(f64);
 
 impl_float!(f32);
 impl_float!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn try_from_u64_should_return_value_if_representable() {
+        assert_eq!(u8::try_from_u64(255), Some(255));
+        assert_eq!(u32::try_from_u64(u32::MAX as u64), Some(u32::MAX));
+        assert_eq!(u64::try_from_u64(u64::MAX), Some(u64::MAX));
+        assert_eq!(f32::try_from_u64(0), Some(0.0));
+        assert_eq!(f32::try_from_u64(1 << 24), Some(16_777_216.0));
+        assert_eq!(f32::try_from_u64(3 << 40), Some(3.0 * 2.0_f32.powi(40)));
+        assert_eq!(f64::try_from_u64(1 << 53), Some(9_007_199_254_740_992.0));
+    }
+
+    #[test]
+    fn try_from_u64_should_return_none_if_not_representable() {
+        assert_eq!(u8::try_from_u64(256), None);
+        assert_eq!(u32::try_from_u64(u32::MAX as u64 + 1), None);
+        assert_eq!(f32::try_from_u64((1 << 24) + 1), None);
+        assert_eq!(f32::try_from_u64((1 << 25) + 2), None);
+        assert_eq!(f32::try_from_u64(u64::MAX), None);
+        assert_eq!(f64::try_from_u64((1 << 53) + 1), None);
+    }
+
+    #[test]
+    fn total_order_should_sort_nan_last() {
+        let mut values = [f64::NAN, 2.0, f64::INFINITY, -1.0, f64::NAN, 0.5];
+        values.sort_by(|a, b| a.total_order(b));
+        assert_eq!(&values[..4], &[-1.0, 0.5, 2.0, f64::INFINITY]);
+        assert!(values[4].is_nan());
+        assert!(values[5].is_nan());
+
+        assert_eq!(1.0_f32.total_order(&1.0), Ordering::Equal);
+        assert_eq!(f32::NAN.total_order(&f32::NAN), Ordering::Equal);
+    }
+
+    #[test]
+    fn try_from_usize_should_check_representable_range() {
+        assert_eq!(u16::try_from_usize(65_535), Some(65_535));
+        assert_eq!(u16::try_from_usize(65_536), None);
+        assert_eq!(f32::try_from_usize(1 << 24), Some(16_777_216.0));
+        assert_eq!(f32::try_from_usize((1 << 24) + 1), None);
+    }
+}
@@ -1,7 +1,10 @@
+use crate::math::distance::metric::DistanceMetric;
 use crate::math::number::Float;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter, Result};
+use core::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Sub, SubAssign};
 use num_traits::Zero;
-use std::fmt::{Debug, Display, Formatter, Result};
-use std::ops::{Add, AddAssign, Div, DivAssign, Index, Mul, MulAssign, Sub, SubAssign};
 
 /// Point in n-dimensional space.
 pub trait Point<F: Float>:
@@ -24,6 +27,61 @@ pub trait Point<F: Float>:
 
     /// Return the vec representation of this point.
     fn to_vec(&self) -> Vec<F>;
+
+    /// Return the value at the given index, or `None` if the index is out of bounds.
+    #[inline]
+    #[must_use]
+    fn get(&self, index: usize) -> Option<F> {
+        (index < self.dim()).then(|| self[index])
+    }
+
+    /// Return a copy of this point keeping the values on the given axes and zeroing the others.
+    ///
+    /// Axes out of bounds are ignored.
+    #[must_use]
+    fn project(&self, axes: &[usize]) -> Self
+    where
+        Self: IndexMut<usize, Output = F>,
+    {
+        let mut projected = Self::zero();
+        for &axis in axes.iter().filter(|&&axis| axis < self.dim()) {
+            projected[axis] = self[axis];
+        }
+        projected
+    }
+
+    /// Create a point whose value on each axis is returned by the given function of the axis.
+    #[must_use]
+    fn from_fn(mut f: impl FnMut(usize) -> F) -> Self
+    where
+        Self: IndexMut<usize, Output = F>,
+    {
+        let mut point = Self::zero();
+        for axis in 0..point.dim() {
+            point[axis] = f(axis);
+        }
+        point
+    }
+
+    /// Return the mean of the given points, or `None` if the slice is empty.
+    #[must_use]
+    fn centroid(points: &[Self]) -> Option<Self> {
+        if points.is_empty() {
+            return None;
+        }
+        let mut centroid = points
+            .iter()
+            .fold(Self::zero(), |total, point| total + *point);
+        centroid.div_assign(F::from_usize(points.len()));
+        Some(centroid)
+    }
+
+    /// Compute the distance to the other point with the given metric.
+    #[inline]
+    #[must_use]
+    fn distance_to(&self, other: &Self, metric: &DistanceMetric) -> F {
+        metric.measure(self, other)
+    }
 }
 
 /// Point in 2-dimensional space.
@@ -90,6 +148,37 @@ where
     }
 }
 
+/// Point in linear RGB color space with alpha.
+///
+/// Arithmetic on this point is performed in linear light, so a centroid of gamma-encoded sRGB colors
+/// is not biased toward dark colors.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct LinearRgbPoint<F: Float>(pub F, pub F, pub F, pub F);
+
+impl<F> Index<usize> for LinearRgbPoint<F>
+where
+    F: Float,
+{
+    type Output = F;
+
+    #[inline]
+    fn index(&self, index: usize) -> &Self::Output {
+        match index {
+            0 => &self.0,
+            1 => &self.1,
+            2 => &self.2,
+            3 => &self.3,
+            _ => panic!("Index out of bounds"),
+        }
+    }
+}
+
+macro_rules! replace_type {
+    ($_field:tt, $T:ty) => {
+        $T
+    };
+}
+
 macro_rules! impl_point {
   ($Point:ident { $($label:tt: $field:tt),+ }, $size:expr) => {
     impl<F> $Point<F> where F: Float {
@@ -101,6 +190,30 @@ macro_rules! impl_point {
         }
     }
 
+    impl<F> From<[F; $size]> for $Point<F> where F: Float {
+        #[inline]
+        fn from(values: [F; $size]) -> Self {
+            Self { $($field: values[$field]),+ }
+        }
+    }
+
+    impl<F> From<($(replace_type!($field, F)),+)> for $Point<F> where F: Float {
+        #[inline]
+        fn from(values: ($(replace_type!($field, F)),+)) -> Self {
+            Self { $($field: values.$field),+ }
+        }
+    }
+
+    impl<F> IndexMut<usize> for $Point<F> where F: Float {
+        #[inline]
+        fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+            match index {
+                $($field => &mut self.$field,)+
+                _ => panic!("Index out of bounds"),
+            }
+        }
+    }
+
     impl<F> Display for $Point<F> where F: Float + Display {
         fn fmt(&self, f: &mut Formatter<'_>) -> Result {
             write!(f, "{}{:?}", stringify!($Point), ($(self.$field),+))
@@ -204,6 +317,15 @@ macro_rules! impl_point {
 
 impl_point!(Point2 { x: 0, y: 1 }, 2);
 impl_point!(Point3 { x: 0, y: 1, z: 2 }, 3);
+impl_point!(
+    LinearRgbPoint {
+        r: 0,
+        g: 1,
+        b: 2,
+        a: 3
+    },
+    4
+);
 impl_point!(
     Point5 {
         v: 0,
@@ -238,6 +360,26 @@ mod tests {
         assert_eq!(*point5.index(4), 5.0);
     }
 
+    #[test]
+    fn from_should_convert_array_and_tuple() {
+        assert_eq!(Point2::from([1.0, 2.0]), Point2::new(1.0, 2.0));
+        assert_eq!(Point2::from((1.0, 2.0)), Point2::new(1.0, 2.0));
+
+        assert_eq!(Point3::from([1.0, 2.0, 3.0]), Point3::new(1.0, 2.0, 3.0));
+        assert_eq!(Point3::from((1.0, 2.0, 3.0)), Point3::new(1.0, 2.0, 3.0));
+
+        let expected = Point5::new(1.0, 2.0, 3.0, 4.0, 5.0);
+        assert_eq!(Point5::from([1.0, 2.0, 3.0, 4.0, 5.0]), expected);
+        assert_eq!(Point5::from((1.0, 2.0, 3.0, 4.0, 5.0)), expected);
+
+        let expected = LinearRgbPoint::new(0.1, 0.2, 0.3, 1.0);
+        assert_eq!(LinearRgbPoint::from([0.1, 0.2, 0.3, 1.0]), expected);
+        assert_eq!(LinearRgbPoint::from((0.1, 0.2, 0.3, 1.0)), expected);
+
+        let point: Point2<f32> = [3.0, 4.0].into();
+        assert_eq!(point, Point2(3.0, 4.0));
+    }
+
     #[test]
     fn dim_should_return_dimension() {
         assert_eq!(Point2::new(1.0, 2.0).dim(), 2);
@@ -250,6 +392,79 @@ mod tests {
         assert_eq!(Point3::new(1.0, 2.0, 3.0).to_vec(), vec![1.0, 2.0, 3.0]);
     }
 
+    #[test]
+    fn get_should_return_value_within_bounds() {
+        let point2 = Point2::new(1.0, 2.0);
+        assert_eq!(point2.get(0), Some(1.0));
+        assert_eq!(point2.get(1), Some(2.0));
+        assert_eq!(point2.get(point2.dim()), None);
+
+        let point3 = Point3::new(1.0, 2.0, 3.0);
+        assert_eq!(point3.get(2), Some(3.0));
+        assert_eq!(point3.get(point3.dim()), None);
+
+        let point5 = Point5::new(1.0, 2.0, 3.0, 4.0, 5.0);
+        assert_eq!(point5.get(4), Some(5.0));
+        assert_eq!(point5.get(point5.dim()), None);
+        assert_eq!(point5.get(usize::MAX), None);
+    }
+
+    #[test]
+    fn project_should_zero_other_axes() {
+        let point5 = Point5::new(1.0, 2.0, 3.0, 4.0, 5.0);
+        assert_eq!(
+            point5.project(&[0, 1, 2]),
+            Point5::new(1.0, 2.0, 3.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            point5.project(&[4, 7]),
+            Point5::new(0.0, 0.0, 0.0, 0.0, 5.0)
+        );
+        assert_eq!(point5.project(&[]), Point5::zero());
+    }
+
+    #[test]
+    fn from_fn_should_create_point_from_axes() {
+        let point2: Point2<f64> = Point::from_fn(|axis| axis as f64 + 0.5);
+        assert_eq!(point2, Point2::new(0.5, 1.5));
+
+        let point5: Point5<f64> = Point::from_fn(|axis| (axis * axis) as f64);
+        assert_eq!(point5, Point5::new(0.0, 1.0, 4.0, 9.0, 16.0));
+    }
+
+    #[test]
+    fn centroid_should_return_mean_of_points() {
+        let square = [
+            Point2(0.0, 0.0),
+            Point2(4.0, 0.0),
+            Point2(0.0, 4.0),
+            Point2(4.0, 4.0),
+        ];
+        assert_eq!(Point2::centroid(&square), Some(Point2(2.0, 2.0)));
+        assert_eq!(Point3::<f64>::centroid(&[]), None);
+    }
+
+    #[test]
+    fn distance_to_should_match_metric() {
+        let metrics = [
+            DistanceMetric::Euclidean,
+            DistanceMetric::SquaredEuclidean,
+            DistanceMetric::Manhattan,
+            DistanceMetric::Chebyshev,
+        ];
+        for metric in metrics {
+            let (lhs, rhs) = (Point2::new(0.0, 1.0), Point2::new(3.0, 5.0));
+            assert_eq!(lhs.distance_to(&rhs, &metric), metric.measure(&lhs, &rhs));
+
+            let (lhs, rhs) = (Point3::new(0.0, 1.0, 2.0), Point3::new(1.0, 3.0, -1.0));
+            assert_eq!(lhs.distance_to(&rhs, &metric), metric.measure(&lhs, &rhs));
+        }
+        assert_eq!(
+            Point2::new(0.0, 0.0).distance_to(&Point2::new(3.0, 4.0), &DistanceMetric::Euclidean),
+            5.0
+        );
+    }
+
     #[test]
     fn to_string_should_return_string_representation() {
         assert_eq!(Point2::new(1.0, 2.0).to_string(), "Point2(1.0, 2.0)");
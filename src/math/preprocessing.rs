@@ -0,0 +1,242 @@
+use crate::math::number::Float;
+use crate::math::point::Point;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::ops::IndexMut;
+
+/// Scaler normalizing each axis of points into [0, 1] by the range of a dataset.
+///
+/// An axis whose values are all the same is mapped to 0, and mapped back to its single value by
+/// [`inverse_transform`](Self::inverse_transform).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinMaxScaler<F, P>
+where
+    F: Float,
+    P: Point<F> + IndexMut<usize, Output = F>,
+{
+    min: P,
+    max: P,
+    _t: PhantomData<F>,
+}
+
+impl<F, P> MinMaxScaler<F, P>
+where
+    F: Float,
+    P: Point<F> + IndexMut<usize, Output = F>,
+{
+    /// Compute the minimum and maximum of each axis of the given dataset.
+    ///
+    /// Returns `None` if the dataset is empty.
+    #[must_use]
+    pub fn fit(dataset: &[P]) -> Option<Self> {
+        let first = dataset.first()?;
+        let (min, max) = dataset.iter().fold((*first, *first), |(min, max), point| {
+            (
+                P::from_fn(|axis| min[axis].min(point[axis])),
+                P::from_fn(|axis| max[axis].max(point[axis])),
+            )
+        });
+        Some(Self {
+            min,
+            max,
+            _t: PhantomData,
+        })
+    }
+
+    /// Return the minimum of each axis as a point.
+    #[must_use]
+    pub fn min(&self) -> &P {
+        &self.min
+    }
+
+    /// Return the maximum of each axis as a point.
+    #[must_use]
+    pub fn max(&self) -> &P {
+        &self.max
+    }
+
+    /// Scale the given point so that each axis of the fitted dataset lies in [0, 1].
+    #[must_use]
+    pub fn transform(&self, point: &P) -> P {
+        P::from_fn(|axis| {
+            let range = self.range(axis);
+            if range.is_zero() {
+                F::zero()
+            } else {
+                (point[axis] - self.min[axis]) / range
+            }
+        })
+    }
+
+    /// Scale each of the given points as [`transform`](Self::transform) does.
+    #[must_use]
+    pub fn transform_all(&self, dataset: &[P]) -> Vec<P> {
+        dataset.iter().map(|point| self.transform(point)).collect()
+    }
+
+    /// Recover the original coordinates of the given scaled point.
+    #[must_use]
+    pub fn inverse_transform(&self, point: &P) -> P {
+        P::from_fn(|axis| point[axis] * self.range(axis) + self.min[axis])
+    }
+
+    #[inline]
+    #[must_use]
+    fn range(&self, axis: usize) -> F {
+        self.max[axis] - self.min[axis]
+    }
+}
+
+/// Scaler standardizing each axis of points to zero mean and unit variance over a dataset.
+///
+/// The variance is that of the population. An axis whose values are all the same is mapped to 0,
+/// and mapped back to its mean by [`inverse_transform`](Self::inverse_transform).
+#[derive(Debug, Clone, PartialEq)]
+pub struct StandardScaler<F, P>
+where
+    F: Float,
+    P: Point<F> + IndexMut<usize, Output = F>,
+{
+    mean: P,
+    std_dev: P,
+    _t: PhantomData<F>,
+}
+
+impl<F, P> StandardScaler<F, P>
+where
+    F: Float,
+    P: Point<F> + IndexMut<usize, Output = F>,
+{
+    /// Compute the mean and standard deviation of each axis of the given dataset.
+    ///
+    /// Returns `None` if the dataset is empty.
+    #[must_use]
+    pub fn fit(dataset: &[P]) -> Option<Self> {
+        let mean = P::centroid(dataset)?;
+        let count = F::from_usize(dataset.len());
+        let std_dev = P::from_fn(|axis| {
+            let variance = dataset.iter().fold(F::zero(), |total, point| {
+                total + (point[axis] - mean[axis]).powi(2)
+            }) / count;
+            variance.sqrt()
+        });
+        Some(Self {
+            mean,
+            std_dev,
+            _t: PhantomData,
+        })
+    }
+
+    /// Return the mean of each axis as a point.
+    #[must_use]
+    pub fn mean(&self) -> &P {
+        &self.mean
+    }
+
+    /// Return the standard deviation of each axis as a point.
+    #[must_use]
+    pub fn std_dev(&self) -> &P {
+        &self.std_dev
+    }
+
+    /// Standardize the given point by the mean and standard deviation of the fitted dataset.
+    #[must_use]
+    pub fn transform(&self, point: &P) -> P {
+        P::from_fn(|axis| {
+            let std_dev = self.std_dev[axis];
+            if std_dev.is_zero() {
+                F::zero()
+            } else {
+                (point[axis] - self.mean[axis]) / std_dev
+            }
+        })
+    }
+
+    /// Standardize each of the given points as [`transform`](Self::transform) does.
+    #[must_use]
+    pub fn transform_all(&self, dataset: &[P]) -> Vec<P> {
+        dataset.iter().map(|point| self.transform(point)).collect()
+    }
+
+    /// Recover the original coordinates of the given standardized point.
+    #[must_use]
+    pub fn inverse_transform(&self, point: &P) -> P {
+        P::from_fn(|axis| point[axis] * self.std_dev[axis] + self.mean[axis])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::point::{Point2, Point3};
+
+    #[test]
+    fn fit_should_compute_range_of_each_axis() {
+        let dataset = [
+            Point3::new(1.0, -2.0, 5.0),
+            Point3::new(3.0, 4.0, 5.0),
+            Point3::new(-1.0, 0.0, 5.0),
+        ];
+        let scaler = MinMaxScaler::fit(&dataset).unwrap();
+        assert_eq!(scaler.min(), &Point3::new(-1.0, -2.0, 5.0));
+        assert_eq!(scaler.max(), &Point3::new(3.0, 4.0, 5.0));
+        assert_eq!(
+            scaler.transform_all(&dataset),
+            vec![
+                Point3::new(0.5, 0.0, 0.0),
+                Point3::new(1.0, 1.0, 0.0),
+                Point3::new(0.0, 1.0 / 3.0, 0.0),
+            ]
+        );
+        assert_eq!(MinMaxScaler::<f64, Point2<f64>>::fit(&[]), None);
+    }
+
+    #[test]
+    fn inverse_transform_should_recover_original_points() {
+        let dataset: Vec<Point3<f64>> = (0..32)
+            .map(|i| {
+                let i = i as f64;
+                Point3::new(i * 3.1 - 40.0, (i * 0.7).sin() * 100.0, 7.0)
+            })
+            .collect();
+        let scaler = MinMaxScaler::fit(&dataset).unwrap();
+        for point in &dataset {
+            let scaled = scaler.transform(point);
+            assert!((0..3).all(|axis| (0.0..=1.0).contains(&scaled[axis])));
+
+            let recovered = scaler.inverse_transform(&scaled);
+            assert!((0..3).all(|axis| (recovered[axis] - point[axis]).abs() < 1e-9));
+        }
+    }
+
+    #[test]
+    fn standard_scaler_should_produce_zero_mean_and_unit_variance() {
+        let dataset: Vec<Point3<f64>> = (0..100)
+            .map(|i| {
+                let i = i as f64;
+                Point3::new(i * 0.5 + 20.0, (i * 1.3).cos() * 0.01, 3.0)
+            })
+            .collect();
+        let scaler = StandardScaler::fit(&dataset).unwrap();
+        assert_eq!(scaler.std_dev()[2], 0.0);
+
+        let scaled = scaler.transform_all(&dataset);
+        for axis in 0..2 {
+            let mean = scaled.iter().map(|point| point[axis]).sum::<f64>() / 100.0;
+            let variance = scaled
+                .iter()
+                .map(|point| (point[axis] - mean).powi(2))
+                .sum::<f64>()
+                / 100.0;
+            assert!(mean.abs() < 1e-9);
+            assert!((variance - 1.0).abs() < 1e-9);
+        }
+        assert!(scaled.iter().all(|point| point[2] == 0.0));
+
+        for (point, scaled) in dataset.iter().zip(&scaled) {
+            let recovered = scaler.inverse_transform(scaled);
+            assert!((0..3).all(|axis| (recovered[axis] - point[axis]).abs() < 1e-9));
+        }
+        assert_eq!(StandardScaler::<f64, Point2<f64>>::fit(&[]), None);
+    }
+}
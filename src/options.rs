@@ -0,0 +1,332 @@
+use crate::math::clustering::dbscan::params::Params;
+use crate::math::number::Float;
+
+/// Options of palette extraction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractOptions<F>
+where
+    F: Float,
+{
+    min_points: Option<usize>,
+    epsilon: Option<F>,
+    equalize: bool,
+    smoothing: F,
+    alpha_weighted: bool,
+    vibrance: F,
+    downscale: u32,
+    seed: Option<u64>,
+}
+
+impl<F> ExtractOptions<F>
+where
+    F: Float,
+{
+    /// Create a new ExtractOptions with default values.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            min_points: None,
+            epsilon: None,
+            equalize: false,
+            smoothing: F::zero(),
+            alpha_weighted: false,
+            vibrance: F::zero(),
+            downscale: 1,
+            seed: None,
+        }
+    }
+
+    /// Create options for extracting vivid colors.
+    ///
+    /// Saturated pixels count more toward the density, so small accents of saturated colors form
+    /// swatches while small patches of dull colors are still discarded as noise.
+    #[must_use]
+    pub fn vivid() -> Self {
+        Self::new().with_vibrance(F::from_f64(2.0))
+    }
+
+    /// Create options for extracting pastel colors.
+    ///
+    /// Pastel images are light and low in contrast, so the lightness is equalized to separate the
+    /// similar colors.
+    #[must_use]
+    pub fn pastel() -> Self {
+        Self::new().with_equalize(true)
+    }
+
+    /// Create options for extracting muted colors.
+    ///
+    /// Muted colors vary little, so the pixels are smoothed and clustered with a larger
+    /// neighborhood radius to avoid splitting a color into near duplicates.
+    #[must_use]
+    pub fn muted() -> Self {
+        Self::new()
+            .with_smoothing(F::from_f64(0.02))
+            .with_epsilon(F::from_f64(0.03))
+    }
+
+    /// Create options for extracting colors from photographs.
+    ///
+    /// Light smoothing keeps noise and texture from fragmenting regions into many swatches, while
+    /// the recommended radius keeps the many colors of a photograph apart.
+    #[must_use]
+    pub fn photo() -> Self {
+        Self::new().with_smoothing(F::from_f64(0.01))
+    }
+
+    /// Create options for extracting the few flat colors of a logo.
+    ///
+    /// A large neighborhood radius merges anti-aliased edges into the flat colors, more pixels
+    /// are required to form a swatch, and each pixel is weighted by its alpha so that transparent
+    /// backgrounds do not count.
+    #[must_use]
+    pub fn logo() -> Self {
+        Self::new()
+            .with_epsilon(F::from_f64(0.05))
+            .with_min_points(50)
+            .with_alpha_weighted(true)
+    }
+
+    /// Set the minimum number of pixels required to form a swatch.
+    ///
+    /// If not set, the number recommended for the dimension and the number of the clustered points
+    /// is used, which is 25 for images of up to 1,000,000 pixels and is raised by the square root
+    /// of the ratio of pixels for larger images.
+    #[must_use]
+    pub fn with_min_points(mut self, min_points: usize) -> Self {
+        self.min_points = Some(min_points);
+        self
+    }
+
+    /// Set the neighborhood radius in the normalized color and position space.
+    ///
+    /// If not set, the radius recommended for the dimension of the clustered points is used, which
+    /// is 0.025 for the color and position space.
+    #[must_use]
+    pub fn with_epsilon(mut self, epsilon: F) -> Self {
+        self.epsilon = Some(epsilon);
+        self
+    }
+
+    /// Set whether to equalize the lightness histogram before clustering.
+    ///
+    /// Equalization spreads the lightness of low-contrast images to separate similar colors, while
+    /// the swatch colors are still computed from the original pixels.
+    #[must_use]
+    pub fn with_equalize(mut self, equalize: bool) -> Self {
+        self.equalize = equalize;
+        self
+    }
+
+    /// Set the radius of the smoothing applied to the pixels before clustering.
+    ///
+    /// Each pixel is clustered by the mean color of the pixels within the radius in the normalized
+    /// color and position space, so small color variations of textured regions do not fragment
+    /// them into many swatches. A larger radius smooths more strongly, and zero disables the
+    /// smoothing.
+    #[must_use]
+    pub fn with_smoothing(mut self, radius: F) -> Self {
+        self.smoothing = radius;
+        self
+    }
+
+    /// Set whether to weight each pixel by its alpha.
+    ///
+    /// Semi-transparent pixels, such as the anti-aliased edges of a logo, then count less toward
+    /// the density, the percentage and the color of a swatch, and fully transparent pixels do not
+    /// count at all.
+    #[must_use]
+    pub fn with_alpha_weighted(mut self, alpha_weighted: bool) -> Self {
+        self.alpha_weighted = alpha_weighted;
+        self
+    }
+
+    /// Set the vibrance weighting, by which saturated pixels count more toward the density.
+    ///
+    /// Each pixel counts `1 + vibrance * chroma` times toward the density, where the chroma is
+    /// measured in the normalized color space, so a pure red pixel counts about twice with a
+    /// vibrance of 2.5. The percentages and the colors of the swatches are not affected, and zero
+    /// disables the weighting.
+    #[must_use]
+    pub fn with_vibrance(mut self, vibrance: F) -> Self {
+        self.vibrance = vibrance.max(F::zero());
+        self
+    }
+
+    /// Set the factor by which the image is downscaled before clustering.
+    ///
+    /// Each block of `factor` x `factor` pixels is averaged into a single pixel of a thumbnail,
+    /// which is much faster to cluster for large images, while the swatch positions are still
+    /// reported in the coordinates of the original image. A factor of 0 or 1 disables downscaling.
+    /// The positions are normalized by the size of the thumbnail, so the epsilon should exceed
+    /// the spacing between its pixels.
+    #[must_use]
+    pub fn with_downscale(mut self, factor: u32) -> Self {
+        self.downscale = factor.max(1);
+        self
+    }
+
+    /// Set the seed of the random number generator used by randomized extraction.
+    ///
+    /// The default extraction is deterministic, so the seed only pins the results of randomized
+    /// extraction modes, such as
+    /// [`ImageData::extract_kmeans`](crate::image::ImageData::extract_kmeans), for reproducible
+    /// snapshot testing.
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Return the minimum number of pixels required to form a swatch if set.
+    #[must_use]
+    pub fn min_points(&self) -> Option<usize> {
+        self.min_points
+    }
+
+    /// Return the neighborhood radius if set.
+    #[must_use]
+    pub fn epsilon(&self) -> Option<F> {
+        self.epsilon
+    }
+
+    /// Return the DBSCAN parameters for the given number of points of the given dimension.
+    ///
+    /// The parameters not set by the user are filled in by [`Params::recommended`].
+    #[must_use]
+    pub(crate) fn params(&self, dim: usize, dataset_len: usize) -> Params<F> {
+        let recommended = Params::recommended(dim, dataset_len);
+        Params::new(
+            self.min_points.unwrap_or(recommended.min_points()),
+            self.epsilon.unwrap_or(recommended.epsilon()),
+            *recommended.metric(),
+        )
+    }
+
+    /// Return whether to equalize the lightness histogram before clustering.
+    #[must_use]
+    pub fn equalize(&self) -> bool {
+        self.equalize
+    }
+
+    /// Return the radius of the smoothing applied before clustering.
+    #[must_use]
+    pub fn smoothing(&self) -> F {
+        self.smoothing
+    }
+
+    /// Return whether to weight each pixel by its alpha.
+    #[must_use]
+    pub fn alpha_weighted(&self) -> bool {
+        self.alpha_weighted
+    }
+
+    /// Return the vibrance weighting of the density.
+    #[must_use]
+    pub fn vibrance(&self) -> F {
+        self.vibrance
+    }
+
+    /// Return the factor by which the image is downscaled before clustering.
+    #[must_use]
+    pub fn downscale(&self) -> u32 {
+        self.downscale
+    }
+
+    /// Return the seed of the random number generator if set.
+    #[must_use]
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+}
+
+impl<F> Default for ExtractOptions<F>
+where
+    F: Float,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_should_create_default_options() {
+        let options: ExtractOptions<f64> = ExtractOptions::new();
+        assert_eq!(options.min_points(), None);
+        assert_eq!(options.epsilon(), None);
+        assert!(!options.equalize());
+        assert_eq!(options.smoothing(), 0.0);
+        assert!(!options.alpha_weighted());
+        assert_eq!(options.vibrance(), 0.0);
+        assert_eq!(options.downscale(), 1);
+        assert_eq!(options.seed(), None);
+        assert_eq!(options, ExtractOptions::default());
+    }
+
+    #[test]
+    fn with_should_override_options() {
+        let options = ExtractOptions::new()
+            .with_min_points(16)
+            .with_epsilon(0.05)
+            .with_equalize(true)
+            .with_smoothing(0.1)
+            .with_alpha_weighted(true)
+            .with_vibrance(1.5)
+            .with_downscale(4)
+            .with_seed(42);
+        assert_eq!(options.min_points(), Some(16));
+        assert_eq!(options.epsilon(), Some(0.05));
+        assert!(options.equalize());
+        assert_eq!(options.smoothing(), 0.1);
+        assert!(options.alpha_weighted());
+        assert_eq!(options.vibrance(), 1.5);
+        assert_eq!(options.downscale(), 4);
+        assert_eq!(options.seed(), Some(42));
+        assert_eq!(
+            ExtractOptions::<f64>::new().with_downscale(0).downscale(),
+            1
+        );
+        assert_eq!(
+            ExtractOptions::<f64>::new().with_vibrance(-1.0).vibrance(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn presets_should_create_valid_params() {
+        for options in [
+            ExtractOptions::<f64>::vivid(),
+            ExtractOptions::pastel(),
+            ExtractOptions::muted(),
+            ExtractOptions::photo(),
+            ExtractOptions::logo(),
+        ] {
+            assert_ne!(options, ExtractOptions::new());
+            let params = options.params(5, 10_000);
+            assert!(params.min_points() > 0);
+            assert!(params.epsilon() > 0.0 && params.epsilon() < 1.0);
+            assert!(options.smoothing() >= 0.0);
+            assert_eq!(options.downscale(), 1);
+        }
+    }
+
+    #[test]
+    fn params_should_fill_in_recommended_values() {
+        let params = ExtractOptions::<f64>::new().params(5, 10_000);
+        assert_eq!(params, Params::recommended(5, 10_000));
+
+        let params = ExtractOptions::<f64>::new()
+            .with_min_points(16)
+            .params(5, 10_000);
+        assert_eq!(params.min_points(), 16);
+        assert_eq!(params.epsilon(), 0.025);
+
+        let params = ExtractOptions::new().with_epsilon(0.05).params(3, 10_000);
+        assert_eq!(params.min_points(), 15);
+        assert_eq!(params.epsilon(), 0.05);
+    }
+}
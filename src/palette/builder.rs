@@ -0,0 +1,337 @@
+use crate::color::lab::Lab;
+use crate::color::rgba::Rgba;
+use crate::color::white_point::D65;
+use crate::color::xyz::XYZ;
+use crate::math::clustering::dbscan::algorithm::DBSCAN;
+use crate::math::clustering::dbscan::params::Params;
+use crate::math::clustering::traits::Fit;
+use crate::math::distance::metric::DistanceMetric;
+use crate::math::number::Float;
+use crate::math::point::{Point3, Point5};
+use crate::options::ExtractOptions;
+use crate::palette::Palette;
+use crate::swatch::Swatch;
+use num_traits::Zero;
+use std::collections::BTreeMap;
+
+/// Builder extracting a palette from pixels fed incrementally.
+///
+/// Pixels are accumulated into a color histogram whose bins are quantized to 5 bits per channel,
+/// so the memory usage is bounded regardless of the image size. Each bin keeps the sums of the
+/// exact colors and positions of its pixels, and the bins are grouped by color only when the
+/// palette is built.
+///
+/// # Examples
+/// ```
+/// use auto_palette::color::Rgba;
+/// use auto_palette::palette::builder::PaletteBuilder;
+/// use auto_palette::palette::Palette;
+///
+/// let mut builder = PaletteBuilder::new(2, 1);
+/// builder.add_chunk(&[255, 0, 0, 255]);
+/// builder.add_pixel(1, 0, Rgba::new(0, 0, 255, 255));
+/// let palette: Palette<f64> = builder.build();
+/// assert_eq!(palette.len(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PaletteBuilder<F: Float> {
+    width: u32,
+    height: u32,
+    cursor: u64,
+    population: usize,
+    bins: BTreeMap<u32, (usize, Point5<F>, F)>,
+}
+
+impl<F> PaletteBuilder<F>
+where
+    F: Float,
+{
+    /// The number of bits per channel used to quantize the histogram bins.
+    const BITS: u32 = 5;
+
+    /// Create a new builder for an image of the given size.
+    #[must_use]
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            cursor: 0,
+            population: 0,
+            bins: BTreeMap::new(),
+        }
+    }
+
+    /// Return the number of pixels added to this builder.
+    #[must_use]
+    pub fn population(&self) -> usize {
+        self.population
+    }
+
+    /// Add the pixel at the given coordinates.
+    pub fn add_pixel(&mut self, x: u32, y: u32, color: Rgba) {
+        let xyz: XYZ<F, D65> = XYZ::from(&color);
+        let Lab { l, a, b, .. } = Lab::from(&xyz);
+        // A coordinate beyond the precision of F is rounded, which is negligible once it is
+        // normalized by the size of the image.
+        let x = F::from_u32(x);
+        let y = F::from_u32(y);
+        let point = Point5::new(
+            l / Self::delta_l(),
+            a / Self::delta_a(),
+            b / Self::delta_b(),
+            x / F::from_u32(self.width),
+            y / F::from_u32(self.height),
+        );
+
+        let shift = 8 - Self::BITS;
+        let key = [color.r, color.g, color.b, color.a]
+            .iter()
+            .fold(0_u32, |key, &channel| {
+                (key << Self::BITS) | u32::from(channel >> shift)
+            });
+        let (count, total, squared) =
+            self.bins
+                .entry(key)
+                .or_insert((0, Point5::zero(), F::zero()));
+        *count += 1;
+        *total += point;
+        *squared += (0..3).fold(F::zero(), |sum, axis| sum + point[axis].powi(2));
+        self.population += 1;
+    }
+
+    /// Add the RGBA bytes of the next pixels in row-major order.
+    ///
+    /// The position of the pixels continues from the end of the previously added chunk.
+    ///
+    /// # Panics
+    /// Panics if the length of the chunk is not a multiple of 4.
+    pub fn add_chunk(&mut self, chunk: &[u8]) {
+        assert!(
+            chunk.len().is_multiple_of(4),
+            "Chunk should contain whole pixels"
+        );
+        let width = u64::from(self.width.max(1));
+        for pixel in chunk.chunks_exact(4) {
+            let x = (self.cursor % width) as u32;
+            let y = (self.cursor / width) as u32;
+            self.add_pixel(x, y, Rgba::new(pixel[0], pixel[1], pixel[2], pixel[3]));
+            self.cursor += 1;
+        }
+    }
+
+    /// Build a palette from the added pixels with the default options.
+    #[must_use]
+    pub fn build(&self) -> Palette<F> {
+        self.build_with(&ExtractOptions::default())
+    }
+
+    /// Build a palette from the added pixels with the given options.
+    ///
+    /// Groups with fewer pixels than the minimum number of points are discarded as noise, unless
+    /// fewer pixels than the minimum number of points were added in total. The discarded pixels are
+    /// reported as the outliers of the palette.
+    #[must_use]
+    pub fn build_with(&self, options: &ExtractOptions<F>) -> Palette<F> {
+        let bins: Vec<&(usize, Point5<F>, F)> = self.bins.values().collect();
+        let colors: Vec<Point3<F>> = bins
+            .iter()
+            .map(|(count, total, _)| {
+                let mean = *total / F::from_usize(*count);
+                Point3::new(mean[0], mean[1], mean[2])
+            })
+            .collect();
+        let epsilon = options.params(3, self.population).epsilon();
+        let params = Params::new(1, epsilon, DistanceMetric::Euclidean);
+        let dbscan = DBSCAN::fit(&colors, &params);
+
+        let min_points = options.params(5, self.population).min_points();
+        let min_points = if self.population < min_points {
+            1
+        } else {
+            min_points
+        };
+        let width_f = F::from_u32(self.width);
+        let height_f = F::from_u32(self.height);
+        let mut outliers = (0, Point5::zero());
+        let mut swatches: Vec<Swatch<F>> = (0..dbscan.centroids().len())
+            .filter_map(|cluster_id| {
+                let (population, total, squared) = dbscan.members_at(cluster_id).iter().fold(
+                    (0, Point5::zero(), F::zero()),
+                    |(population, total, squared), &index| {
+                        let (count, sum, sum_squared) = bins[index];
+                        (population + count, total + *sum, squared + *sum_squared)
+                    },
+                );
+                if population < min_points {
+                    outliers = (outliers.0 + population, outliers.1 + total);
+                    return None;
+                }
+
+                let centroid = total / F::from_usize(population);
+                let color = Self::color_of(&centroid);
+                let x = (centroid[3] * width_f)
+                    .to_u32()
+                    .expect("Width should be converted to u32");
+                let y = (centroid[4] * height_f)
+                    .to_u32()
+                    .expect("Height should be converted to u32");
+                // The variance is the mean of the squared norms minus the squared norm of the mean.
+                let spread = (squared / F::from_usize(population)
+                    - (0..3).fold(F::zero(), |sum, axis| sum + centroid[axis].powi(2)))
+                .max(F::zero());
+                let percentage = F::from_usize(population) / F::from_usize(self.population);
+                Some(Swatch::new(color, (x, y), population, percentage).with_spread(spread))
+            })
+            .collect();
+        swatches.sort();
+
+        let (outlier_population, outlier_total) = outliers;
+        if outlier_population == 0 {
+            return Palette::new(swatches);
+        }
+        let outlier_color = Self::color_of(&(outlier_total / F::from_usize(outlier_population)));
+        Palette::new(swatches).with_outliers(
+            F::from_usize(outlier_population) / F::from_usize(self.population),
+            Some(outlier_color),
+        )
+    }
+
+    /// Convert the normalized color of the given point into RGBA.
+    fn color_of(point: &Point5<F>) -> Rgba {
+        let lab = Lab::new(
+            point[0] * Self::delta_l(),
+            point[1] * Self::delta_a(),
+            point[2] * Self::delta_b(),
+        );
+        Rgba::from(&XYZ::from(&lab))
+    }
+
+    fn delta_l() -> F {
+        Lab::<F>::max_l::<F>() - Lab::<F>::min_l::<F>()
+    }
+
+    fn delta_a() -> F {
+        Lab::<F>::max_a::<F>() - Lab::<F>::min_a::<F>()
+    }
+
+    fn delta_b() -> F {
+        Lab::<F>::max_b::<F>() - Lab::<F>::min_b::<F>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::approx::ApproxEq;
+    use crate::image::ImageData;
+
+    fn banded_image(width: u32, height: u32, bands: &[(u32, (u8, u8, u8))]) -> Vec<u8> {
+        let mut data = Vec::with_capacity((width * height * 4) as usize);
+        for _ in 0..height {
+            for x in 0..width {
+                let &(_, (r, g, b)) = bands
+                    .iter()
+                    .find(|&&(end, _)| x < end)
+                    .unwrap_or(&bands[bands.len() - 1]);
+                data.extend_from_slice(&[r, g, b, 255]);
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn build_should_return_same_palette_as_extract() {
+        let data = banded_image(
+            48,
+            48,
+            &[(8, (255, 0, 0)), (20, (0, 128, 0)), (48, (0, 0, 255))],
+        );
+        let options = ExtractOptions::new().with_min_points(4);
+        let expected: Palette<f64> = ImageData::new(&data, 48, 48).extract_with(&options);
+        assert_eq!(expected.len(), 3);
+
+        let mut builder = PaletteBuilder::new(48, 48);
+        for chunk in data.chunks(4 * 37) {
+            builder.add_chunk(chunk);
+        }
+        assert_eq!(builder.population(), 48 * 48);
+        let palette = builder.build_with(&options);
+        assert_eq!(palette.len(), expected.len());
+        for (actual, expected) in palette.iter().zip(expected.iter()) {
+            assert!(actual.approx_eq(expected, 1e-9));
+        }
+    }
+
+    #[test]
+    fn build_should_group_pixels_added_individually() {
+        let mut builder: PaletteBuilder<f64> = PaletteBuilder::new(4, 4);
+        for y in 0..4 {
+            for x in 0..4 {
+                let color = if x < 3 {
+                    Rgba::new(255, 255, 255, 255)
+                } else {
+                    Rgba::new(0, 0, 0, 255)
+                };
+                builder.add_pixel(x, y, color);
+            }
+        }
+
+        let palette = builder.build();
+        assert_eq!(
+            palette.swatches(),
+            &[
+                Swatch::new(Rgba::new(0, 0, 0, 255), (3, 1), 4, 0.25),
+                Swatch::new(Rgba::new(255, 255, 255, 255), (1, 1), 12, 0.75),
+            ]
+        );
+    }
+
+    #[test]
+    fn build_with_should_report_discarded_pixels_as_outliers() {
+        let mut builder: PaletteBuilder<f64> = PaletteBuilder::new(10, 10);
+        for y in 0..10 {
+            for x in 0..10 {
+                let color = if x == 9 && y < 4 {
+                    Rgba::new(0, 0, 0, 255)
+                } else {
+                    Rgba::new(255, 255, 255, 255)
+                };
+                builder.add_pixel(x, y, color);
+            }
+        }
+
+        let palette = builder.build_with(&ExtractOptions::new().with_min_points(10));
+        assert_eq!(palette.len(), 1);
+        let swatch = &palette.swatches()[0];
+        assert_eq!(swatch.color(), Rgba::new(255, 255, 255, 255));
+        assert_eq!(swatch.population(), 96);
+        assert!((palette.outlier_percentage() - 0.04).abs() < 1e-12);
+        assert_eq!(palette.outlier_color(), Some(Rgba::new(0, 0, 0, 255)));
+
+        let palette = builder.build_with(&ExtractOptions::new().with_min_points(4));
+        assert_eq!(palette.len(), 2);
+        assert_eq!(palette.outlier_percentage(), 0.0);
+        assert_eq!(palette.outlier_color(), None);
+    }
+
+    #[test]
+    fn build_should_return_empty_palette_if_no_pixels_added() {
+        let builder: PaletteBuilder<f64> = PaletteBuilder::new(4, 4);
+        assert!(builder.build().is_empty());
+    }
+
+    #[test]
+    fn add_pixel_should_accept_coordinates_beyond_f32_precision() {
+        let width = (1 << 24) + 2;
+        let mut builder: PaletteBuilder<f32> = PaletteBuilder::new(width, 1);
+        builder.add_pixel(width - 1, 0, Rgba::new(255, 0, 0, 255));
+        assert_eq!(builder.population(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Chunk should contain whole pixels")]
+    fn add_chunk_should_panic_if_chunk_has_partial_pixel() {
+        let mut builder: PaletteBuilder<f64> = PaletteBuilder::new(4, 4);
+        builder.add_chunk(&[255, 0, 0]);
+    }
+}
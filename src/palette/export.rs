@@ -0,0 +1,140 @@
+use crate::math::number::Float;
+use crate::palette::Palette;
+use crate::swatch::Swatch;
+use std::fmt::Write;
+
+/// The number of colors in an Adobe Color Table.
+const ACT_COLORS: usize = 256;
+
+/// Serialize the palette in the GIMP palette format.
+///
+/// Each swatch is written as a row of decimal RGB values followed by its hexadecimal color as
+/// the name, the dominant swatch first.
+#[must_use]
+pub fn to_gpl<F: Float>(palette: &Palette<F>) -> String {
+    let mut gpl = String::from("GIMP Palette\nName: auto-palette\nColumns: 0\n#\n");
+    for swatch in dominant_first(palette) {
+        let color = swatch.color();
+        writeln!(
+            gpl,
+            "{:3} {:3} {:3}\t#{:02x}{:02x}{:02x}",
+            color.r, color.g, color.b, color.r, color.g, color.b
+        )
+        .expect("Writing to a string should not fail");
+    }
+    gpl
+}
+
+/// Serialize the palette in the Adobe Color Table format.
+///
+/// The table always consists of 256 RGB triples written the dominant swatch first, so the least
+/// dominant swatches beyond 256 are dropped and the remaining entries are filled with black.
+#[must_use]
+pub fn to_act<F: Float>(palette: &Palette<F>) -> Vec<u8> {
+    let mut act = vec![0; ACT_COLORS * 3];
+    for (entry, swatch) in act.chunks_exact_mut(3).zip(dominant_first(palette)) {
+        let color = swatch.color();
+        entry.copy_from_slice(&[color.r, color.g, color.b]);
+    }
+    act
+}
+
+/// Serialize the palette as CSS custom properties declared on the `:root` selector.
+///
+/// The properties are named `--color-1`, `--color-2` and so on, the dominant swatch first.
+#[must_use]
+pub fn to_css_variables<F: Float>(palette: &Palette<F>) -> String {
+    let mut css = String::from(":root {\n");
+    for (index, swatch) in dominant_first(palette).into_iter().enumerate() {
+        let color = swatch.color();
+        writeln!(
+            css,
+            "  --color-{}: #{:02x}{:02x}{:02x};",
+            index + 1,
+            color.r,
+            color.g,
+            color.b
+        )
+        .expect("Writing to a string should not fail");
+    }
+    css.push_str("}\n");
+    css
+}
+
+/// Return the swatches of the palette sorted in descending order of percentage.
+///
+/// The sort is stable, so swatches with equal percentages keep their order in the palette.
+#[must_use]
+fn dominant_first<F: Float>(palette: &Palette<F>) -> Vec<&Swatch<F>> {
+    let mut swatches: Vec<&Swatch<F>> = palette.iter().collect();
+    swatches.sort_by(|swatch1, swatch2| swatch2.cmp(swatch1));
+    swatches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::rgba::Rgba;
+    use crate::swatch::Swatch;
+
+    fn palette() -> Palette<f64> {
+        Palette::new(vec![
+            Swatch::new(Rgba::new(0, 16, 255, 255), (0, 0), 10, 0.25),
+            Swatch::new(Rgba::new(255, 128, 0, 255), (0, 0), 30, 0.75),
+        ])
+    }
+
+    #[test]
+    fn to_gpl_should_serialize_gimp_palette() {
+        let gpl = to_gpl(&palette());
+        let lines: Vec<&str> = gpl.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "GIMP Palette",
+                "Name: auto-palette",
+                "Columns: 0",
+                "#",
+                "255 128   0\t#ff8000",
+                "  0  16 255\t#0010ff",
+            ]
+        );
+    }
+
+    #[test]
+    fn to_act_should_serialize_adobe_color_table() {
+        let act = to_act(&palette());
+        assert_eq!(act.len(), 768);
+        assert_eq!(&act[..6], &[255, 128, 0, 0, 16, 255]);
+        assert!(act[6..].iter().all(|&value| value == 0));
+
+        let act = to_act(&Palette::<f64>::new(vec![]));
+        assert_eq!(act.len(), 768);
+    }
+
+    #[test]
+    fn to_act_should_keep_most_dominant_swatches() {
+        // The dominant swatches come last, so a truncation in palette order would drop them.
+        let swatches: Vec<Swatch<f64>> = (0..300_u32)
+            .map(|index| {
+                let value = (index % 256) as u8;
+                let percentage = f64::from(index + 1) / 45_150.0;
+                Swatch::new(Rgba::new(value, value, 0, 255), (0, 0), 1, percentage)
+            })
+            .collect();
+        let act = to_act(&Palette::new(swatches));
+        assert_eq!(act.len(), 768);
+        // The 300th swatch is the most dominant, and swatches 1 to 44 are dropped.
+        assert_eq!(&act[..3], &[43, 43, 0]);
+        assert_eq!(&act[765..], &[44, 44, 0]);
+    }
+
+    #[test]
+    fn to_css_variables_should_serialize_custom_properties() {
+        let css = to_css_variables(&palette());
+        assert_eq!(
+            css,
+            ":root {\n  --color-1: #ff8000;\n  --color-2: #0010ff;\n}\n"
+        );
+    }
+}
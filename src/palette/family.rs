@@ -0,0 +1,117 @@
+use crate::color::lab::Lab;
+use crate::color::lch::Lch;
+use crate::color::rgba::Rgba;
+use crate::color::white_point::D65;
+use crate::color::xyz::XYZ;
+use crate::math::number::Float;
+use crate::palette::Palette;
+
+/// Family of colors sharing a hue, or the neutral colors without a distinct hue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorFamily {
+    /// Reds and pinks.
+    Red,
+    /// Oranges and browns.
+    Orange,
+    /// Yellows.
+    Yellow,
+    /// Greens.
+    Green,
+    /// Cyans and teals.
+    Cyan,
+    /// Blues.
+    Blue,
+    /// Purples and magentas.
+    Purple,
+    /// Achromatic colors such as white, gray and black.
+    Neutral,
+}
+
+impl ColorFamily {
+    /// The upper bounds of the CIE LCh hue angles of the chromatic families in degrees.
+    const HUE_RANGES: [(f64, ColorFamily); 7] = [
+        (50.0, ColorFamily::Red),
+        (80.0, ColorFamily::Orange),
+        (115.0, ColorFamily::Yellow),
+        (170.0, ColorFamily::Green),
+        (230.0, ColorFamily::Cyan),
+        (315.0, ColorFamily::Blue),
+        (345.0, ColorFamily::Purple),
+    ];
+
+    /// Return the family of the given color.
+    ///
+    /// Colors whose CIE LCh chroma is below the achromatic threshold are neutral regardless of
+    /// their hue, and the others are classified by their hue angle.
+    #[must_use]
+    pub fn of<F: Float>(color: &Rgba) -> Self {
+        let xyz: XYZ<F, D65> = XYZ::from(color);
+        let lch = Lch::from(&Lab::from(&xyz));
+        if lch.c < F::from_f64(Palette::<F>::ACHROMATIC_THRESHOLD) {
+            return ColorFamily::Neutral;
+        }
+        Self::HUE_RANGES
+            .iter()
+            .find(|(upper, _)| lch.h < F::from_f64(*upper))
+            .map_or(ColorFamily::Red, |(_, family)| *family)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn of_should_classify_color_by_hue() {
+        assert_eq!(
+            ColorFamily::of::<f64>(&Rgba::new(255, 0, 0, 255)),
+            ColorFamily::Red
+        );
+        assert_eq!(
+            ColorFamily::of::<f64>(&Rgba::new(255, 192, 203, 255)),
+            ColorFamily::Red
+        );
+        assert_eq!(
+            ColorFamily::of::<f64>(&Rgba::new(255, 20, 147, 255)),
+            ColorFamily::Red
+        );
+        assert_eq!(
+            ColorFamily::of::<f64>(&Rgba::new(255, 128, 0, 255)),
+            ColorFamily::Orange
+        );
+        assert_eq!(
+            ColorFamily::of::<f64>(&Rgba::new(255, 255, 0, 255)),
+            ColorFamily::Yellow
+        );
+        assert_eq!(
+            ColorFamily::of::<f64>(&Rgba::new(0, 128, 0, 255)),
+            ColorFamily::Green
+        );
+        assert_eq!(
+            ColorFamily::of::<f64>(&Rgba::new(0, 255, 255, 255)),
+            ColorFamily::Cyan
+        );
+        assert_eq!(
+            ColorFamily::of::<f64>(&Rgba::new(0, 0, 255, 255)),
+            ColorFamily::Blue
+        );
+        assert_eq!(
+            ColorFamily::of::<f64>(&Rgba::new(255, 0, 255, 255)),
+            ColorFamily::Purple
+        );
+    }
+
+    #[test]
+    fn of_should_classify_achromatic_color_as_neutral() {
+        for value in [0, 128, 255] {
+            assert_eq!(
+                ColorFamily::of::<f64>(&Rgba::new(value, value, value, 255)),
+                ColorFamily::Neutral
+            );
+        }
+        assert_eq!(
+            ColorFamily::of::<f32>(&Rgba::new(130, 128, 126, 255)),
+            ColorFamily::Neutral
+        );
+    }
+}
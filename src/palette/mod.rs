@@ -0,0 +1,1259 @@
+use crate::color::cct::correlated_color_temperature;
+use crate::color::cvd::{simulate_deuteranopia, simulate_protanopia, simulate_tritanopia};
+use crate::color::difference::ciede2000;
+use crate::color::gamut::to_rgba;
+use crate::color::lab::Lab;
+use crate::color::lch::Lch;
+use crate::color::names::closest_css_name;
+use crate::color::rgba::Rgba;
+use crate::color::white_point::D65;
+use crate::color::xyz::XYZ;
+use crate::math::clustering::dbscan::algorithm::DBSCAN;
+use crate::math::clustering::dbscan::params::Params;
+use crate::math::clustering::traits::Fit;
+use crate::math::distance::metric::DistanceMetric;
+use crate::math::number::Float;
+use crate::math::point::Point3;
+use crate::palette::family::ColorFamily;
+use crate::palette::scheme::ColorScheme;
+use crate::palette::sort::SwatchSortKey;
+use crate::swatch::Swatch;
+use num_traits::Zero;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Write};
+
+pub mod builder;
+pub mod export;
+pub mod family;
+pub mod scheme;
+pub mod similarity;
+pub mod sort;
+
+/// Color palette extracted from an image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Palette<F: Float> {
+    swatches: Vec<Swatch<F>>,
+    outlier_percentage: F,
+    outlier_color: Option<Rgba>,
+}
+
+impl<F> Palette<F>
+where
+    F: Float,
+{
+    /// The chroma below which a color is considered achromatic.
+    const ACHROMATIC_THRESHOLD: f64 = 5.0;
+
+    /// The minimum lightness distance between the halves of a split swatch in normalized units.
+    const MIN_SPLIT_OFFSET: f64 = 0.05;
+
+    /// Create a palette from the given swatches.
+    #[must_use]
+    pub(crate) fn new(swatches: Vec<Swatch<F>>) -> Self {
+        Self {
+            swatches,
+            outlier_percentage: F::zero(),
+            outlier_color: None,
+        }
+    }
+
+    /// Set the percentage and the average color of the pixels not belonging to any swatch.
+    #[must_use]
+    pub(crate) fn with_outliers(mut self, percentage: F, color: Option<Rgba>) -> Self {
+        self.outlier_percentage = percentage;
+        self.outlier_color = color;
+        self
+    }
+
+    /// Copy the outliers of the other palette to this palette.
+    #[must_use]
+    pub(crate) fn with_outliers_of(self, other: &Palette<F>) -> Self {
+        self.with_outliers(other.outlier_percentage, other.outlier_color.clone())
+    }
+
+    /// Name each swatch of this palette after the closest CSS named color.
+    #[must_use]
+    pub fn with_names(self) -> Self {
+        let outlier_percentage = self.outlier_percentage;
+        let outlier_color = self.outlier_color.clone();
+        self.swatches
+            .into_iter()
+            .map(|swatch| {
+                let name = closest_css_name(&swatch.color());
+                swatch.with_name(name)
+            })
+            .collect::<Palette<F>>()
+            .with_outliers(outlier_percentage, outlier_color)
+    }
+
+    /// Return the percentage of the image not belonging to any swatch.
+    ///
+    /// These pixels are the outliers of the clustering, such as noise or colors too rare to form a
+    /// swatch. A high percentage suggests that the epsilon is too small or the minimum number of
+    /// points is too large for the image. Palettes not extracted from an image, such as merged or
+    /// resized ones, report 0.
+    #[must_use]
+    pub fn outlier_percentage(&self) -> F {
+        self.outlier_percentage
+    }
+
+    /// Return the average color of the pixels not belonging to any swatch, or `None` if there are
+    /// no such pixels.
+    #[must_use]
+    pub fn outlier_color(&self) -> Option<Rgba> {
+        self.outlier_color.clone()
+    }
+
+    /// Return the swatches of this palette.
+    #[must_use]
+    pub fn swatches(&self) -> &[Swatch<F>] {
+        &self.swatches
+    }
+
+    /// Return an iterator over the swatches of this palette.
+    pub fn iter(&self) -> std::slice::Iter<'_, Swatch<F>> {
+        self.swatches.iter()
+    }
+
+    /// Return the number of swatches.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.swatches.len()
+    }
+
+    /// Return whether this palette has no swatches.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.swatches.is_empty()
+    }
+
+    /// Return the color of the swatch with the highest percentage, or `None` if this palette is
+    /// empty.
+    #[must_use]
+    pub fn dominant_color(&self) -> Option<Rgba> {
+        self.swatches.iter().max().map(Swatch::color)
+    }
+
+    /// Return the average color of the swatches weighted by their population.
+    ///
+    /// The colors are averaged in linear light so that the result is not biased toward dark
+    /// colors. Returns transparent black if this palette has no pixels.
+    #[must_use]
+    pub fn average_color(&self) -> Rgba {
+        let colors: Vec<(Rgba, F)> = self
+            .swatches
+            .iter()
+            .map(|swatch| (swatch.color(), F::from_usize(swatch.population())))
+            .collect();
+        Rgba::mix(&colors)
+    }
+
+    /// Return a palette of the swatches within the given CIEDE2000 color difference of the center
+    /// color.
+    ///
+    /// The percentages of the swatches and the outliers are kept as they are in this palette. Call
+    /// [`renormalize`](Self::renormalize) on the result to rescale them over the retained swatches.
+    #[must_use]
+    pub fn filter_near(&self, center: &Rgba, delta_e: F) -> Palette<F> {
+        self.swatches
+            .iter()
+            .filter(|swatch| swatch.is_within(center, delta_e))
+            .cloned()
+            .collect::<Palette<F>>()
+            .with_outliers_of(self)
+    }
+
+    /// Rescale the percentages of the swatches so that they sum to 1.
+    ///
+    /// This is useful after filtering out swatches, since the percentages of the retained swatches
+    /// are relative to the original image. The outliers are cleared, since the swatches then cover
+    /// the whole palette. Does nothing if the percentages sum to 0.
+    pub fn renormalize(&mut self) {
+        let total = self
+            .swatches
+            .iter()
+            .fold(F::zero(), |total, swatch| total + swatch.percentage());
+        if total.is_zero() {
+            return;
+        }
+        self.outlier_percentage = F::zero();
+        self.outlier_color = None;
+        self.swatches = self
+            .swatches
+            .drain(..)
+            .map(|swatch| {
+                let percentage = swatch.percentage() / total;
+                swatch.with_percentage(percentage)
+            })
+            .collect();
+    }
+
+    /// Group the swatches of this palette by their color family.
+    ///
+    /// The swatches of each family keep their order in this palette. See [`ColorFamily::of`] for
+    /// how the family of a swatch is determined.
+    #[must_use]
+    pub fn group_by_family(&self) -> HashMap<ColorFamily, Vec<Swatch<F>>> {
+        let mut groups: HashMap<ColorFamily, Vec<Swatch<F>>> = HashMap::new();
+        for swatch in &self.swatches {
+            let family = ColorFamily::of::<F>(&swatch.color());
+            groups.entry(family).or_default().push(swatch.clone());
+        }
+        groups
+    }
+
+    /// Choose a coordinated color scheme from the swatches of this palette.
+    ///
+    /// The most dominant swatch becomes the background, the swatch with the highest WCAG contrast
+    /// ratio against it the text, and the most vivid of the remaining swatches the accent. The
+    /// primary and secondary swatches are the most dominant of the rest. Each swatch fills at most
+    /// one slot.
+    #[must_use]
+    pub fn scheme(&self) -> ColorScheme<F> {
+        let mut candidates: Vec<&Swatch<F>> = self.swatches.iter().collect();
+        let mut take_max_by = |key: &dyn Fn(&Swatch<F>) -> F| -> Option<Swatch<F>> {
+            let (index, _) = candidates
+                .iter()
+                .enumerate()
+                .max_by(|(_, swatch1), (_, swatch2)| key(swatch1).total_order(&key(swatch2)))?;
+            Some(candidates.remove(index).clone())
+        };
+
+        let background = take_max_by(&Swatch::percentage);
+        let text = background.as_ref().and_then(|background| {
+            take_max_by(&|swatch| swatch.color().contrast_ratio(&background.color()))
+        });
+        let accent = take_max_by(&|swatch| SwatchSortKey::Vibrance.value(swatch));
+        let primary = take_max_by(&Swatch::percentage);
+        let secondary = take_max_by(&Swatch::percentage);
+        ColorScheme::new(background, text, accent, primary, secondary)
+    }
+
+    /// Sort the swatches of this palette by hue for display.
+    ///
+    /// Chromatic swatches are ordered by their CIE LCh hue angle, and achromatic swatches whose
+    /// chroma is below a threshold are placed at the end ordered from light to dark.
+    pub fn sort_by_hue(&mut self) {
+        let threshold = F::from_f64(Self::ACHROMATIC_THRESHOLD);
+        let mut keyed: Vec<(bool, F, Swatch<F>)> = self
+            .swatches
+            .drain(..)
+            .map(|swatch| {
+                let xyz: XYZ<F, D65> = XYZ::from(&swatch.color());
+                let lch = Lch::from(&Lab::from(&xyz));
+                if lch.c < threshold {
+                    (true, -lch.l, swatch)
+                } else {
+                    (false, lch.h, swatch)
+                }
+            })
+            .collect();
+        keyed.sort_by(|(achromatic1, key1, _), (achromatic2, key2, _)| {
+            achromatic1
+                .cmp(achromatic2)
+                .then_with(|| key1.total_order(key2))
+        });
+        self.swatches = keyed.into_iter().map(|(_, _, swatch)| swatch).collect();
+    }
+
+    /// Return the index of the swatch whose color is nearest to the given color, or `None` if this
+    /// palette is empty.
+    ///
+    /// The colors are compared by the CIEDE2000 color difference, and the first swatch wins a tie.
+    /// The index refers to [`Palette::swatches`], so it can be stored compactly in an indexed-color
+    /// image buffer.
+    #[must_use]
+    pub fn nearest_index(&self, color: &Rgba) -> Option<usize> {
+        let target: Lab<F> = Lab::from(&XYZ::<F, D65>::from(color));
+        self.swatches
+            .iter()
+            .map(|swatch| {
+                let lab: Lab<F> = Lab::from(&XYZ::<F, D65>::from(&swatch.color()));
+                ciede2000(&target, &lab)
+            })
+            .enumerate()
+            .min_by(|(_, delta1), (_, delta2)| delta1.total_order(delta2))
+            .map(|(index, _)| index)
+    }
+
+    /// Return the indices and the CIEDE2000 color difference of the two most similar swatches.
+    ///
+    /// The first index is always less than the second one, and the first pair wins a tie. Returns
+    /// `None` if this palette has fewer than two swatches.
+    #[must_use]
+    pub fn closest_pair(&self) -> Option<(usize, usize, F)> {
+        let labs: Vec<Lab<F>> = self
+            .swatches
+            .iter()
+            .map(|swatch| Lab::from(&XYZ::<F, D65>::from(&swatch.color())))
+            .collect();
+        let mut closest: Option<(usize, usize, F)> = None;
+        for (i, lab1) in labs.iter().enumerate() {
+            for (j, lab2) in labs.iter().enumerate().skip(i + 1) {
+                let delta = ciede2000(lab1, lab2);
+                if closest.is_none_or(|(_, _, min)| delta < min) {
+                    closest = Some((i, j, delta));
+                }
+            }
+        }
+        closest
+    }
+
+    /// Return the index pairs of the swatches that are hard to tell apart.
+    ///
+    /// A pair is flagged if the CIEDE2000 color difference of the swatches is less than the given
+    /// threshold as seen normally or as simulated for protanopia, deuteranopia or tritanopia. The
+    /// first index of a pair is always less than the second one.
+    #[must_use]
+    pub fn audit_distinguishability(&self, min_delta_e: F) -> Vec<(usize, usize)> {
+        let simulations: [fn(&Rgba) -> Rgba; 4] = [
+            Rgba::clone,
+            simulate_protanopia,
+            simulate_deuteranopia,
+            simulate_tritanopia,
+        ];
+        let labs: Vec<Vec<Lab<F>>> = simulations
+            .iter()
+            .map(|simulate| {
+                self.swatches
+                    .iter()
+                    .map(|swatch| Lab::from(&XYZ::<F, D65>::from(&simulate(&swatch.color()))))
+                    .collect()
+            })
+            .collect();
+
+        let mut pairs = Vec::new();
+        for i in 0..self.swatches.len() {
+            for j in (i + 1)..self.swatches.len() {
+                if labs
+                    .iter()
+                    .any(|lab| ciede2000(&lab[i], &lab[j]) < min_delta_e)
+                {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Render the swatches of this palette as an RGBA image of the given size.
+    ///
+    /// Each swatch occupies a full-height segment from left to right in the order of this palette,
+    /// whose width is proportional to its share of the total percentage. The buffer is
+    /// transparent if the percentages sum to 0.
+    #[must_use]
+    pub fn to_image_strip(&self, width: u32, height: u32) -> Vec<u8> {
+        let mut row = vec![0_u8; width as usize * 4];
+        let total = self
+            .swatches
+            .iter()
+            .fold(F::zero(), |total, swatch| total + swatch.percentage());
+        if !total.is_zero() {
+            let mut cumulative = F::zero();
+            let mut start = 0;
+            for swatch in &self.swatches {
+                cumulative += swatch.percentage();
+                let end = (cumulative / total * F::from_u32(width))
+                    .round()
+                    .to_usize()
+                    .unwrap_or(0)
+                    .clamp(start, width as usize);
+                let color = swatch.color();
+                for pixel in row[start * 4..end * 4].chunks_exact_mut(4) {
+                    pixel.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+                }
+                start = end;
+            }
+        }
+        row.repeat(height as usize)
+    }
+
+    /// Return a stable human-readable dump of this palette for golden tests.
+    ///
+    /// Each line holds the hexadecimal color with alpha, the percentage to 4 decimal places and
+    /// the population of a swatch. The lines are sorted by descending percentage and then by color,
+    /// so the snapshot does not depend on the order of the swatches.
+    #[must_use]
+    pub fn to_snapshot_string(&self) -> String {
+        let mut swatches: Vec<&Swatch<F>> = self.swatches.iter().collect();
+        swatches.sort_by(|swatch1, swatch2| {
+            swatch2
+                .percentage()
+                .total_order(&swatch1.percentage())
+                .then_with(|| {
+                    let (color1, color2) = (swatch1.color(), swatch2.color());
+                    (color1.r, color1.g, color1.b, color1.a)
+                        .cmp(&(color2.r, color2.g, color2.b, color2.a))
+                })
+        });
+
+        let mut snapshot = String::new();
+        for swatch in swatches {
+            let color = swatch.color();
+            writeln!(
+                snapshot,
+                "#{:02x}{:02x}{:02x}{:02x} {:.4} {}",
+                color.r,
+                color.g,
+                color.b,
+                color.a,
+                swatch.percentage().to_f64().unwrap_or(f64::NAN),
+                swatch.population()
+            )
+            .expect("Writing to a string should not fail");
+        }
+        snapshot
+    }
+
+    /// Return the swatches of this palette sorted in ascending order of the given key.
+    ///
+    /// The sort is stable, so swatches with equal keys keep their order in this palette.
+    #[must_use]
+    pub fn sorted_by(&self, key: SwatchSortKey) -> Vec<Swatch<F>> {
+        let mut keyed: Vec<(F, &Swatch<F>)> = self
+            .swatches
+            .iter()
+            .map(|swatch| (key.value(swatch), swatch))
+            .collect();
+        keyed.sort_by(|(key1, _), (key2, _)| key1.total_order(key2));
+        keyed
+            .into_iter()
+            .map(|(_, swatch)| swatch.clone())
+            .collect()
+    }
+
+    /// Return the average correlated color temperature in Kelvin of this palette.
+    ///
+    /// The chromaticity coordinates of the swatches are averaged by population and the temperature
+    /// is estimated once from the mean, so the dominant swatches determine whether the palette is
+    /// warm or cool, and a saturated swatch far from the Planckian locus only shifts the mean
+    /// instead of contributing a meaningless temperature of its own. Returns `None` if no swatch
+    /// has a defined chromaticity.
+    #[must_use]
+    pub fn average_cct(&self) -> Option<F> {
+        let (x, y, population) = self.swatches.iter().fold(
+            (F::zero(), F::zero(), 0_usize),
+            |(x, y, population), swatch| {
+                let xyz: XYZ<F, D65> = XYZ::from(&swatch.color());
+                let sum = xyz.x + xyz.y + xyz.z;
+                if sum.is_zero() {
+                    return (x, y, population);
+                }
+                let weight = F::from_usize(swatch.population());
+                (
+                    x + xyz.x / sum * weight,
+                    y + xyz.y / sum * weight,
+                    population + swatch.population(),
+                )
+            },
+        );
+        if population == 0 {
+            return None;
+        }
+
+        let x = x / F::from_usize(population);
+        let y = y / F::from_usize(population);
+        let chromaticity: XYZ<F, D65> = XYZ::new(x, y, F::one() - x - y);
+        Some(correlated_color_temperature(&chromaticity))
+    }
+
+    /// Return a palette of `n` swatches, merging or splitting the swatches of this palette.
+    ///
+    /// If there are too many swatches, the two most similar swatches by CIEDE2000 are repeatedly
+    /// combined weighted by their population. If there are too few, the swatch with the highest
+    /// spread is repeatedly split into a lighter and a darker swatch one standard deviation apart.
+    /// Only a swatch of at least 2 pixels with a spread is split, and only if the halves differ in
+    /// color, so fewer than `n` swatches are returned if no swatch can be split. An empty palette
+    /// stays empty.
+    ///
+    /// Splitting estimates the colors from the spread, since the member pixels are no longer
+    /// available. To get exactly `n` colors from an image, prefer
+    /// [`ImageData::extract_kmeans`](crate::image::ImageData::extract_kmeans), which clusters the pixels
+    /// themselves.
+    #[must_use]
+    pub fn ensure_size(&self, n: usize) -> Palette<F> {
+        let mut swatches = self.swatches.clone();
+        while swatches.len() > n {
+            let Some((i, j, _)) = Palette::new(swatches.clone()).closest_pair() else {
+                swatches.clear();
+                break;
+            };
+            let swatch2 = swatches.remove(j);
+            let swatch1 = swatches.remove(i);
+            swatches.push(Self::combine(&swatch1, &swatch2));
+        }
+        while swatches.len() < n {
+            let mut candidates: Vec<usize> = (0..swatches.len())
+                .filter(|&index| {
+                    swatches[index].population() >= 2 && swatches[index].spread() > F::zero()
+                })
+                .collect();
+            candidates.sort_by(|&index1, &index2| {
+                let (swatch1, swatch2) = (&swatches[index1], &swatches[index2]);
+                swatch2
+                    .spread()
+                    .total_order(&swatch1.spread())
+                    .then_with(|| swatch2.population().cmp(&swatch1.population()))
+            });
+            let Some((index, (lighter, darker))) = candidates.into_iter().find_map(|index| {
+                let (lighter, darker) = Self::split(&swatches[index]);
+                (lighter.color() != darker.color()).then_some((index, (lighter, darker)))
+            }) else {
+                break;
+            };
+            swatches.remove(index);
+            swatches.push(lighter);
+            swatches.push(darker);
+        }
+        swatches.sort();
+        Palette::new(swatches)
+    }
+
+    /// Combine two swatches into one weighted by their population.
+    ///
+    /// The spread of the combined swatch includes the distances between the two colors.
+    fn combine(swatch1: &Swatch<F>, swatch2: &Swatch<F>) -> Swatch<F> {
+        let population = swatch1.population() + swatch2.population();
+        let (weight1, weight2) = if population == 0 {
+            (F::from_f64(0.5), F::from_f64(0.5))
+        } else {
+            let total = F::from_usize(population);
+            (
+                F::from_usize(swatch1.population()) / total,
+                F::from_usize(swatch2.population()) / total,
+            )
+        };
+        let point1 = Self::to_normalized_lab(&swatch1.color());
+        let point2 = Self::to_normalized_lab(&swatch2.color());
+        let centroid = point1 * weight1 + point2 * weight2;
+        let spread = weight1
+            * (swatch1.spread() + DistanceMetric::SquaredEuclidean.measure(&point1, &centroid))
+            + weight2
+                * (swatch2.spread() + DistanceMetric::SquaredEuclidean.measure(&point2, &centroid));
+        let position = if swatch1.population() >= swatch2.population() {
+            swatch1.position()
+        } else {
+            swatch2.position()
+        };
+        let percentage = swatch1.percentage() + swatch2.percentage();
+        Swatch::new(
+            Self::from_normalized_lab(&centroid),
+            position,
+            population,
+            percentage,
+        )
+        .with_spread(spread)
+    }
+
+    /// Split a swatch into a lighter and a darker swatch sharing its population.
+    ///
+    /// The colors are one standard deviation of the spread apart along the lightness axis, but at
+    /// least a minimum distance so that a swatch without any spread still yields distinct colors.
+    fn split(swatch: &Swatch<F>) -> (Swatch<F>, Swatch<F>) {
+        let offset = swatch
+            .spread()
+            .sqrt()
+            .max(F::from_f64(Self::MIN_SPLIT_OFFSET));
+        let point = Self::to_normalized_lab(&swatch.color());
+        let lighter = Point3::new(point[0] + offset, point[1], point[2]);
+        let darker = Point3::new(point[0] - offset, point[1], point[2]);
+
+        let population1 = swatch.population().div_ceil(2);
+        let population2 = swatch.population() / 2;
+        let half = swatch.percentage() / F::from_f64(2.0);
+        let spread = (swatch.spread() - offset.powi(2)).max(F::zero());
+        let make = |point: &Point3<F>, population: usize| {
+            Swatch::new(
+                Self::from_normalized_lab(point),
+                swatch.position(),
+                population,
+                half,
+            )
+            .with_spread(spread)
+        };
+        (make(&lighter, population1), make(&darker, population2))
+    }
+
+    /// Convert the color to a point in the CIE L*a*b* color space normalized to the unit range.
+    fn to_normalized_lab(color: &Rgba) -> Point3<F> {
+        let Lab { l, a, b, .. } = Lab::from(&XYZ::<F, D65>::from(color));
+        Point3::new(
+            l / (Lab::<F>::max_l::<F>() - Lab::<F>::min_l::<F>()),
+            a / (Lab::<F>::max_a::<F>() - Lab::<F>::min_a::<F>()),
+            b / (Lab::<F>::max_b::<F>() - Lab::<F>::min_b::<F>()),
+        )
+    }
+
+    /// Convert the point in the normalized CIE L*a*b* color space to a color within the sRGB gamut.
+    fn from_normalized_lab(point: &Point3<F>) -> Rgba {
+        let lab: Lab<F> = Lab::new(
+            point[0] * (Lab::<F>::max_l::<F>() - Lab::<F>::min_l::<F>()),
+            point[1] * (Lab::<F>::max_a::<F>() - Lab::<F>::min_a::<F>()),
+            point[2] * (Lab::<F>::max_b::<F>() - Lab::<F>::min_b::<F>()),
+        );
+        to_rgba(&lab)
+    }
+
+    /// Merge the given palettes into a single palette.
+    ///
+    /// Swatches of similar colors are re-clustered in CIE L*a*b* color space and combined
+    /// weighted by their population, then the percentages are renormalized over all swatches.
+    /// Similar colors can chain into a group whose mean color is out of the sRGB gamut, and such a
+    /// color is mapped into the gamut. The position of a merged swatch is taken from its most populous member, and its spread
+    /// includes the distances between the merged colors.
+    #[must_use]
+    pub fn merge(palettes: &[Palette<F>]) -> Palette<F> {
+        let swatches: Vec<&Swatch<F>> = palettes
+            .iter()
+            .flat_map(|palette| palette.swatches.iter())
+            .collect();
+        let total_population: usize = swatches.iter().map(|swatch| swatch.population()).sum();
+        if total_population == 0 {
+            return Palette::new(Vec::new());
+        }
+
+        let delta_l: F = Lab::<F>::max_l::<F>() - Lab::<F>::min_l::<F>();
+        let delta_a: F = Lab::<F>::max_a::<F>() - Lab::<F>::min_a::<F>();
+        let delta_b: F = Lab::<F>::max_b::<F>() - Lab::<F>::min_b::<F>();
+        let colors: Vec<Point3<F>> = swatches
+            .iter()
+            .map(|swatch| {
+                let xyz: XYZ<F, D65> = XYZ::from(&swatch.color());
+                let Lab { l, a, b, .. } = Lab::from(&xyz);
+                Point3::new(l / delta_l, a / delta_a, b / delta_b)
+            })
+            .collect();
+
+        let params = Params::new(1, F::from_f64(0.025), DistanceMetric::Euclidean);
+        let dbscan = DBSCAN::fit(&colors, &params);
+        let mut merged: Vec<Swatch<F>> = (0..dbscan.centroids().len())
+            .filter_map(|cluster_id| {
+                let members = dbscan.members_at(cluster_id);
+                let population: usize = members
+                    .iter()
+                    .map(|&index| swatches[index].population())
+                    .sum();
+                if population == 0 {
+                    return None;
+                }
+
+                let centroid = members.iter().fold(Point3::zero(), |total, &index| {
+                    total + colors[index] * F::from_usize(swatches[index].population())
+                }) / F::from_usize(population);
+                let lab = Lab::new(
+                    centroid[0] * delta_l,
+                    centroid[1] * delta_a,
+                    centroid[2] * delta_b,
+                );
+                let color = to_rgba(&lab);
+
+                let position = members
+                    .iter()
+                    .map(|&index| swatches[index])
+                    .max_by_key(|swatch| swatch.population())
+                    .map_or((0, 0), |swatch| swatch.position());
+                // Combine the spread within each swatch and the spread between the swatches.
+                let spread = members.iter().fold(F::zero(), |total, &index| {
+                    let swatch = swatches[index];
+                    let between =
+                        DistanceMetric::SquaredEuclidean.measure(&colors[index], &centroid);
+                    total + (swatch.spread() + between) * F::from_usize(swatch.population())
+                }) / F::from_usize(population);
+                let percentage = F::from_usize(population) / F::from_usize(total_population);
+                Some(Swatch::new(color, position, population, percentage).with_spread(spread))
+            })
+            .collect();
+
+        merged.sort();
+        Palette::new(merged)
+    }
+}
+
+impl<F> Display for Palette<F>
+where
+    F: Float,
+{
+    /// Format this palette with one swatch per line, the dominant swatch first.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut swatches: Vec<&Swatch<F>> = self.swatches.iter().collect();
+        swatches.sort_by(|swatch1, swatch2| swatch2.cmp(swatch1));
+        for (index, swatch) in swatches.into_iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{swatch}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<F> IntoIterator for Palette<F>
+where
+    F: Float,
+{
+    type Item = Swatch<F>;
+    type IntoIter = std::vec::IntoIter<Swatch<F>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.swatches.into_iter()
+    }
+}
+
+impl<'a, F> IntoIterator for &'a Palette<F>
+where
+    F: Float,
+{
+    type Item = &'a Swatch<F>;
+    type IntoIter = std::slice::Iter<'a, Swatch<F>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.swatches.iter()
+    }
+}
+
+impl<F> FromIterator<Swatch<F>> for Palette<F>
+where
+    F: Float,
+{
+    fn from_iter<I: IntoIterator<Item = Swatch<F>>>(iter: I) -> Self {
+        Palette::new(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::approx::ApproxEq;
+
+    fn swatch((r, g, b): (u8, u8, u8), population: usize, percentage: f64) -> Swatch<f64> {
+        Swatch::new(Rgba::new(r, g, b, 255), (0, 0), population, percentage)
+    }
+
+    #[test]
+    fn iter_should_iterate_swatches() {
+        let palette = Palette::new(vec![
+            swatch((255, 0, 0), 10, 0.25),
+            swatch((0, 0, 255), 30, 0.75),
+        ]);
+        let populations: Vec<usize> = palette.iter().map(|s| s.population()).collect();
+        assert_eq!(populations, vec![10, 30]);
+
+        let populations: Vec<usize> = (&palette).into_iter().map(|s| s.population()).collect();
+        assert_eq!(populations, vec![10, 30]);
+    }
+
+    #[test]
+    fn into_iter_should_collect_into_palette() {
+        let palette = Palette::new(vec![
+            swatch((255, 0, 0), 10, 0.02),
+            swatch((0, 255, 0), 20, 0.08),
+            swatch((0, 0, 255), 30, 0.9),
+        ]);
+        let filtered: Palette<f64> = palette
+            .into_iter()
+            .filter(|swatch| swatch.percentage() > 0.05)
+            .collect();
+        assert_eq!(
+            filtered,
+            Palette::new(vec![
+                swatch((0, 255, 0), 20, 0.08),
+                swatch((0, 0, 255), 30, 0.9),
+            ])
+        );
+    }
+
+    #[test]
+    fn with_names_should_name_swatches_after_css_colors() {
+        let palette = Palette::new(vec![
+            swatch((255, 0, 0), 10, 0.25),
+            swatch((70, 130, 180), 30, 0.75),
+        ]);
+        assert!(palette.iter().all(|swatch| swatch.name().is_none()));
+
+        let palette = palette.with_names();
+        let names: Vec<Option<&str>> = palette.iter().map(|swatch| swatch.name()).collect();
+        assert_eq!(names, vec![Some("red"), Some("steelblue")]);
+    }
+
+    #[test]
+    fn dominant_color_should_return_color_of_highest_percentage() {
+        let palette = Palette::new(vec![
+            swatch((255, 0, 0), 100, 0.25),
+            swatch((0, 0, 255), 300, 0.75),
+        ]);
+        assert_eq!(palette.dominant_color(), Some(Rgba::new(0, 0, 255, 255)));
+
+        let palette: Palette<f64> = Palette::new(vec![]);
+        assert_eq!(palette.dominant_color(), None);
+    }
+
+    #[test]
+    fn average_color_should_return_weighted_mean_in_linear_light() {
+        let palette = Palette::new(vec![
+            swatch((255, 0, 0), 100, 0.25),
+            swatch((0, 0, 255), 300, 0.75),
+        ]);
+        assert_eq!(palette.average_color(), Rgba::new(137, 0, 225, 255));
+
+        let palette = Palette::new(vec![
+            swatch((255, 255, 255), 1, 0.5),
+            swatch((0, 0, 0), 1, 0.5),
+        ]);
+        assert_eq!(palette.average_color(), Rgba::new(188, 188, 188, 255));
+
+        let palette: Palette<f64> = Palette::new(vec![]);
+        assert_eq!(palette.average_color(), Rgba::new(0, 0, 0, 0));
+    }
+
+    #[test]
+    fn to_image_strip_should_render_proportional_segments() {
+        let palette = Palette::new(vec![
+            swatch((255, 0, 0), 10, 0.5),
+            swatch((0, 0, 255), 10, 0.5),
+        ]);
+        let strip = palette.to_image_strip(4, 2);
+        assert_eq!(strip.len(), 4 * 2 * 4);
+        for row in strip.chunks_exact(4 * 4) {
+            assert_eq!(
+                row,
+                &[255, 0, 0, 255, 255, 0, 0, 255, 0, 0, 255, 255, 0, 0, 255, 255]
+            );
+        }
+
+        let palette = Palette::new(vec![
+            swatch((255, 0, 0), 10, 0.1),
+            swatch((0, 0, 255), 30, 0.3),
+        ]);
+        let strip = palette.to_image_strip(8, 1);
+        let colors: Vec<u8> = strip.chunks_exact(4).map(|pixel| pixel[0]).collect();
+        assert_eq!(colors, vec![255, 255, 0, 0, 0, 0, 0, 0]);
+
+        let strip = Palette::<f64>::new(vec![]).to_image_strip(2, 2);
+        assert_eq!(strip, vec![0; 16]);
+    }
+
+    #[test]
+    fn to_snapshot_string_should_dump_sorted_swatches() {
+        let palette = Palette::new(vec![
+            swatch((0, 0, 255), 10, 0.25),
+            swatch((255, 128, 0), 20, 0.5),
+            swatch((0, 16, 0), 10, 0.25),
+        ]);
+        assert_eq!(
+            palette.to_snapshot_string(),
+            "#ff8000ff 0.5000 20\n#0000ffff 0.2500 10\n#001000ff 0.2500 10\n"
+        );
+        assert_eq!(Palette::<f64>::new(vec![]).to_snapshot_string(), "");
+    }
+
+    #[test]
+    fn to_string_should_format_swatches_dominant_first() {
+        let palette = Palette::new(vec![
+            swatch((0, 0, 255), 10, 0.25),
+            swatch((255, 128, 0), 30, 0.75),
+        ]);
+        assert_eq!(
+            palette.to_string(),
+            "#ff8000 75.0% @ (0, 0)\n#0000ff 25.0% @ (0, 0)"
+        );
+        assert_eq!(Palette::<f64>::new(vec![]).to_string(), "");
+    }
+
+    #[test]
+    fn ensure_size_should_merge_closest_swatches() {
+        let palette = Palette::new(vec![
+            swatch((255, 0, 0), 10, 0.1),
+            swatch((0, 0, 255), 30, 0.3),
+            swatch((0, 255, 0), 20, 0.2),
+            swatch((4, 0, 250), 40, 0.4),
+        ]);
+        let resized = palette.ensure_size(3);
+        assert_eq!(resized.len(), 3);
+        let blue = resized.swatches().last().unwrap();
+        assert_eq!(blue.population(), 70);
+        assert!((blue.percentage() - 0.7).abs() < 1e-12);
+        assert!(blue.is_within(&Rgba::new(2, 0, 252, 255), 2.0));
+        assert!(blue.spread() > 0.0);
+        assert_eq!(palette.ensure_size(4).len(), 4);
+
+        let resized = palette.ensure_size(1);
+        assert_eq!(resized.len(), 1);
+        assert_eq!(resized.swatches()[0].population(), 100);
+        assert!((resized.swatches()[0].percentage() - 1.0).abs() < 1e-12);
+        assert!(palette.ensure_size(0).is_empty());
+    }
+
+    #[test]
+    fn ensure_size_should_split_swatch_with_highest_spread() {
+        let palette = Palette::new(vec![
+            swatch((128, 0, 0), 50, 0.5).with_spread(0.01),
+            swatch((0, 0, 128), 50, 0.5).with_spread(0.001),
+        ]);
+        let resized = palette.ensure_size(3);
+        assert_eq!(resized.len(), 3);
+        let reds: Vec<&Swatch<f64>> = resized
+            .iter()
+            .filter(|swatch| swatch.color().r > swatch.color().b)
+            .collect();
+        assert_eq!(reds.len(), 2);
+        assert_eq!(reds[0].population() + reds[1].population(), 50);
+        assert!((reds[0].percentage() + reds[1].percentage() - 0.5).abs() < 1e-12);
+        let lightness =
+            |swatch: &Swatch<f64>| Lab::<f64>::from(&XYZ::<f64, D65>::from(&swatch.color())).l;
+        assert!((lightness(reds[0]) - lightness(reds[1])).abs() > 15.0);
+
+        // The spread of each swatch is used up by a single split.
+        let resized = palette.ensure_size(6);
+        assert_eq!(resized.len(), 4);
+        assert_eq!(resized.iter().map(Swatch::population).sum::<usize>(), 100);
+        assert!(Palette::<f64>::new(vec![]).ensure_size(3).is_empty());
+    }
+
+    #[test]
+    fn ensure_size_should_not_split_single_pixel_or_uniform_swatches() {
+        let palette = Palette::new(vec![
+            swatch((255, 255, 255), 1, 0.1).with_spread(0.01),
+            swatch((255, 0, 0), 9, 0.9),
+        ]);
+        let resized = palette.ensure_size(4);
+        assert_eq!(resized, palette);
+
+        // The red swatch is split, while the single white pixel is kept as is.
+        let palette = Palette::new(vec![
+            swatch((255, 255, 255), 1, 0.1).with_spread(0.01),
+            swatch((255, 0, 0), 9, 0.9).with_spread(0.001),
+        ]);
+        let resized = palette.ensure_size(4);
+        assert_eq!(resized.len(), 3);
+        assert!(resized.iter().all(|swatch| swatch.population() > 0));
+        assert_eq!(resized.iter().map(Swatch::population).sum::<usize>(), 10);
+    }
+
+    #[test]
+    fn audit_distinguishability_should_flag_pairs_under_deuteranopia() {
+        let palette = Palette::new(vec![
+            swatch((200, 60, 40), 10, 0.3),
+            swatch((0, 0, 255), 20, 0.3),
+            swatch((90, 130, 40), 30, 0.4),
+        ]);
+        // The red and the green differ by about 54 normally but only about 3 under deuteranopia.
+        assert_eq!(palette.audit_distinguishability(10.0), vec![(0, 2)]);
+        assert_eq!(palette.audit_distinguishability(1.0), vec![]);
+        assert_eq!(palette.audit_distinguishability(100.0).len(), 3);
+    }
+
+    #[test]
+    fn closest_pair_should_return_most_similar_swatches() {
+        let palette = Palette::new(vec![
+            swatch((255, 0, 0), 10, 0.1),
+            swatch((0, 0, 255), 20, 0.2),
+            swatch((0, 255, 0), 30, 0.3),
+            swatch((2, 0, 250), 40, 0.4),
+        ]);
+        let (i, j, delta) = palette.closest_pair().unwrap();
+        assert_eq!((i, j), (1, 3));
+        assert!(delta > 0.0 && delta < 2.0);
+
+        let palette: Palette<f64> = Palette::new(vec![swatch((255, 0, 0), 10, 1.0)]);
+        assert_eq!(palette.closest_pair(), None);
+    }
+
+    #[test]
+    fn nearest_index_should_return_index_of_nearest_swatch() {
+        let palette = Palette::new(vec![
+            swatch((255, 0, 0), 10, 0.1),
+            swatch((0, 255, 0), 20, 0.2),
+            swatch((0, 0, 255), 30, 0.3),
+            swatch((255, 255, 255), 40, 0.4),
+        ]);
+        let queries = [
+            Rgba::new(250, 10, 5, 255),
+            Rgba::new(20, 200, 40, 255),
+            Rgba::new(10, 20, 180, 255),
+            Rgba::new(240, 240, 235, 255),
+            Rgba::new(0, 0, 255, 255),
+            Rgba::new(200, 60, 80, 255),
+        ];
+        let indices: Vec<u8> = queries
+            .iter()
+            .filter_map(|color| palette.nearest_index(color))
+            .map(|index| index as u8)
+            .collect();
+        assert_eq!(indices, vec![0, 1, 2, 3, 2, 0]);
+
+        let palette: Palette<f64> = Palette::new(vec![]);
+        assert_eq!(palette.nearest_index(&Rgba::new(0, 0, 0, 255)), None);
+    }
+
+    #[test]
+    fn merge_should_map_mean_color_of_chained_swatches_into_gamut() {
+        // Neighboring colors along the edge of the sRGB gamut chain into a group whose mean color
+        // is out of the gamut.
+        let palettes: Vec<Palette<f64>> = (0..=255)
+            .step_by(4)
+            .map(|g| Palette::new(vec![swatch((0, g, 255), 1, 1.0)]))
+            .collect();
+        let merged = Palette::merge(&palettes);
+        assert!(merged.len() < palettes.len());
+        assert_eq!(
+            merged.iter().map(Swatch::population).sum::<usize>(),
+            palettes.len()
+        );
+    }
+
+    #[test]
+    fn renormalize_should_rescale_percentages_to_sum_to_one() {
+        let palette = Palette::new(vec![
+            swatch((255, 0, 0), 40, 0.4),
+            swatch((0, 0, 255), 30, 0.3),
+            swatch((0, 0, 250), 20, 0.2),
+            swatch((255, 255, 255), 10, 0.1),
+        ]);
+        let palette = palette.with_outliers(0.25, Some(Rgba::new(128, 128, 128, 255)));
+        let mut blue = palette.filter_near(&Rgba::new(0, 0, 255, 255), 10.0);
+        assert_eq!(blue.outlier_percentage(), 0.25);
+        blue.renormalize();
+        assert_eq!(blue.outlier_percentage(), 0.0);
+        assert_eq!(blue.outlier_color(), None);
+        let percentages: Vec<f64> = blue.iter().map(Swatch::percentage).collect();
+        assert_eq!(percentages.len(), 2);
+        assert!((percentages[0] - 0.6).abs() < 1e-12);
+        assert!((percentages[1] - 0.4).abs() < 1e-12);
+        assert!((percentages.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+
+        let mut empty: Palette<f64> = Palette::new(vec![]);
+        empty.renormalize();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn group_by_family_should_group_swatches() {
+        let palette = Palette::new(vec![
+            swatch((255, 0, 0), 10, 0.1),
+            swatch((128, 128, 128), 20, 0.2),
+            swatch((0, 0, 255), 30, 0.3),
+            swatch((255, 192, 203), 40, 0.4),
+        ]);
+        let groups = palette.group_by_family();
+        assert_eq!(groups.len(), 3);
+        assert_eq!(
+            groups[&ColorFamily::Red],
+            vec![
+                swatch((255, 0, 0), 10, 0.1),
+                swatch((255, 192, 203), 40, 0.4)
+            ]
+        );
+        assert_eq!(
+            groups[&ColorFamily::Neutral],
+            vec![swatch((128, 128, 128), 20, 0.2)]
+        );
+        assert_eq!(groups[&ColorFamily::Blue].len(), 1);
+        assert!(Palette::<f64>::new(vec![]).group_by_family().is_empty());
+    }
+
+    #[test]
+    fn scheme_should_fill_slots_by_rules() {
+        let palette = Palette::new(vec![
+            swatch((240, 235, 220), 40, 0.4),
+            swatch((60, 90, 140), 20, 0.2),
+            swatch((20, 20, 30), 10, 0.1),
+            swatch((255, 40, 0), 5, 0.05),
+            swatch((120, 150, 110), 15, 0.15),
+            swatch((180, 170, 160), 10, 0.1),
+        ]);
+        let scheme = palette.scheme();
+        assert_eq!(scheme.background(), Some(&palette.swatches()[0]));
+        assert_eq!(scheme.text(), Some(&palette.swatches()[2]));
+        assert_eq!(scheme.accent(), Some(&palette.swatches()[3]));
+        assert_eq!(scheme.primary(), Some(&palette.swatches()[1]));
+        assert_eq!(scheme.secondary(), Some(&palette.swatches()[4]));
+    }
+
+    #[test]
+    fn scheme_should_leave_slots_empty_for_small_palette() {
+        let palette = Palette::new(vec![
+            swatch((255, 255, 255), 30, 0.75),
+            swatch((0, 0, 0), 10, 0.25),
+        ]);
+        let scheme = palette.scheme();
+        assert_eq!(scheme.background(), Some(&palette.swatches()[0]));
+        assert_eq!(scheme.text(), Some(&palette.swatches()[1]));
+        assert_eq!(scheme.accent(), None);
+        assert_eq!(scheme.primary(), None);
+
+        let scheme = Palette::<f64>::new(vec![]).scheme();
+        assert_eq!(scheme.background(), None);
+        assert_eq!(scheme.text(), None);
+    }
+
+    #[test]
+    fn filter_near_should_select_swatches_near_center() {
+        let palette = Palette::new(vec![
+            swatch((255, 0, 0), 10, 0.1),
+            swatch((0, 0, 255), 20, 0.2),
+            swatch((0, 255, 0), 30, 0.3),
+            swatch((20, 30, 230), 25, 0.25),
+            swatch((255, 255, 255), 15, 0.15),
+        ]);
+        let blue = palette.filter_near(&Rgba::new(0, 0, 255, 255), 10.0);
+        assert_eq!(
+            blue.swatches(),
+            &[
+                swatch((0, 0, 255), 20, 0.2),
+                swatch((20, 30, 230), 25, 0.25),
+            ]
+        );
+
+        let none = palette.filter_near(&Rgba::new(128, 64, 0, 255), 1.0);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn sort_by_hue_should_order_swatches_spectrally() {
+        let mut palette = Palette::new(vec![
+            swatch((128, 128, 128), 10, 0.1),
+            swatch((0, 0, 255), 10, 0.1),
+            swatch((255, 255, 255), 10, 0.1),
+            swatch((0, 255, 0), 10, 0.1),
+            swatch((255, 0, 0), 10, 0.1),
+        ]);
+        palette.sort_by_hue();
+        let colors: Vec<Rgba> = palette.iter().map(|s| s.color()).collect();
+        assert_eq!(
+            colors,
+            vec![
+                Rgba::new(255, 0, 0, 255),
+                Rgba::new(0, 255, 0, 255),
+                Rgba::new(0, 0, 255, 255),
+                Rgba::new(255, 255, 255, 255),
+                Rgba::new(128, 128, 128, 255),
+            ]
+        );
+    }
+
+    fn sorted_colors(palette: &Palette<f64>, key: SwatchSortKey) -> Vec<Rgba> {
+        palette.sorted_by(key).iter().map(|s| s.color()).collect()
+    }
+
+    #[test]
+    fn sorted_by_should_sort_swatches_by_key() {
+        let palette = Palette::new(vec![
+            swatch((0, 0, 255), 30, 0.3),
+            swatch((255, 255, 0), 10, 0.1),
+            swatch((255, 0, 0), 20, 0.2),
+            swatch((160, 255, 160), 40, 0.4),
+        ]);
+
+        assert_eq!(
+            sorted_colors(&palette, SwatchSortKey::Percentage),
+            vec![
+                Rgba::new(255, 255, 0, 255),
+                Rgba::new(255, 0, 0, 255),
+                Rgba::new(0, 0, 255, 255),
+                Rgba::new(160, 255, 160, 255)
+            ]
+        );
+        assert_eq!(
+            sorted_colors(&palette, SwatchSortKey::Luminance),
+            vec![
+                Rgba::new(0, 0, 255, 255),
+                Rgba::new(255, 0, 0, 255),
+                Rgba::new(160, 255, 160, 255),
+                Rgba::new(255, 255, 0, 255)
+            ]
+        );
+        assert_eq!(
+            sorted_colors(&palette, SwatchSortKey::Hue),
+            vec![
+                Rgba::new(255, 0, 0, 255),
+                Rgba::new(255, 255, 0, 255),
+                Rgba::new(160, 255, 160, 255),
+                Rgba::new(0, 0, 255, 255)
+            ]
+        );
+        assert_eq!(
+            sorted_colors(&palette, SwatchSortKey::Vibrance),
+            vec![
+                Rgba::new(160, 255, 160, 255),
+                Rgba::new(255, 255, 0, 255),
+                Rgba::new(255, 0, 0, 255),
+                Rgba::new(0, 0, 255, 255)
+            ]
+        );
+        assert_eq!(palette.swatches()[0].color(), Rgba::new(0, 0, 255, 255));
+    }
+
+    #[test]
+    fn average_cct_should_return_weighted_temperature() {
+        let warm = Palette::new(vec![
+            swatch((0, 0, 255), 10, 0.1),
+            swatch((255, 180, 107), 60, 0.6),
+            swatch((255, 169, 87), 30, 0.3),
+        ]);
+        let cct = warm.average_cct().unwrap();
+        assert!((2500.0..3500.0).contains(&cct));
+
+        let cool = Palette::new(vec![
+            swatch((255, 180, 107), 10, 0.1),
+            swatch((150, 180, 255), 90, 0.9),
+        ]);
+        let cct = cool.average_cct().unwrap();
+        assert!(cct > 8000.0);
+    }
+
+    #[test]
+    fn average_cct_should_estimate_mean_chromaticity_of_saturated_swatches() {
+        // Pure blue alone maps to a warm temperature far off the Planckian locus, but mixed with
+        // yellow the palette averages to a bluish white.
+        let palette = Palette::new(vec![
+            swatch((0, 0, 255), 50, 0.5),
+            swatch((255, 255, 0), 50, 0.5),
+        ]);
+        let cct = palette.average_cct().unwrap();
+        assert!(cct > 8000.0, "cct: {cct}");
+    }
+
+    #[test]
+    fn average_cct_should_return_none_if_undefined() {
+        let palette: Palette<f64> = Palette::new(vec![]);
+        assert_eq!(palette.average_cct(), None);
+
+        let palette = Palette::new(vec![swatch((0, 0, 0), 10, 1.0)]);
+        assert_eq!(palette.average_cct(), None);
+    }
+
+    #[test]
+    fn merge_should_combine_swatches_weighted_by_population() {
+        let red = Palette::new(vec![swatch((255, 0, 0), 100, 1.0)]);
+        let blue = Palette::new(vec![swatch((0, 0, 255), 300, 1.0)]);
+
+        let palette = Palette::merge(&[red, blue]);
+        assert_eq!(
+            palette.swatches(),
+            &[
+                swatch((255, 0, 0), 100, 0.25),
+                swatch((0, 0, 255), 300, 0.75),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_should_combine_similar_colors() {
+        let palette1 = Palette::new(vec![swatch((255, 0, 0), 100, 1.0)]);
+        let palette2 = Palette::new(vec![
+            swatch((255, 0, 0), 100, 0.5),
+            swatch((0, 255, 0), 100, 0.5),
+        ]);
+
+        let palette = Palette::merge(&[palette1, palette2]);
+        assert_eq!(palette.len(), 2);
+        assert!(palette.swatches()[1].spread() < 1e-12);
+        assert!(palette.swatches()[1].approx_eq(&swatch((255, 0, 0), 200, 0.6667), 1e-4));
+    }
+
+    #[test]
+    fn merge_should_return_empty_palette_if_no_palettes_given() {
+        let palette: Palette<f64> = Palette::merge(&[]);
+        assert!(palette.is_empty());
+    }
+}
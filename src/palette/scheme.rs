@@ -0,0 +1,67 @@
+use crate::math::number::Float;
+use crate::swatch::Swatch;
+
+/// Coordinated color scheme chosen from the swatches of a palette.
+///
+/// Each slot holds a distinct swatch, so a slot is `None` if the palette has too few swatches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorScheme<F: Float> {
+    background: Option<Swatch<F>>,
+    text: Option<Swatch<F>>,
+    accent: Option<Swatch<F>>,
+    primary: Option<Swatch<F>>,
+    secondary: Option<Swatch<F>>,
+}
+
+impl<F> ColorScheme<F>
+where
+    F: Float,
+{
+    /// Create a color scheme from the given slots.
+    #[must_use]
+    pub(crate) fn new(
+        background: Option<Swatch<F>>,
+        text: Option<Swatch<F>>,
+        accent: Option<Swatch<F>>,
+        primary: Option<Swatch<F>>,
+        secondary: Option<Swatch<F>>,
+    ) -> Self {
+        Self {
+            background,
+            text,
+            accent,
+            primary,
+            secondary,
+        }
+    }
+
+    /// Return the background swatch, which is the most dominant swatch.
+    #[must_use]
+    pub fn background(&self) -> Option<&Swatch<F>> {
+        self.background.as_ref()
+    }
+
+    /// Return the text swatch, which has the highest contrast against the background.
+    #[must_use]
+    pub fn text(&self) -> Option<&Swatch<F>> {
+        self.text.as_ref()
+    }
+
+    /// Return the accent swatch, which is the most vivid of the remaining swatches.
+    #[must_use]
+    pub fn accent(&self) -> Option<&Swatch<F>> {
+        self.accent.as_ref()
+    }
+
+    /// Return the primary swatch, which is the most dominant of the remaining swatches.
+    #[must_use]
+    pub fn primary(&self) -> Option<&Swatch<F>> {
+        self.primary.as_ref()
+    }
+
+    /// Return the secondary swatch, which is the most dominant swatch after the primary one.
+    #[must_use]
+    pub fn secondary(&self) -> Option<&Swatch<F>> {
+        self.secondary.as_ref()
+    }
+}
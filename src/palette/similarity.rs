@@ -0,0 +1,111 @@
+use crate::color::difference::ciede2000;
+use crate::color::lab::Lab;
+use crate::color::white_point::D65;
+use crate::color::xyz::XYZ;
+use crate::math::number::Float;
+use crate::palette::Palette;
+
+/// Compute the perceptual distance between two palettes.
+///
+/// Swatches are matched like the Earth Mover's Distance, where the percentage of each swatch is
+/// moved to the swatches of the other palette and the cost of moving is the CIEDE2000 color
+/// difference. The flow is assigned greedily from the cheapest pair, so the result approximates
+/// the exact distance.
+///
+/// Returns zero if both palettes have no swatches, and the maximum value of `F` if only one of them
+/// has no swatches, since there is nothing to move the percentage of the other palette to.
+///
+/// [Earth mover's distance - Wikipedia](https://en.wikipedia.org/wiki/Earth_mover%27s_distance)
+#[must_use]
+pub fn palette_distance<F: Float>(a: &Palette<F>, b: &Palette<F>) -> F {
+    let (colors_a, mut supplies) = weighted_colors(a);
+    let (colors_b, mut demands) = weighted_colors(b);
+    match (colors_a.is_empty(), colors_b.is_empty()) {
+        (true, true) => return F::zero(),
+        (true, false) | (false, true) => return F::max_value(),
+        (false, false) => {}
+    }
+
+    let mut costs: Vec<(usize, usize, F)> = Vec::with_capacity(colors_a.len() * colors_b.len());
+    for (i, lab_a) in colors_a.iter().enumerate() {
+        for (j, lab_b) in colors_b.iter().enumerate() {
+            costs.push((i, j, ciede2000(lab_a, lab_b)));
+        }
+    }
+    costs.sort_by(|(_, _, cost1), (_, _, cost2)| cost1.total_order(cost2));
+
+    costs.into_iter().fold(F::zero(), |total, (i, j, cost)| {
+        let flow = supplies[i].min(demands[j]);
+        if flow <= F::zero() {
+            return total;
+        }
+        supplies[i] -= flow;
+        demands[j] -= flow;
+        total + flow * cost
+    })
+}
+
+/// Convert the swatches to colors in CIE L*a*b* color space with their normalized weights.
+fn weighted_colors<F: Float>(palette: &Palette<F>) -> (Vec<Lab<F>>, Vec<F>) {
+    let total = palette
+        .iter()
+        .fold(F::zero(), |total, swatch| total + swatch.percentage());
+    if total <= F::zero() {
+        return (Vec::new(), Vec::new());
+    }
+
+    palette
+        .iter()
+        .map(|swatch| {
+            let xyz: XYZ<F, D65> = XYZ::from(&swatch.color());
+            (Lab::from(&xyz), swatch.percentage() / total)
+        })
+        .unzip()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::rgba::Rgba;
+    use crate::swatch::Swatch;
+
+    fn palette(swatches: &[((u8, u8, u8), f64)]) -> Palette<f64> {
+        swatches
+            .iter()
+            .map(|&((r, g, b), percentage)| {
+                Swatch::new(Rgba::new(r, g, b, 255), (0, 0), 1, percentage)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn palette_distance_should_return_zero_for_same_palette() {
+        let a = palette(&[((255, 0, 0), 0.2), ((0, 128, 0), 0.3), ((0, 0, 255), 0.5)]);
+        assert_eq!(palette_distance(&a, &a), 0.0);
+
+        let b = palette(&[((0, 0, 255), 0.5), ((255, 0, 0), 0.2), ((0, 128, 0), 0.3)]);
+        assert_eq!(palette_distance(&a, &b), 0.0);
+    }
+
+    #[test]
+    fn palette_distance_should_increase_with_difference() {
+        let a = palette(&[((255, 0, 0), 0.5), ((0, 0, 255), 0.5)]);
+        let b = palette(&[((240, 10, 10), 0.5), ((10, 10, 240), 0.5)]);
+        let c = palette(&[((0, 255, 0), 0.5), ((255, 255, 0), 0.5)]);
+
+        let near = palette_distance(&a, &b);
+        let far = palette_distance(&a, &c);
+        assert!(near > 0.0);
+        assert!(far > near);
+        assert!((palette_distance(&b, &a) - near).abs() < 1e-9);
+    }
+
+    #[test]
+    fn palette_distance_should_handle_empty_palette() {
+        let a = palette(&[((255, 0, 0), 1.0)]);
+        let empty = palette(&[]);
+        assert_eq!(palette_distance(&a, &empty), f64::MAX);
+        assert_eq!(palette_distance(&empty, &a), f64::MAX);
+        assert_eq!(palette_distance(&empty, &empty), 0.0);
+    }
+}
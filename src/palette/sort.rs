@@ -0,0 +1,38 @@
+use crate::color::lab::Lab;
+use crate::color::lch::Lch;
+use crate::color::white_point::D65;
+use crate::color::xyz::XYZ;
+use crate::math::number::Float;
+use crate::swatch::Swatch;
+
+/// Key to sort the swatches of a palette by.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SwatchSortKey {
+    /// The percentage of the swatch, which is the default order of swatches.
+    #[default]
+    Percentage,
+    /// The relative luminance of the swatch color.
+    Luminance,
+    /// The CIE LCh hue angle of the swatch color.
+    Hue,
+    /// The CIE LCh chroma of the swatch color.
+    Vibrance,
+}
+
+impl SwatchSortKey {
+    /// Return the value of the given swatch for this key.
+    #[must_use]
+    pub(crate) fn value<F: Float>(&self, swatch: &Swatch<F>) -> F {
+        if *self == SwatchSortKey::Percentage {
+            return swatch.percentage();
+        }
+
+        let xyz: XYZ<F, D65> = XYZ::from(&swatch.color());
+        match self {
+            SwatchSortKey::Luminance => xyz.y,
+            SwatchSortKey::Hue => Lch::from(&Lab::from(&xyz)).h,
+            SwatchSortKey::Vibrance => Lch::from(&Lab::from(&xyz)).c,
+            SwatchSortKey::Percentage => unreachable!("Percentage is returned without conversion"),
+        }
+    }
+}
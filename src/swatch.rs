@@ -1,17 +1,171 @@
+use crate::color::approx::ApproxEq;
+use crate::color::difference::ciede2000;
+use crate::color::lab::Lab;
+use crate::color::rgba::Rgba;
+use crate::color::space::{convert, ColorSpace};
+use crate::color::white_point::D65;
+use crate::color::xyz::XYZ;
 use crate::math::number::Float;
 use std::cmp::Ordering;
+use std::fmt::{Display, Formatter, Result};
 
 /// Color swatch.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Swatch<F: Float> {
-    /// The representative color.
-    pub color: (u8, u8, u8),
+    color: Rgba,
+    position: (u32, u32),
+    population: usize,
+    percentage: F,
+    spread: F,
+    name: Option<&'static str>,
+}
+
+impl<F> Swatch<F>
+where
+    F: Float,
+{
+    /// Create a new swatch.
+    #[must_use]
+    pub(crate) fn new(color: Rgba, position: (u32, u32), population: usize, percentage: F) -> Self {
+        Self {
+            color,
+            position,
+            population,
+            percentage,
+            spread: F::zero(),
+            name: None,
+        }
+    }
+
+    /// Set the percentage of this swatch.
+    #[must_use]
+    pub(crate) fn with_percentage(mut self, percentage: F) -> Self {
+        self.percentage = percentage;
+        self
+    }
 
-    /// The position of this swatch.
-    pub position: (u32, u32),
+    /// Set the spread of this swatch.
+    #[must_use]
+    pub(crate) fn with_spread(mut self, spread: F) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Set the position of this swatch.
+    #[must_use]
+    pub(crate) fn with_position(mut self, position: (u32, u32)) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Set the name of this swatch.
+    #[must_use]
+    pub(crate) fn with_name(mut self, name: &'static str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Return the representative color.
+    #[must_use]
+    pub fn color(&self) -> Rgba {
+        self.color.clone()
+    }
 
-    /// The percentage of this swatch.
-    pub percentage: F,
+    /// Return the representative color converted to the given color space.
+    #[must_use]
+    pub fn color_as<C>(&self) -> C
+    where
+        C: ColorSpace<F>,
+    {
+        convert(&self.color)
+    }
+
+    /// Return the position of this swatch.
+    #[must_use]
+    pub fn position(&self) -> (u32, u32) {
+        self.position
+    }
+
+    /// Return the number of pixels belonging to this swatch.
+    #[must_use]
+    pub fn population(&self) -> usize {
+        self.population
+    }
+
+    /// Return the percentage of this swatch.
+    #[must_use]
+    pub fn percentage(&self) -> F {
+        self.percentage
+    }
+
+    /// Return the human-readable name of this swatch if named.
+    ///
+    /// See [`Palette::with_names`](crate::palette::Palette::with_names).
+    #[must_use]
+    pub fn name(&self) -> Option<&'static str> {
+        self.name
+    }
+
+    /// Return whether the color of this swatch is within the given CIEDE2000 color difference of
+    /// the center color.
+    #[must_use]
+    pub fn is_within(&self, center: &Rgba, delta_e: F) -> bool {
+        let lab1: Lab<F> = Lab::from(&XYZ::<F, D65>::from(&self.color));
+        let lab2: Lab<F> = Lab::from(&XYZ::<F, D65>::from(center));
+        ciede2000(&lab1, &lab2) <= delta_e
+    }
+
+    /// Return the spread of the colors belonging to this swatch.
+    ///
+    /// The spread is the mean squared distance of the member colors to the swatch color in the
+    /// normalized CIE L*a*b* color space. A high spread indicates a color that varies a lot within
+    /// the swatch.
+    #[must_use]
+    pub fn spread(&self) -> F {
+        self.spread
+    }
+}
+
+impl<F> ApproxEq for Swatch<F>
+where
+    F: Float,
+{
+    type Epsilon = F;
+
+    fn default_epsilon() -> F {
+        F::from_f64(1e-6)
+    }
+
+    /// Return whether the color channels scaled to [0, 1], the percentage and the spread are within
+    /// `epsilon` of the other swatch, and the position, population and name are equal.
+    fn approx_eq(&self, other: &Self, epsilon: F) -> bool {
+        let max = Rgba::max_value::<F>();
+        let channel_eq = |a: u8, b: u8| F::from_u8(a.abs_diff(b)) / max <= epsilon;
+        channel_eq(self.color.r, other.color.r)
+            && channel_eq(self.color.g, other.color.g)
+            && channel_eq(self.color.b, other.color.b)
+            && channel_eq(self.color.a, other.color.a)
+            && self.position == other.position
+            && self.population == other.population
+            && self.name == other.name
+            && (self.percentage - other.percentage).abs() <= epsilon
+            && (self.spread - other.spread).abs() <= epsilon
+    }
+}
+
+impl<F> Display for Swatch<F>
+where
+    F: Float,
+{
+    /// Format this swatch as its hex color, percentage and position, e.g. `#ff8000 25.0% @ (1, 2)`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let percentage = self.percentage.to_f64().unwrap_or(f64::NAN) * 100.0;
+        write!(
+            f,
+            "#{:02x}{:02x}{:02x} {:.1}% @ ({}, {})",
+            self.color.r, self.color.g, self.color.b, percentage, self.position.0, self.position.1
+        )
+    }
 }
 
 impl<F> Eq for Swatch<F> where F: Float {}
@@ -21,7 +175,7 @@ where
     F: Float,
 {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.percentage.partial_cmp(&other.percentage)
+        Some(self.cmp(other))
     }
 }
 
@@ -30,8 +184,82 @@ where
     F: Float,
 {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.percentage
-            .partial_cmp(&other.percentage)
-            .unwrap_or(Ordering::Equal)
+        self.percentage.total_order(&other.percentage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_should_create_swatch() {
+        let swatch = Swatch::new(Rgba::new(255, 128, 0, 255), (12, 34), 120, 0.25);
+        assert_eq!(swatch.color(), Rgba::new(255, 128, 0, 255));
+        assert_eq!(swatch.position(), (12, 34));
+        assert_eq!(swatch.population(), 120);
+        assert_eq!(swatch.percentage(), 0.25);
+        assert_eq!(swatch.spread(), 0.0);
+        assert_eq!(swatch.name(), None);
+
+        let swatch = swatch.with_spread(0.125).with_name("orange");
+        assert_eq!(swatch.spread(), 0.125);
+        assert_eq!(swatch.name(), Some("orange"));
+    }
+
+    #[test]
+    fn color_as_should_convert_color() {
+        let swatch: Swatch<f64> = Swatch::new(Rgba::new(255, 0, 0, 255), (0, 0), 10, 0.5);
+        let lab: Lab<f64> = swatch.color_as();
+        assert!((lab.l - 53.24).abs() < 0.01);
+        assert!((lab.a - 80.09).abs() < 0.01);
+        assert!((lab.b - 67.20).abs() < 0.01);
+
+        let rgba: Rgba = swatch.color_as();
+        assert_eq!(rgba, swatch.color());
+    }
+
+    #[test]
+    fn to_string_should_format_swatch() {
+        let swatch = Swatch::new(Rgba::new(255, 128, 0, 255), (12, 34), 120, 0.234);
+        assert_eq!(swatch.to_string(), "#ff8000 23.4% @ (12, 34)");
+    }
+
+    #[test]
+    fn cmp_should_compare_percentage() {
+        let swatch1 = Swatch::new(Rgba::new(255, 0, 0, 255), (0, 0), 10, 0.1);
+        let swatch2 = Swatch::new(Rgba::new(0, 0, 255, 255), (0, 0), 30, 0.3);
+        assert_eq!(swatch1.cmp(&swatch2), Ordering::Less);
+        assert_eq!(swatch2.cmp(&swatch1), Ordering::Greater);
+        assert_eq!(swatch1.cmp(&swatch1.clone()), Ordering::Equal);
+    }
+
+    #[test]
+    fn is_within_should_compare_color_difference() {
+        let swatch = Swatch::new(Rgba::new(0, 0, 255, 255), (0, 0), 10, 0.5);
+        assert!(swatch.is_within(&Rgba::new(0, 0, 255, 255), 0.0));
+        assert!(swatch.is_within(&Rgba::new(10, 10, 240, 255), 5.0));
+        assert!(!swatch.is_within(&Rgba::new(255, 0, 0, 255), 5.0));
+    }
+
+    #[test]
+    fn approx_eq_should_compare_swatches_within_tolerance() {
+        let swatch = Swatch::new(Rgba::new(255, 128, 0, 255), (12, 34), 120, 0.25);
+        assert!(swatch.approx_eq(
+            &Swatch::new(Rgba::new(254, 128, 1, 255), (12, 34), 120, 0.2500001),
+            0.01
+        ));
+        assert!(!swatch.approx_eq(
+            &Swatch::new(Rgba::new(200, 128, 0, 255), (12, 34), 120, 0.25),
+            0.01
+        ));
+        assert!(!swatch.approx_eq(
+            &Swatch::new(Rgba::new(255, 128, 0, 255), (12, 34), 120, 0.3),
+            0.01
+        ));
+        assert!(!swatch.approx_eq(
+            &Swatch::new(Rgba::new(255, 128, 0, 255), (0, 0), 120, 0.25),
+            0.01
+        ));
     }
 }
@@ -0,0 +1,49 @@
+use crate::image::ImageData;
+use crate::palette::Palette;
+use wasm_bindgen::prelude::*;
+
+/// Extract a palette from the given RGBA pixels for use from JavaScript.
+///
+/// Each swatch color is packed into a `u32` as `0xRRGGBBAA`, ordered from the most dominant
+/// swatch, and at most `max_colors` colors are returned.
+#[wasm_bindgen]
+#[must_use]
+pub fn extract_palette(data: &[u8], width: u32, height: u32, max_colors: usize) -> Vec<u32> {
+    let image_data = ImageData::new(data, width, height);
+    let palette: Palette<f64> = image_data.extract();
+    palette
+        .iter()
+        .rev()
+        .take(max_colors)
+        .map(|swatch| {
+            let color = swatch.color();
+            u32::from_be_bytes([color.r, color.g, color.b, color.a])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_palette_should_return_packed_colors() {
+        let mut data = Vec::with_capacity(128 * 128 * 4);
+        for _ in 0..128 {
+            for x in 0..128 {
+                if x < 96 {
+                    data.extend_from_slice(&[255, 0, 0, 255]);
+                } else {
+                    data.extend_from_slice(&[0, 0, 255, 255]);
+                }
+            }
+        }
+
+        let colors = extract_palette(&data, 128, 128, 1);
+        assert_eq!(colors, vec![0xFF0000FF]);
+
+        let colors = extract_palette(&data, 128, 128, 8);
+        let decoded: Vec<[u8; 4]> = colors.iter().map(|color| color.to_be_bytes()).collect();
+        assert_eq!(decoded, vec![[255, 0, 0, 255], [0, 0, 255, 255]]);
+    }
+}
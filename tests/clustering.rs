@@ -0,0 +1,41 @@
+use auto_palette::clustering::{DBSCANParams, Fit, Kmeans, KmeansParams, DBSCAN};
+use auto_palette::{DistanceMetric, Initializer, Point3};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+fn dataset() -> Vec<Point3<f64>> {
+    let mut dataset = Vec::new();
+    for (x, y, z) in [(0.0, 0.0, 0.0), (10.0, 10.0, 10.0)] {
+        for i in 0..5 {
+            let offset = i as f64 * 0.1;
+            dataset.push(Point3(x + offset, y, z - offset));
+        }
+    }
+    dataset
+}
+
+#[test]
+fn dbscan() {
+    let dataset = dataset();
+    let params = DBSCANParams::new(3, 0.5, DistanceMetric::Euclidean);
+    let dbscan = DBSCAN::fit(&dataset, &params);
+    assert_eq!(dbscan.centroids().len(), 2);
+    assert_eq!(dbscan.members_at(0), &[0, 1, 2, 3, 4]);
+    assert_eq!(dbscan.members_at(1), &[5, 6, 7, 8, 9]);
+    assert!(dbscan.outliers().is_empty());
+}
+
+#[test]
+fn kmeans() {
+    let dataset = dataset();
+    let initializer = Initializer::KmeansPlusPlus(StdRng::seed_from_u64(0));
+    let params = KmeansParams::new(2, DistanceMetric::SquaredEuclidean, initializer);
+    let kmeans = Kmeans::fit(&dataset, &params);
+    assert_eq!(kmeans.count_at(0), 5);
+    assert_eq!(kmeans.count_at(1), 5);
+
+    let labels = kmeans.labels();
+    assert!(labels[..5].iter().all(|&label| label == labels[0]));
+    assert!(labels[5..].iter().all(|&label| label == labels[5]));
+    assert_ne!(labels[0], labels[5]);
+}
@@ -1,6 +1,8 @@
 extern crate image;
 
 use auto_palette::image::ImageData;
+use auto_palette::options::ExtractOptions;
+use auto_palette::palette::Palette;
 use auto_palette::swatch::Swatch;
 
 #[test]
@@ -8,10 +10,65 @@ fn extract() {
     let img = image::open("./tests/images/flag_gr.png").unwrap();
     let data = img.to_rgba8().to_vec();
     let image_data = ImageData::new(&data, img.width(), img.height());
-    let swatches: Vec<Swatch<f64>> = image_data
-        .extract()
-        .into_iter()
-        .filter(|swatch| swatch.percentage > 0.05)
+    let palette: Palette<f64> = image_data.extract();
+    assert_eq!(
+        palette.to_snapshot_string(),
+        include_str!("./snapshots/flag_gr.txt")
+    );
+}
+
+#[test]
+fn types_should_be_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ImageData<'static>>();
+    assert_send_sync::<Palette<f32>>();
+    assert_send_sync::<Palette<f64>>();
+    assert_send_sync::<Swatch<f64>>();
+}
+
+#[test]
+fn extract_should_run_on_multiple_threads() {
+    let img = image::open("./tests/images/flag_gr.png").unwrap();
+    let flag = img.to_rgba8().to_vec();
+    let (flag_width, flag_height) = (img.width(), img.height());
+    let stripes: Vec<u8> = (0..64 * 64)
+        .flat_map(|i| {
+            if (i % 64) < 32 {
+                [255, 0, 0, 255]
+            } else {
+                [0, 0, 255, 255]
+            }
+        })
         .collect();
-    println!("{:?}", swatches)
+    let images = [
+        ImageData::new(&flag, flag_width, flag_height),
+        ImageData::new(&stripes, 64, 64),
+    ];
+
+    let expected: Vec<String> = images
+        .iter()
+        .map(|image_data| image_data.extract::<f64>().to_snapshot_string())
+        .collect();
+    let actual: Vec<String> = std::thread::scope(|scope| {
+        let handles: Vec<_> = images
+            .iter()
+            .map(|image_data| scope.spawn(move || image_data.extract::<f64>().to_snapshot_string()))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    });
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn logo_should_extract_fewer_swatches_than_photo() {
+    let img = image::open("./tests/images/flag_gr.png").unwrap();
+    let data = img.to_rgba8().to_vec();
+    let image_data = ImageData::new(&data, img.width(), img.height());
+    let logo: Palette<f64> = image_data.extract_with(&ExtractOptions::logo());
+    let photo: Palette<f64> = image_data.extract_with(&ExtractOptions::photo());
+    assert!(!logo.is_empty());
+    assert!(logo.len() < photo.len());
 }